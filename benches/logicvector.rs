@@ -39,7 +39,7 @@ fn create_from_str(b: &mut Bencher) {
 fn create_width(b: &mut Bencher) {
     b.iter(|| {
         for i in 0..NITER {
-            bb(LogicVector::with_width(((i % 128) + 1) as u8));
+            bb(LogicVector::with_width(((i % 128) + 1) as usize));
         }
     })
 }
@@ -52,3 +52,35 @@ fn to_u128(b: &mut Bencher) {
         }
     })
 }
+
+#[bench]
+fn clone_narrow(b: &mut Bencher) {
+    let v = LogicVector::from_int(0xDEAD_BEEF, 64).unwrap();
+    b.iter(|| {
+        for _ in 0..NITER {
+            bb(bb(&v).clone());
+        }
+    })
+}
+
+#[bench]
+fn and_narrow(b: &mut Bencher) {
+    let lhs = LogicVector::from_int(0xDEAD_BEEF, 64).unwrap();
+    let rhs = LogicVector::from_int(0xCAFE_BABE, 64).unwrap();
+    b.iter(|| {
+        for _ in 0..NITER {
+            bb(bb(&lhs) & bb(&rhs));
+        }
+    })
+}
+
+#[bench]
+fn add_narrow(b: &mut Bencher) {
+    let lhs = LogicVector::from_int(0xDEAD_BEEF, 64).unwrap();
+    let rhs = LogicVector::from_int(0xCAFE_BABE, 64).unwrap();
+    b.iter(|| {
+        for _ in 0..NITER {
+            bb(bb(&lhs) + bb(&rhs));
+        }
+    })
+}
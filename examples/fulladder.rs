@@ -85,11 +85,11 @@ fn main() {
     circuit.add_updater(&s_ha2_o);
     circuit.add_updater(&s_or_cout);
     circuit.add_updater(&s_ha_s);
-    circuit.add_updater(&and1);
-    circuit.add_updater(&and2);
-    circuit.add_updater(&xor1);
-    circuit.add_updater(&xor2);
-    circuit.add_updater(&or);
+    circuit.add(and1);
+    circuit.add(and2);
+    circuit.add(xor1);
+    circuit.add(xor2);
+    circuit.add(or);
 
     const _0: Ieee1164 = Ieee1164::_0; // this helps to keep the
     const _1: Ieee1164 = Ieee1164::_1; // matrix clean and short
@@ -110,27 +110,16 @@ fn main() {
         y.replace(triple[1]);
         c.replace(triple[2]);
 
-        let mut cycle_count = 1;
-        while circuit.tick() && cycle_count < 11 {
-            cycle_count += 1;
-        }
-
-        // If the number of required ticks is known, it is possible to use
-        // a loop with a fixed number of iterations
-        // ```
-        // for _ in 0..3 {
-        //     circuit.tick();
-        // }
-        // ```
+        let deltas = circuit.step().unwrap();
 
         println!(
-            "{} + {} + {} = {}{} (used {} cycles to reach a stable circuit)",
+            "{} + {} + {} = {}{} (used {} delta cycles to reach a stable circuit)",
             triple[0],
             triple[1],
             triple[2],
             cout.value(),
             s.value(),
-            cycle_count
+            deltas
         );
         assert_eq!(triple[3], s.value());
         assert_eq!(triple[4], cout.value());
@@ -1,2 +1,62 @@
+//! Copies the content of a ROM into a RAM, one byte every 10ps.
+
+use logical::direction::Output;
+use logical::models::{
+    inputs::{Clock, Pattern},
+    rtlib::memory::{Ram, Rom1kx8},
+};
+use logical::{signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+
 fn main() {
+    let rom = Rom1kx8::try_from_iter((0..=255).rev().cycle()).unwrap();
+    let ram = Ram::new(10, 8).unwrap();
+
+    //every address is applied for 10ps
+    let addresses = (0..1024)
+        .map(|a| LogicVector::from_int(a, 10).unwrap())
+        .collect();
+    let addr = Pattern::once(addresses, 10).unwrap();
+
+    //the write enable is pulled low in the second half of each address
+    let n_write_enable = Clock::new(10, 5, 0).unwrap();
+
+    let enable = Port::<_, Output>::new(Ieee1164::_0);
+    let disable = Port::<_, Output>::new(Ieee1164::_1);
+
+    let mut circuit = Circuit::default();
+    circuit.add(signal!(addr.data, rom.addr, ram.addr));
+    circuit.add(signal!(rom.data, ram.data_in));
+    circuit.add(signal!(n_write_enable.clk, ram.n_write_enable));
+    circuit.add(signal!(
+        enable,
+        rom.n_chip_select,
+        rom.n_output_enable,
+        ram.n_chip_select
+    ));
+    circuit.add(signal!(disable, ram.n_output_enable));
+    circuit.add(addr);
+    circuit.add(n_write_enable);
+    let rom = circuit.add(rom);
+    let ram = circuit.add(ram);
+
+    circuit.run_until(1024 * 10).unwrap();
+
+    let copied = circuit[ram]
+        .memory
+        .iter()
+        .zip(circuit[rom].memory.iter())
+        .filter(|(r, b)| r == b)
+        .count();
+    println!("copied {} of 1024 bytes in {}ps", copied, circuit.now());
+    for (i, line) in circuit[ram].memory.chunks(16).take(4).enumerate() {
+        let bytes: Vec<String> = line
+            .iter()
+            .map(|b| {
+                b.as_u128()
+                    .map_or_else(|| "xx".to_string(), |v| format!("{:02x}", v))
+            })
+            .collect();
+        println!("{:03x}: {}", i * 16, bytes.join(" "));
+    }
+    assert_eq!(1024, copied);
 }
@@ -4,13 +4,13 @@ use logical::models::{
     inputs::Switch,
     outputs::Led,
 };
-use logical::{circuit, signal, Circuit, Ieee1164, Signal};
+use logical::{circuit, signal, Circuit, Delay, Ieee1164, Signal};
 
 fn main() {
     let mut val = Ieee1164::_1;
 
-    let xor = XorGate::default();
-    let mux = Mux::default();
+    let xor = XorGate::with_delay(Delay::inertial(3, 2));
+    let mux = Mux::with_delay(Delay::inertial(1, 1));
     let mut input1 = Switch::new(val);
     let input2 = Switch::new(Ieee1164::_1);
     let mut mux_switch = Switch::new(Ieee1164::_0);
@@ -23,7 +23,6 @@ fn main() {
     let sig_mux_xor = signal!(mux.z, xor.b);
 
     let mut circuit = circuit!(
-        xor,
         mux,
         sig_input_signal,
         sig_input_mux,
@@ -31,21 +30,17 @@ fn main() {
         sig_rec,
         sig_mux_xor
     );
+    let xor = circuit.add(xor);
 
-    circuit.tick();
-    circuit.tick();
-    circuit.tick();
+    circuit.run_until(10).unwrap();
     mux_switch.replace(Ieee1164::_1);
-    circuit.tick();
 
     let mut dumper = Vcd::new("VCD Example");
 
     for i in 0..90 {
-        dumper.serialize_ports(&xor);
-        circuit.tick();
-        circuit.tick();
-        dumper.tick();
-        dumper.tick();
+        circuit.run_until(10 + i).unwrap();
+        dumper.set_time(circuit.now());
+        dumper.serialize_ports(&circuit[xor]);
         if i % 20 == 0 {
             val = !val;
             input1.replace(val);
@@ -0,0 +1,199 @@
+#![warn(missing_docs)]
+
+//! Derive macros for [logical](https://docs.rs/logical). You should not depend on this crate
+//! directly, but use the re-exports of `logical` instead, e.g. `logical::Component`.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Result};
+
+/// Derives `logical::Component` for a struct with named fields.
+///
+/// Every field that is marked with `#[port(input)]`, `#[port(output)]` or `#[port(inout)]` is
+/// listed in `Component::ports`, in the order of declaration. The direction has to match the one
+/// of the `Port`, otherwise compilation fails. Every field that is marked with `#[component]` is
+/// listed in `Component::children`. All other fields are ignored.
+///
+/// By default the name of the field is used, which can be changed with `name = "..."`, e.g.
+/// `#[port(output, name = "q_n")]` or `#[component(name = "lower")]`.
+///
+/// Additionally the private inherent methods `input_ids` and `output_ids` are generated, which
+/// return the `PortId`s of all ports that are read (`input` and `inout`) or written (`output` and
+/// `inout`). They are meant to implement `Updateable::sensitivity` and `Updateable::drives`.
+#[proc_macro_derive(Component, attributes(port, component))]
+pub fn derive_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_component(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+enum Member {
+    Port {
+        name: String,
+        direction: TokenStream2,
+        reads: bool,
+        writes: bool,
+    },
+    Child {
+        name: String,
+    },
+}
+
+fn parse_member(field: &syn::Field) -> Result<Option<Member>> {
+    let mut member = None;
+    for attr in &field.attrs {
+        let is_port = attr.path().is_ident("port");
+        let is_child = attr.path().is_ident("component");
+        if !is_port && !is_child {
+            continue;
+        }
+        if member.is_some() {
+            return Err(Error::new(
+                attr.span(),
+                "a field can only be a single port or component",
+            ));
+        }
+
+        let mut name = field
+            .ident
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let mut direction = None;
+        if is_port || matches!(attr.meta, syn::Meta::List(_)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    return Ok(());
+                }
+                if is_port {
+                    for dir in &["input", "output", "inout"] {
+                        if meta.path.is_ident(dir) {
+                            if direction.is_some() {
+                                return Err(meta.error("the direction of a port is already given"));
+                            }
+                            direction = Some(*dir);
+                            return Ok(());
+                        }
+                    }
+                }
+                Err(meta.error("unknown attribute argument"))
+            })?;
+        }
+
+        member = Some(if is_port {
+            let (direction, reads, writes) = match direction {
+                Some("input") => (quote!(::logical::direction::Input), true, false),
+                Some("output") => (quote!(::logical::direction::Output), false, true),
+                Some(_) => (quote!(::logical::direction::InOut), true, true),
+                None => {
+                    return Err(Error::new(
+                        attr.span(),
+                        "expected a direction: `input`, `output` or `inout`",
+                    ))
+                }
+            };
+            Member::Port {
+                name,
+                direction,
+                reads,
+                writes,
+            }
+        } else {
+            Member::Child { name }
+        });
+    }
+    Ok(member)
+}
+
+fn expand_component(input: &DeriveInput) -> Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "Component can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "Component can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut ports = vec![];
+    let mut inputs = vec![];
+    let mut outputs = vec![];
+    let mut children = vec![];
+    for field in fields {
+        let ident = &field.ident;
+        match parse_member(field)? {
+            Some(Member::Port {
+                name,
+                direction,
+                reads,
+                writes,
+            }) => {
+                ports.push(quote! {{
+                    let port: &::logical::Port<_, #direction> = &self.#ident;
+                    ::logical::component::PortInfo::new(#name, port)
+                }});
+                if reads {
+                    inputs.push(quote!(self.#ident.id()));
+                }
+                if writes {
+                    outputs.push(quote!(self.#ident.id()));
+                }
+            }
+            Some(Member::Child { name }) => children.push(quote! {
+                (::std::string::String::from(#name), &self.#ident as &dyn ::logical::Component)
+            }),
+            None => {}
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let children = if children.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            fn children(&self) -> ::std::vec::Vec<(::std::string::String, &dyn ::logical::Component)> {
+                ::std::vec![#(#children),*]
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::logical::Component for #name #ty_generics #where_clause {
+            fn ports(&self) -> ::std::vec::Vec<::logical::component::PortInfo> {
+                ::std::vec![#(#ports),*]
+            }
+
+            #children
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the ids of all ports that are read, in the order of declaration.
+            #[allow(dead_code)]
+            fn input_ids(&self) -> ::std::vec::Vec<::logical::PortId> {
+                ::std::vec![#(#inputs),*]
+            }
+
+            /// Returns the ids of all ports that are written, in the order of declaration.
+            #[allow(dead_code)]
+            fn output_ids(&self) -> ::std::vec::Vec<::logical::PortId> {
+                ::std::vec![#(#outputs),*]
+            }
+        }
+    })
+}
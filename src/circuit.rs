@@ -1,21 +1,208 @@
-use crate::Updateable;
+use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+use crate::{PortId, Time, Updateable};
+
+/// Errors that can occur while simulating a [`Circuit`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// The circuit did not become stable within the delta limit.
+    Oscillation {
+        /// The simulated time at which the circuit started to oscillate
+        time: Time,
+        /// The number of delta cycles that were processed
+        deltas: usize,
+        /// The [names](Updateable::name) of the elements that kept changing during the last half
+        /// of the delta cycles, including [`Signal`](crate::Signal)s.
+        updaters: Vec<String>,
+    },
+    /// The circuit contains combinational loops. Each loop is described by the
+    /// [names](Updateable::name) of the elements that form it, including
+    /// [`Signal`](crate::Signal)s.
+    CombinationalLoop(Vec<Vec<String>>),
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CircuitError::Oscillation {
+                time,
+                deltas,
+                updaters,
+            } => write!(
+                f,
+                "circuit did not settle after {} delta cycles at {}ps, still changing: {}",
+                deltas,
+                time,
+                updaters.join(", ")
+            ),
+            CircuitError::CombinationalLoop(loops) => {
+                write!(f, "circuit contains {} combinational loop(s):", loops.len())?;
+                for l in loops {
+                    write!(f, " [{}]", l.join(" -> "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+/// An [`Updateable`] that can be downcasted to its concrete type again.
+trait AnyUpdateable: Updateable {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Updateable + 'static> AnyUpdateable for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A `Handle` refers to a model that is owned by a [`Circuit`]. It is returned by [`Circuit::add`]
+/// and can be used to access the model again, e.g. to read or write its ports.
+///
+/// A `Handle` is only valid for the `Circuit` that has created it.
+pub struct Handle<T> {
+    idx: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Handle({})", self.idx)
+    }
+}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Handle<T>) -> bool {
+        self.idx == other.idx
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+/// The connections between the elements of a [`Circuit`], collected from their
+/// [sensitivity](Updateable::sensitivity) lists.
+#[derive(Debug, Default)]
+struct Readers {
+    /// The elements that read from a port
+    ports: HashMap<PortId, Vec<usize>>,
+    /// The elements with an unknown sensitivity, which are evaluated on every step
+    always: Vec<usize>,
+    /// The [wiring generation](Updateable::wiring_generation) of every element at collection time
+    wiring: Vec<usize>,
+}
+
+impl Readers {
+    fn collect(updater: &[Box<dyn AnyUpdateable>]) -> Self {
+        let mut readers = Readers::default();
+        for (idx, updater) in updater.iter().enumerate() {
+            let sensitivity = updater.sensitivity();
+            if sensitivity.is_empty() && !updater.time_driven() {
+                readers.always.push(idx);
+            }
+            for port in sensitivity {
+                readers.ports.entry(port).or_default().push(idx);
+            }
+            readers.wiring.push(updater.wiring_generation());
+        }
+        readers
+    }
+
+    /// Checks if any element has been rewired since the readers have been collected.
+    fn is_stale(&self, updater: &[Box<dyn AnyUpdateable>]) -> bool {
+        updater
+            .iter()
+            .map(|u| u.wiring_generation())
+            .ne(self.wiring.iter().cloned())
+    }
+}
 
 /// A `Circuit` is a combination of connected logic elements
 ///
-/// A `Circuit` holds references of [`Updateable`](Updateable) structs
-/// ([`Port`](crate::port::Port), ...), which are updated on every call of
-/// tick.
-#[derive(Default)]
+/// A `Circuit` owns [`Updateable`](Updateable) structs (models, [`Signal`](crate::Signal)s, ...),
+/// which are updated on every call of tick. Models are moved into the `Circuit` with
+/// [`Circuit::add`] and can be accessed afterwards through the returned [`Handle`].
+///
+/// The `Circuit` also keeps track of the simulated [`Time`]. Elements that have scheduled a value
+/// change for the future (e.g. because they have a [`Delay`](crate::Delay)) are kept in a
+/// time-ordered queue and will be woken up by [`Circuit::run_until`] or [`Circuit::advance`].
+///
+/// If the circuit does not become stable (e.g. because of a ring oscillator without any delays),
+/// the simulation is stopped after [`Circuit::delta_limit`] delta cycles and a
+/// [`CircuitError::Oscillation`] is returned.
+///
+/// The connections between the elements are collected once from their
+/// [sensitivity](Updateable::sensitivity) lists and are cached until another element is added, an
+/// element is accessed mutably through its [`Handle`] or an element reports a new
+/// [wiring generation](Updateable::wiring_generation), e.g. a [`Signal`](crate::Signal) that has
+/// been connected to another [`Port`](crate::Port).
 pub struct Circuit {
-    updater: Vec<Box<dyn Updateable>>,
+    updater: Vec<Box<dyn AnyUpdateable>>,
+    readers: Option<Readers>,
+    generations: HashMap<PortId, usize>,
+    initialized: bool,
+    now: Time,
+    queue: BTreeMap<Time, BTreeSet<usize>>,
+    delta_limit: usize,
+}
+
+impl Default for Circuit {
+    fn default() -> Self {
+        Circuit {
+            updater: vec![],
+            readers: None,
+            generations: HashMap::new(),
+            initialized: false,
+            now: 0,
+            queue: BTreeMap::new(),
+            delta_limit: Circuit::DEFAULT_DELTA_LIMIT,
+        }
+    }
 }
 
 impl Circuit {
+    /// The number of delta cycles after which a `Circuit` is considered to oscillate, if not set
+    /// otherwise with [`Circuit::set_delta_limit`].
+    pub const DEFAULT_DELTA_LIMIT: usize = 1_000;
+
+    /// Returns the maximum number of delta cycles a [`Circuit::step`] may take.
+    pub fn delta_limit(&self) -> usize {
+        self.delta_limit
+    }
+
+    /// Sets the maximum number of delta cycles a [`Circuit::step`] may take, before the circuit is
+    /// considered to oscillate. The default is [`Circuit::DEFAULT_DELTA_LIMIT`].
+    pub fn set_delta_limit(&mut self, limit: usize) {
+        self.delta_limit = limit;
+    }
+
     /// The update tick function
     /// This function propagates the logic values by one Updateable element.
     ///
     /// Returns `true` as long as at least one element's output value has changed in the circuit.
     ///
+    /// Every element is updated on every call, regardless whether its inputs have changed or not.
+    /// Prefer [`Circuit::step`], which only evaluates the elements that are affected by a change.
+    ///
     /// ```
     /// use logical::{Circuit, Signal};
     /// use logical::models::gates::OrGate;
@@ -32,23 +219,748 @@ impl Circuit {
         self.updater.iter_mut().fold(false, |acc, u| acc | u.update())
     }
 
-    /// Add an [`Updateable`](Updateable) to the `Circuit`
+    /// Returns the current simulated [`Time`] of this `Circuit`.
+    pub fn now(&self) -> Time {
+        self.now
+    }
+
+    /// Returns the point in simulated time of the next scheduled event, if there is any.
+    pub fn next_event(&self) -> Option<Time> {
+        self.queue.keys().next().cloned()
+    }
+
+    /// Propagates all changes through the circuit until it is stable again. The simulated time
+    /// does not advance.
+    ///
+    /// Only those elements are evaluated, whose [sensitivity](Updateable::sensitivity) contains a
+    /// [`Port`](crate::Port) that has changed since the last call, or that have scheduled an event
+    /// for the current time. Every element that changes its outputs schedules the elements reading
+    /// from them for the next delta cycle. This is repeated until no further changes happen. On the
+    /// very first call every element is evaluated once. Elements with an unknown sensitivity are
+    /// evaluated on every call, unless they are [time-driven](Updateable::time_driven).
+    ///
+    /// Returns the number of delta cycles it took to reach a stable state, which is `0` if nothing
+    /// had to be done at all. If the circuit is not stable after [`Circuit::delta_limit`] delta
+    /// cycles, a [`CircuitError::Oscillation`] is returned instead. The elements that were still
+    /// pending are scheduled for the current time again, so a following call picks up where this
+    /// one stopped.
+    ///
+    /// ```
+    /// use logical::{Circuit, Ieee1164, Signal};
+    /// use logical::direction::{Input, Output};
+    /// use logical::models::gates::Inverter;
+    /// use logical::Port;
+    ///
+    /// let mut input = Port::<_, Output>::new(Ieee1164::_0);
+    /// let output = Port::<_, Input>::default();
+    /// let inv = Inverter::default();
+    ///
+    /// let mut sig_in = Signal::default();
+    /// sig_in.connect(&input).unwrap();
+    /// sig_in.connect(&inv.a).unwrap();
+    /// let mut sig_out = Signal::default();
+    /// sig_out.connect(&inv.z).unwrap();
+    /// sig_out.connect(&output).unwrap();
+    ///
+    /// let mut circuit = Circuit::default();
+    /// circuit.add(sig_in);
+    /// circuit.add(inv);
+    /// circuit.add(sig_out);
+    ///
+    /// assert_eq!(Ok(2), circuit.step());
+    /// assert_eq!(Ieee1164::_1, output.value());
+    /// assert_eq!(Ok(0), circuit.step());
+    ///
+    /// input.replace(Ieee1164::_1);
+    /// assert_eq!(Ok(3), circuit.step());
+    /// assert_eq!(Ieee1164::_0, output.value());
+    /// ```
+    pub fn step(&mut self) -> Result<usize, CircuitError> {
+        let readers = match self.readers.take() {
+            Some(readers) if !readers.is_stale(&self.updater) => readers,
+            _ => Readers::collect(&self.updater),
+        };
+
+        let mut pending = BTreeSet::new();
+        if self.initialized {
+            for (port, idxs) in &readers.ports {
+                if port.generation() != self.generations.get(port).cloned() {
+                    pending.extend(idxs);
+                }
+            }
+            pending.extend(&readers.always);
+        } else {
+            pending.extend(0..self.updater.len());
+            self.initialized = true;
+        }
+        if let Some(due) = self.queue.remove(&self.now) {
+            pending.extend(due);
+        }
+
+        let count = self.updater.len();
+        let mut deltas = 0;
+        // everything that changes in the second half of the allowed deltas is considered toggling
+        let mut toggling = BTreeSet::new();
+        while !pending.is_empty() {
+            if deltas == self.delta_limit {
+                break;
+            }
+            deltas += 1;
+            let mut next = BTreeSet::new();
+            for idx in pending {
+                let updater = &mut self.updater[idx];
+                if updater.update_at(self.now) {
+                    if deltas > self.delta_limit / 2 {
+                        toggling.insert(idx);
+                    }
+                    let drives = updater.drives();
+                    if drives.is_empty() {
+                        next.extend(0..count);
+                    }
+                    for port in drives {
+                        if let Some(idxs) = readers.ports.get(&port) {
+                            next.extend(idxs);
+                        }
+                    }
+                }
+                match updater.next_event() {
+                    Some(time) if time <= self.now => {
+                        next.insert(idx);
+                    }
+                    Some(time) => {
+                        self.queue.entry(time).or_default().insert(idx);
+                    }
+                    None => {}
+                }
+            }
+            pending = next;
+        }
+
+        self.generations = readers
+            .ports
+            .keys()
+            .filter_map(|port| port.generation().map(|g| (port.clone(), g)))
+            .collect();
+        self.readers = Some(readers);
+
+        if pending.is_empty() {
+            Ok(deltas)
+        } else {
+            self.queue.entry(self.now).or_default().extend(pending);
+            Err(CircuitError::Oscillation {
+                time: self.now,
+                deltas,
+                updaters: toggling
+                    .into_iter()
+                    .map(|idx| self.updater[idx].name())
+                    .collect(),
+            })
+        }
+    }
+
+    /// Advances the simulated time to the next scheduled event and processes it (see
+    /// [`Circuit::step`]). Pending changes at the current time are processed first.
+    ///
+    /// Returns the new time, or `None` if there is no scheduled event left. In that case the time
+    /// does not change.
+    pub fn advance(&mut self) -> Result<Option<Time>, CircuitError> {
+        self.step()?;
+        if let Some(time) = self.next_event() {
+            self.now = time;
+            self.step()?;
+            Ok(Some(time))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Processes every scheduled event up to and including `time` and sets the simulated time of
+    /// this `Circuit` to `time` afterwards. If the circuit starts to oscillate, the simulation stops
+    /// at the time the oscillation has been detected.
+    ///
+    /// ```
+    /// use logical::{Circuit, Delay, Ieee1164, Signal};
+    /// use logical::direction::{Input, Output};
+    /// use logical::models::gates::Inverter;
+    /// use logical::Port;
+    ///
+    /// let mut input = Port::<_, Output>::new(Ieee1164::_0);
+    /// let output = Port::<_, Input>::default();
+    /// let inv = Inverter::with_delay(Delay::inertial(10, 7));
+    ///
+    /// let mut sig_in = Signal::default();
+    /// sig_in.connect(&input).unwrap();
+    /// sig_in.connect(&inv.a).unwrap();
+    /// let mut sig_out = Signal::default();
+    /// sig_out.connect(&inv.z).unwrap();
+    /// sig_out.connect(&output).unwrap();
+    ///
+    /// let mut circuit = Circuit::default();
+    /// circuit.add(sig_in);
+    /// circuit.add(inv);
+    /// circuit.add(sig_out);
+    ///
+    /// circuit.run_until(9).unwrap();
+    /// assert_eq!(Ieee1164::_U, output.value());
+    /// circuit.run_until(10).unwrap();
+    /// assert_eq!(Ieee1164::_1, output.value());
+    ///
+    /// input.replace(Ieee1164::_1);
+    /// circuit.run_until(16).unwrap();
+    /// assert_eq!(Ieee1164::_1, output.value());
+    /// circuit.run_until(17).unwrap();
+    /// assert_eq!(Ieee1164::_0, output.value());
+    /// ```
+    pub fn run_until(&mut self, time: Time) -> Result<(), CircuitError> {
+        self.step()?;
+        while let Some(next) = self.next_event().filter(|&t| t <= time) {
+            self.now = next;
+            self.step()?;
+        }
+        self.now = self.now.max(time);
+        Ok(())
+    }
+
+    /// Searches the connections of this `Circuit` for combinational loops, i.e. elements whose
+    /// outputs influence their own inputs without anything in between that breaks the loop.
+    ///
+    /// The connections are taken from [`Updateable::combinational_inputs`] and
+    /// [`Updateable::drives`], so elements that do not report them are ignored. A feedback through
+    /// a clocked element, e.g. the data input of a flip-flop, is not a combinational loop.
+    /// Returns a [`CircuitError::CombinationalLoop`] if any loop has been found.
+    ///
+    /// ```
+    /// use logical::{Circuit, CircuitError, Signal};
+    /// use logical::models::gates::Inverter;
+    ///
+    /// let inv = Inverter::default();
+    /// let mut sig = Signal::named("feedback");
+    /// sig.connect(&inv.z).unwrap();
+    /// sig.connect(&inv.a).unwrap();
+    ///
+    /// let mut circuit = Circuit::default();
+    /// circuit.add(inv);
+    /// circuit.add(sig);
+    ///
+    /// match circuit.check_loops() {
+    ///     Err(CircuitError::CombinationalLoop(loops)) => {
+    ///         assert_eq!(1, loops.len());
+    ///         assert!(loops[0].contains(&"feedback".to_string()));
+    ///     }
+    ///     _ => panic!("loop has not been found"),
+    /// }
+    /// ```
+    pub fn check_loops(&self) -> Result<(), CircuitError> {
+        let mut readers: HashMap<PortId, Vec<usize>> = HashMap::new();
+        for (idx, updater) in self.updater.iter().enumerate() {
+            for port in updater.combinational_inputs() {
+                readers.entry(port).or_default().push(idx);
+            }
+        }
+        let edges: Vec<Vec<usize>> = self
+            .updater
+            .iter()
+            .map(|updater| {
+                let mut edges: Vec<usize> = updater
+                    .drives()
+                    .iter()
+                    .filter_map(|port| readers.get(port))
+                    .flatten()
+                    .cloned()
+                    .collect();
+                edges.sort_unstable();
+                edges.dedup();
+                edges
+            })
+            .collect();
+
+        let loops: Vec<Vec<String>> = strongly_connected(&edges)
+            .into_iter()
+            .filter(|c| c.len() > 1 || edges[c[0]].contains(&c[0]))
+            .map(|c| c.into_iter().map(|idx| self.updater[idx].name()).collect())
+            .collect();
+
+        if loops.is_empty() {
+            Ok(())
+        } else {
+            Err(CircuitError::CombinationalLoop(loops))
+        }
+    }
+
+    /// Adds an [`Updateable`](Updateable) to the `Circuit`, which takes the ownership of it.
+    ///
+    /// Returns a [`Handle`] which can be used to access the model afterwards.
+    ///
+    /// ```
+    /// use logical::{signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+    /// use logical::direction::{Input, Output};
+    /// use logical::models::rtlib::memory::Rom1kx8;
+    ///
+    /// let rom = Rom1kx8::try_from_iter((0..=255).cycle()).unwrap();
+    /// let mut addr = Port::<_, Output>::new(LogicVector::from_int(42, 10).unwrap());
+    /// let data = Port::<_, Input>::new(LogicVector::with_width(8));
+    /// let enable = Port::<_, Output>::new(Ieee1164::_0);
+    ///
+    /// let mut circuit = Circuit::default();
+    /// circuit.add(signal!(addr, rom.addr));
+    /// circuit.add(signal!(rom.data, data));
+    /// circuit.add(signal!(enable, rom.n_chip_select, rom.n_output_enable));
+    /// let rom = circuit.add(rom);
+    ///
+    /// circuit.step().unwrap();
+    /// assert_eq!(data.value(), 42);
+    ///
+    /// circuit[rom].memory[43] = LogicVector::from_int(24, 8).unwrap();
+    /// addr.replace(LogicVector::from_int(43, 10).unwrap());
+    /// circuit.step().unwrap();
+    /// assert_eq!(data.value(), 24);
+    /// ```
+    pub fn add<T: Updateable + 'static>(&mut self, updater: T) -> Handle<T> {
+        self.readers = None;
+        self.updater.push(Box::new(updater));
+        Handle {
+            idx: self.updater.len() - 1,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Adds a clone of an [`Updateable`](Updateable) to the `Circuit`. This is useful for structs
+    /// whose clones share their state, e.g. [`Signal`](crate::Signal)s, because you can keep using
+    /// the original.
     ///
     /// ```
     /// use logical::{Circuit, Signal, Ieee1164};
-    /// use logical::models::gates::OrGate;
     ///
     /// let mut sig: logical::Signal<Ieee1164> = Signal::default();
     /// // Configure signal here
     ///
-    /// let or = OrGate::default(); // Gates do not need to be mutable
-    /// // Connect gate here
-    ///
     /// let mut circuit = Circuit::default();
     /// circuit.add_updater(&sig);
-    /// circuit.add_updater(&or);
     /// ```
     pub fn add_updater<T: Updateable + Clone + 'static>(&mut self, updater: &T) {
+        self.readers = None;
         self.updater.push(Box::new(updater.clone()))
     }
+
+    /// Returns a reference to the model `handle` refers to, or `None` if `handle` does not
+    /// belong to this `Circuit`.
+    pub fn get<T: Updateable + 'static>(&self, handle: Handle<T>) -> Option<&T> {
+        self.updater
+            .get(handle.idx)
+            .and_then(|u| u.as_any().downcast_ref())
+    }
+
+    /// Returns a mutable reference to the model `handle` refers to, or `None` if `handle` does not
+    /// belong to this `Circuit`.
+    pub fn get_mut<T: Updateable + 'static>(&mut self, handle: Handle<T>) -> Option<&mut T> {
+        // the model may be rewired, e.g. by replacing one of its ports
+        self.readers = None;
+        self.updater
+            .get_mut(handle.idx)
+            .and_then(|u| u.as_any_mut().downcast_mut())
+    }
+}
+
+impl<T: Updateable + 'static> Index<Handle<T>> for Circuit {
+    type Output = T;
+
+    fn index(&self, handle: Handle<T>) -> &T {
+        self.get(handle)
+            .expect("Handle does not belong to this Circuit")
+    }
+}
+
+impl<T: Updateable + 'static> IndexMut<Handle<T>> for Circuit {
+    fn index_mut(&mut self, handle: Handle<T>) -> &mut T {
+        self.get_mut(handle)
+            .expect("Handle does not belong to this Circuit")
+    }
+}
+
+/// Returns the strongly connected components of the graph described by `edges` (Tarjan's
+/// algorithm). The nodes of each component are in the order they have been visited.
+fn strongly_connected(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut index: Vec<Option<usize>> = vec![None; edges.len()];
+    let mut low = vec![0; edges.len()];
+    let mut on_stack = vec![false; edges.len()];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut counter = 0;
+
+    for root in 0..edges.len() {
+        if index[root].is_some() {
+            continue;
+        }
+        index[root] = Some(counter);
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        let mut work = vec![(root, 0)];
+        while let Some((v, pos)) = work.pop() {
+            if let Some(&w) = edges[v].get(pos) {
+                work.push((v, pos + 1));
+                match index[w] {
+                    None => {
+                        index[w] = Some(counter);
+                        low[w] = counter;
+                        counter += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        work.push((w, 0));
+                    }
+                    Some(iw) if on_stack[w] => low[v] = low[v].min(iw),
+                    Some(_) => {}
+                }
+            } else {
+                if let Some(&(parent, _)) = work.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if Some(low[v]) == index[v] {
+                    let mut component = vec![];
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    component.reverse();
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::{Input, Output};
+    use crate::models::gates::{AndGate, Inverter, NandGate};
+    use crate::models::rtlib::arithmic::Add;
+    use crate::models::rtlib::memory::Rom1kx8;
+    use crate::{Delay, Ieee1164, LogicVector, Port, Signal};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Counter {
+        a: Port<Ieee1164, Input>,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl Updateable for Counter {
+        fn update(&mut self) -> bool {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            false
+        }
+
+        fn sensitivity(&self) -> Vec<PortId> {
+            vec![self.a.id()]
+        }
+    }
+
+    #[test]
+    fn only_affected_updaters_are_evaluated() {
+        let mut switch1 = Port::<_, Output>::new(Ieee1164::_0);
+        let switch2 = Port::<_, Output>::new(Ieee1164::_0);
+        let counter1 = Counter::default();
+        let counter2 = Counter::default();
+
+        let mut sig1 = Signal::default();
+        sig1.connect(&switch1).unwrap();
+        sig1.connect(&counter1.a).unwrap();
+        let mut sig2 = Signal::default();
+        sig2.connect(&switch2).unwrap();
+        sig2.connect(&counter2.a).unwrap();
+
+        let mut circuit = Circuit::default();
+        circuit.add_updater(&sig1);
+        circuit.add_updater(&sig2);
+        circuit.add_updater(&counter1);
+        circuit.add_updater(&counter2);
+
+        assert_eq!(Ok(2), circuit.step());
+        let count1 = counter1.count.load(Ordering::SeqCst);
+        let count2 = counter2.count.load(Ordering::SeqCst);
+
+        assert_eq!(Ok(0), circuit.step());
+        assert_eq!(count1, counter1.count.load(Ordering::SeqCst));
+        assert_eq!(count2, counter2.count.load(Ordering::SeqCst));
+
+        switch1.replace(Ieee1164::_1);
+        assert_eq!(Ok(2), circuit.step());
+        assert_eq!(count1 + 1, counter1.count.load(Ordering::SeqCst));
+        assert_eq!(count2, counter2.count.load(Ordering::SeqCst));
+    }
+
+    #[derive(Clone, Default)]
+    struct Generator {
+        time_driven: bool,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl Updateable for Generator {
+        fn update(&mut self) -> bool {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            false
+        }
+
+        fn time_driven(&self) -> bool {
+            self.time_driven
+        }
+    }
+
+    #[test]
+    fn time_driven_updaters_are_not_always_evaluated() {
+        let mut switch = Port::<_, Output>::new(Ieee1164::_0);
+        let counter = Counter::default();
+        let mut sig = Signal::default();
+        sig.connect(&switch).unwrap();
+        sig.connect(&counter.a).unwrap();
+        let unknown = Generator::default();
+        let generator = Generator {
+            time_driven: true,
+            ..Generator::default()
+        };
+
+        let mut circuit = Circuit::default();
+        circuit.add_updater(&sig);
+        circuit.add_updater(&counter);
+        circuit.add_updater(&unknown);
+        circuit.add_updater(&generator);
+
+        circuit.step().unwrap();
+        assert_eq!(1, generator.count.load(Ordering::SeqCst));
+        let unknown_count = unknown.count.load(Ordering::SeqCst);
+
+        switch.replace(Ieee1164::_1);
+        assert_eq!(Ok(2), circuit.step());
+        assert_eq!(1, generator.count.load(Ordering::SeqCst));
+        assert_eq!(unknown_count + 1, unknown.count.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn readers_are_collected_again_after_add() {
+        let mut switch = Port::<_, Output>::new(Ieee1164::_0);
+        let counter = Counter::default();
+        let mut sig = Signal::default();
+        sig.connect(&switch).unwrap();
+        sig.connect(&counter.a).unwrap();
+
+        let mut circuit = Circuit::default();
+        circuit.add_updater(&sig);
+        circuit.step().unwrap();
+        circuit.add_updater(&counter);
+        circuit.step().unwrap();
+        let count = counter.count.load(Ordering::SeqCst);
+
+        switch.replace(Ieee1164::_1);
+        circuit.step().unwrap();
+        assert_eq!(count + 1, counter.count.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn stale_cache_after_connect() {
+        let idle = Port::<_, Output>::new(Ieee1164::_Z);
+        let mut drv = Port::<_, Output>::new(Ieee1164::_1);
+        let out = Port::<_, Input>::default();
+        let inv = Inverter::default();
+        let mut sig = signal!(idle, inv.a);
+        let sig_out = signal!(inv.z, out);
+
+        let mut circuit = Circuit::default();
+        circuit.add_updater(&sig);
+        circuit.add(sig_out);
+        circuit.add(inv);
+        circuit.step().unwrap();
+        assert_eq!(Ieee1164::_X, out.value());
+
+        sig.connect(&drv).unwrap();
+        circuit.step().unwrap();
+        assert_eq!(Ieee1164::_0, out.value());
+
+        drv.replace(Ieee1164::_0);
+        circuit.step().unwrap();
+        assert_eq!(Ieee1164::_1, out.value());
+    }
+
+    #[test]
+    fn owned_models_are_reachable_through_handles() {
+        let a = Port::<_, Output>::new(LogicVector::from_int(3, 8).unwrap());
+        let mut b = Port::<_, Output>::new(LogicVector::from_int(4, 8).unwrap());
+        let sum = Port::<_, Input>::new(LogicVector::with_width(8));
+        let add = Add::with_width(8);
+        let sig_a = signal!(a, add.a);
+        let sig_b = signal!(b, add.b);
+        let sig_sum = signal!(add.s, sum);
+
+        let addr = Port::<_, Output>::new(LogicVector::from_int(42, 10).unwrap());
+        let select = Port::<_, Output>::new(Ieee1164::_0);
+        let data = Port::<_, Input>::new(LogicVector::with_width(8));
+        let rom = Rom1kx8::default();
+        let sig_addr = signal!(addr, rom.addr);
+        let sig_select = signal!(select, rom.n_chip_select, rom.n_output_enable);
+        let sig_data = signal!(rom.data, data);
+
+        let mut circuit = circuit!(sig_a, sig_b, sig_sum, sig_addr, sig_select, sig_data);
+        let add = circuit.add(add);
+        let rom = circuit.add(rom);
+
+        circuit[rom].memory[42] = LogicVector::from_int(0xAA, 8).unwrap();
+        circuit.step().unwrap();
+        assert_eq!(Some(7), sum.value().as_u128());
+        assert_eq!(Some(0xAA), data.value().as_u128());
+
+        b.replace(LogicVector::from_int(39, 8).unwrap());
+        circuit.step().unwrap();
+        assert_eq!(Some(42), sum.value().as_u128());
+        assert_eq!(Some(39), circuit.get(add).unwrap().b.value().as_u128());
+    }
+
+    fn ring_oscillator(delay: Delay) -> (Circuit, Port<Ieee1164, Output>) {
+        let nand = NandGate::with_delay(delay);
+        let enable = Port::<_, Output>::new(Ieee1164::_0);
+        let mut sig_ring = Signal::named("ring");
+        sig_ring.connect(&nand.z).unwrap();
+        sig_ring.connect(&nand.a).unwrap();
+        let mut sig_enable = Signal::named("enable");
+        sig_enable.connect(&enable).unwrap();
+        sig_enable.connect(&nand.b).unwrap();
+
+        let mut circuit = Circuit::default();
+        circuit.add(nand);
+        circuit.add(sig_ring);
+        circuit.add(sig_enable);
+        (circuit, enable)
+    }
+
+    #[test]
+    fn oscillation_is_detected() {
+        let (mut circuit, mut enable) = ring_oscillator(Delay::default());
+        circuit.set_delta_limit(20);
+        assert!(circuit.step().is_ok());
+
+        enable.replace(Ieee1164::_1);
+        match circuit.step() {
+            Err(CircuitError::Oscillation {
+                time,
+                deltas,
+                updaters,
+            }) => {
+                assert_eq!(0, time);
+                assert_eq!(20, deltas);
+                assert!(updaters.contains(&"ring".to_string()));
+                assert!(!updaters.contains(&"enable".to_string()));
+            }
+            e => panic!("unexpected result {:?}", e),
+        }
+
+        assert_eq!(Some(0), circuit.next_event());
+        assert!(circuit.step().is_err());
+        enable.replace(Ieee1164::_0);
+        assert!(circuit.step().is_ok());
+        assert_eq!(None, circuit.next_event());
+    }
+
+    #[test]
+    fn delayed_ring_oscillator_advances_time() {
+        let (mut circuit, mut enable) = ring_oscillator(Delay::inertial(5, 5));
+        circuit.run_until(10).unwrap();
+
+        enable.replace(Ieee1164::_1);
+        circuit.run_until(100).unwrap();
+        assert_eq!(100, circuit.now());
+        assert!(circuit.next_event().is_some());
+    }
+
+    #[test]
+    fn no_loops_in_chain() {
+        let inv1 = Inverter::default();
+        let inv2 = Inverter::default();
+        let mut sig = Signal::default();
+        sig.connect(&inv1.z).unwrap();
+        sig.connect(&inv2.a).unwrap();
+
+        let mut circuit = Circuit::default();
+        circuit.add(inv1);
+        circuit.add(sig);
+        circuit.add(inv2);
+
+        assert_eq!(Ok(()), circuit.check_loops());
+    }
+
+    #[derive(Default)]
+    struct Registered {
+        clk: Port<Ieee1164, Input>,
+        d: Port<Ieee1164, Input>,
+        q: Port<Ieee1164, Output>,
+    }
+
+    impl Updateable for Registered {
+        fn update(&mut self) -> bool {
+            false
+        }
+
+        fn sensitivity(&self) -> Vec<PortId> {
+            vec![self.clk.id(), self.d.id()]
+        }
+
+        fn combinational_inputs(&self) -> Vec<PortId> {
+            vec![self.clk.id()]
+        }
+
+        fn drives(&self) -> Vec<PortId> {
+            vec![self.q.id()]
+        }
+    }
+
+    #[test]
+    fn registered_feedback_is_no_loop() {
+        let reg = Registered::default();
+        let inv = Inverter::default();
+        let clk = Port::<_, Output>::new(Ieee1164::_0);
+        let sig_clk = signal!(clk, reg.clk);
+        let sig_q = signal!(reg.q, inv.a);
+        let sig_d = signal!(inv.z, reg.d);
+
+        let mut circuit = Circuit::default();
+        circuit.add(sig_clk);
+        circuit.add(reg);
+        circuit.add(sig_q);
+        circuit.add(inv);
+        circuit.add(sig_d);
+
+        assert_eq!(Ok(()), circuit.check_loops());
+    }
+
+    #[test]
+    fn finds_multiple_loops() {
+        let and = AndGate::default();
+        let inv = Inverter::default();
+        let mut sig_and = Signal::named("and");
+        sig_and.connect(&and.z).unwrap();
+        sig_and.connect(&and.a).unwrap();
+        let mut sig_inv = Signal::named("inv");
+        sig_inv.connect(&inv.z).unwrap();
+        sig_inv.connect(&inv.a).unwrap();
+
+        let mut circuit = Circuit::default();
+        circuit.add(and);
+        circuit.add(sig_and);
+        circuit.add(inv);
+        circuit.add(sig_inv);
+
+        match circuit.check_loops() {
+            Err(CircuitError::CombinationalLoop(loops)) => {
+                assert_eq!(2, loops.len());
+                assert!(loops.iter().all(|l| l.len() == 2));
+            }
+            e => panic!("unexpected result {:?}", e),
+        }
+    }
 }
@@ -0,0 +1,395 @@
+//! This module contains the [`Component`] trait, which makes the [`Port`]s of a model and its
+//! sub-components discoverable at runtime.
+//!
+//! Tools like dumpers, netlist exporters or debuggers can use it to walk through a design without
+//! knowing the concrete types of its models.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::direction::PortDirection;
+use crate::port::InnerPort;
+use crate::{Ieee1164, LogicVector, Port, PortId};
+
+pub use logical_derive::Component;
+
+/// The direction of a [`Port`] as seen from the [`Component`] it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// The component reads from this port, see [`Input`](crate::direction::Input)
+    Input,
+    /// The component writes to this port, see [`Output`](crate::direction::Output)
+    Output,
+    /// The component reads from and writes to this port, see [`InOut`](crate::direction::InOut)
+    InOut,
+}
+
+impl Direction {
+    /// Returns the `Direction` of the [`PortDirection`] `D`.
+    pub fn of<D: PortDirection>() -> Self {
+        if D::IS_INPUT {
+            Direction::Input
+        } else if D::IS_OUTPUT {
+            Direction::Output
+        } else {
+            Direction::InOut
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::Input => write!(f, "in"),
+            Direction::Output => write!(f, "out"),
+            Direction::InOut => write!(f, "inout"),
+        }
+    }
+}
+
+/// The type of the value a [`Port`] carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortType {
+    /// A single [`Ieee1164`] value
+    Ieee1164,
+    /// A [`LogicVector`] with the given width
+    LogicVector(usize),
+}
+
+impl PortType {
+    /// Returns the number of bits of this type, which is `1` for an [`Ieee1164`].
+    pub fn width(&self) -> usize {
+        match self {
+            PortType::Ieee1164 => 1,
+            PortType::LogicVector(width) => *width,
+        }
+    }
+}
+
+/// The current value of a [`Port`], see [`PortInfo::value`].
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// The value of an [`Ieee1164`] port
+    Ieee1164(Ieee1164),
+    /// The value of a [`LogicVector`] port
+    LogicVector(LogicVector),
+}
+
+impl Value {
+    /// Returns the [`PortType`] of this value.
+    pub fn port_type(&self) -> PortType {
+        match self {
+            Value::Ieee1164(_) => PortType::Ieee1164,
+            Value::LogicVector(lv) => PortType::LogicVector(lv.width()),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Ieee1164(v) => write!(f, "{}", v),
+            Value::LogicVector(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// A value type that can be carried by a [`Port`] which is listed in [`Component::ports`].
+pub trait PortValue: Clone + 'static {
+    /// Converts this value into a [`Value`].
+    fn to_value(&self) -> Value;
+}
+
+impl PortValue for Ieee1164 {
+    fn to_value(&self) -> Value {
+        Value::Ieee1164(*self)
+    }
+}
+
+impl PortValue for LogicVector {
+    fn to_value(&self) -> Value {
+        Value::LogicVector(self.clone())
+    }
+}
+
+/// Type-erased read access to a port, regardless of its value type and direction.
+trait Probe {
+    fn value(&self) -> Value;
+}
+
+impl<T: PortValue> Probe for InnerPort<T> {
+    fn value(&self) -> Value {
+        self.peek().to_value()
+    }
+}
+
+/// Describes a single [`Port`] of a [`Component`].
+///
+/// A `PortInfo` can always read the value of the port, even if its direction is
+/// [`Output`](crate::direction::Output).
+#[derive(Clone)]
+pub struct PortInfo {
+    name: String,
+    direction: Direction,
+    id: PortId,
+    probe: Arc<dyn Probe>,
+}
+
+impl PortInfo {
+    /// Describes `port` which is known as `name` inside of its component.
+    pub fn new<T: PortValue, D: PortDirection>(name: &str, port: &Port<T, D>) -> Self {
+        let probe: Arc<InnerPort<T>> = port.inner.clone();
+        PortInfo {
+            name: name.to_string(),
+            direction: Direction::of::<D>(),
+            id: port.id(),
+            probe: probe as Arc<dyn Probe>,
+        }
+    }
+
+    /// The name of the port inside of its component, e.g. `"a"` or `"z"`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The [`Direction`] of the port.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// The [`PortType`] of the port, which is determined by its current value.
+    pub fn port_type(&self) -> PortType {
+        self.value().port_type()
+    }
+
+    /// The [`PortId`] of the port, which can be used to find out which ports are connected to the
+    /// same [`Signal`](crate::Signal).
+    pub fn id(&self) -> PortId {
+        self.id.clone()
+    }
+
+    /// Reads the current value of the port.
+    pub fn value(&self) -> Value {
+        self.probe.value()
+    }
+}
+
+impl fmt::Debug for PortInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PortInfo")
+            .field("name", &self.name)
+            .field("direction", &self.direction)
+            .field("value", &self.value())
+            .finish()
+    }
+}
+
+/// A `Component` is a model whose [`Port`]s (and sub-components) can be enumerated at runtime.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::component::{Component, Direction, PortType};
+/// use logical::models::gates::AndGate;
+///
+/// let and = AndGate::default();
+/// let ports = and.ports();
+/// assert_eq!(vec!["a", "b", "z"], ports.iter().map(|p| p.name()).collect::<Vec<_>>());
+/// assert_eq!(Direction::Output, ports[2].direction());
+/// assert_eq!(PortType::Ieee1164, ports[2].port_type());
+/// ```
+///
+/// # Deriving
+///
+/// Instead of implementing this trait by hand, you can derive it for a struct of [`Port`]s. Every
+/// field that is marked with `#[port(input)]`, `#[port(output)]` or `#[port(inout)]` becomes a
+/// port, every field that is marked with `#[component]` becomes a child. Both accept a different
+/// name with `name = "..."`.
+///
+/// ```rust
+/// use logical::direction::{Input, Output};
+/// use logical::models::gates::XorGate;
+/// use logical::{Component, Ieee1164, LogicVector, Port};
+///
+/// #[derive(Component)]
+/// struct Parity {
+///     #[port(input)]
+///     data: Port<LogicVector, Input>,
+///     #[port(output, name = "p")]
+///     parity: Port<Ieee1164, Output>,
+///     #[component(name = "xor0")]
+///     xor: XorGate,
+///     ignored: u32,
+/// }
+///
+/// let parity = Parity {
+///     data: Port::new(LogicVector::with_width(2)),
+///     parity: Port::default(),
+///     xor: XorGate::default(),
+///     ignored: 0,
+/// };
+/// assert_eq!("p", parity.ports()[1].name());
+/// assert_eq!("xor0", parity.children()[0].0);
+/// ```
+///
+/// The given direction has to match the one of the `Port`:
+///
+/// ```rust,compile_fail
+/// use logical::direction::Input;
+/// use logical::{Component, Ieee1164, Port};
+///
+/// #[derive(Component)]
+/// struct Wrong {
+///     #[port(output)]
+///     a: Port<Ieee1164, Input>,
+/// }
+/// ```
+pub trait Component {
+    /// Returns all ports of this component, but not the ones of its
+    /// [children](Component::children).
+    fn ports(&self) -> Vec<PortInfo>;
+
+    /// Returns the sub-components of this component together with their names. The default is no
+    /// children at all.
+    fn children(&self) -> Vec<(String, &dyn Component)> {
+        vec![]
+    }
+
+    /// Calls `f` for every port of this component and all of its children (recursively). The first
+    /// argument is the hierarchical path of the component the port belongs to, e.g.
+    /// `["alu", "adder"]`, which is empty for the ports of `self`.
+    fn visit_ports(&self, f: &mut dyn FnMut(&[String], &PortInfo)) {
+        fn visit(
+            component: &dyn Component,
+            path: &mut Vec<String>,
+            f: &mut dyn FnMut(&[String], &PortInfo),
+        ) {
+            for port in component.ports() {
+                f(path, &port);
+            }
+            for (name, child) in component.children() {
+                path.push(name);
+                visit(child, path, f);
+                path.pop();
+            }
+        }
+
+        let mut path = vec![];
+        for port in self.ports() {
+            f(&path, &port);
+        }
+        for (name, child) in self.children() {
+            path.push(name);
+            visit(child, &mut path, f);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::{InOut, Input, Output};
+    use crate::models::gates::{AndGate, Inverter};
+
+    struct HalfInverted {
+        inv: Inverter,
+        and: AndGate,
+        y: Port<LogicVector, InOut>,
+    }
+
+    impl Component for HalfInverted {
+        fn ports(&self) -> Vec<PortInfo> {
+            vec![PortInfo::new("y", &self.y)]
+        }
+
+        fn children(&self) -> Vec<(String, &dyn Component)> {
+            vec![
+                ("inv".to_string(), &self.inv),
+                ("and".to_string(), &self.and),
+            ]
+        }
+    }
+
+    #[derive(Component)]
+    struct Derived<T: Component> {
+        #[port(input)]
+        a: Port<Ieee1164, Input>,
+        #[port(inout, name = "bus")]
+        b: Port<LogicVector, InOut>,
+        #[component]
+        inner: T,
+        _other: usize,
+    }
+
+    #[test]
+    fn derived_component() {
+        let c = Derived {
+            a: Port::default(),
+            b: Port::new(LogicVector::with_width(8)),
+            inner: Inverter::default(),
+            _other: 0,
+        };
+
+        let ports = c.ports();
+        assert_eq!(2, ports.len());
+        assert_eq!(
+            ("a", Direction::Input, PortType::Ieee1164),
+            (ports[0].name(), ports[0].direction(), ports[0].port_type())
+        );
+        assert_eq!(
+            ("bus", Direction::InOut, PortType::LogicVector(8)),
+            (ports[1].name(), ports[1].direction(), ports[1].port_type())
+        );
+        assert_eq!(c.b.id(), ports[1].id());
+        assert_eq!(vec![c.a.id(), c.b.id()], c.input_ids());
+        assert_eq!(vec![c.b.id()], c.output_ids());
+
+        let children = c.children();
+        assert_eq!(1, children.len());
+        assert_eq!("inner", children[0].0);
+        assert_eq!(2, children[0].1.ports().len());
+    }
+
+    #[test]
+    fn direction_of() {
+        assert_eq!(Direction::Input, Direction::of::<Input>());
+        assert_eq!(Direction::Output, Direction::of::<Output>());
+        assert_eq!(Direction::InOut, Direction::of::<InOut>());
+    }
+
+    #[test]
+    fn output_ports_can_be_probed() {
+        let mut port = Port::<_, Output>::new(LogicVector::with_width(4));
+        let info = PortInfo::new("data", &port);
+        assert_eq!(PortType::LogicVector(4), info.port_type());
+        assert_eq!(port.id(), info.id());
+
+        port.replace(LogicVector::from_int(9, 4).unwrap());
+        assert_eq!(
+            Value::LogicVector(LogicVector::from_int(9, 4).unwrap()),
+            info.value()
+        );
+    }
+
+    #[test]
+    fn visit_hierarchy() {
+        let c = HalfInverted {
+            inv: Inverter::default(),
+            and: AndGate::default(),
+            y: Port::new(LogicVector::with_width(2)),
+        };
+
+        let mut names = vec![];
+        c.visit_ports(&mut |path, port| {
+            let mut name = path.to_vec();
+            name.push(port.name().to_string());
+            names.push(name.join("."));
+        });
+        assert_eq!(
+            vec!["y", "inv.a", "inv.z", "and.a", "and.b", "and.z"],
+            names
+        );
+    }
+}
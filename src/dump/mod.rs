@@ -1,7 +1,7 @@
 //! TODO
 
-use crate::direction::Output;
-use crate::{Ieee1164, Port};
+use crate::component::{Component, Value};
+use crate::{Ieee1164, Time};
 
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
@@ -12,34 +12,6 @@ use std::path::Path;
 use crate::logicbit::LogicVector;
 use chrono::Local;
 
-/// A trait for iterating over the containing [`Port`]s of a `Model`.
-///
-/// Instead of using (non-exiting) reflection, you have to pass all Ports you want to export to the
-/// argument `FnMut`.
-///
-/// This is mainly used for dumping purposes, because this operations can be quiet expensive.
-//TODO: Is this really needed? Let's rethink dumping values.
-pub trait IterPorts {
-    /// See [`IterPorts] for a good description.
-    ///
-    /// The implementor should pass a short, descripting `&str` as long with the `Port`.
-    /// Currently only [`Ieee1164`] is supported, in the future other values will be supported too.
-    fn iter_ports<F>(&self, f: F)
-    where
-        F: FnMut(&str, &Port<Ieee1164, Output>);
-}
-
-//TODO: Is this really needed? Let's rethink dumping values.
-/// Iterates over the values of a struct. This can either be a single value or multiple, depending
-/// on the struct itself.
-pub trait IterValues {
-    /// FooBar
-    // TODO!
-    fn iter_values<F>(&self, f: F)
-    where
-        F: FnMut(&Ieee1164);
-}
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Type {
     Wire,
@@ -65,7 +37,7 @@ impl fmt::Display for Type {
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Ident {
     ty: Type,
-    width: u8,
+    width: usize,
     ident: char,
     name: String,
 }
@@ -75,9 +47,9 @@ struct Ident {
 #[derive(Debug, Default)]
 pub struct Vcd {
     module_name: String,
-    tags: BTreeMap<u32, Vec<(Ident, String)>>, //Do we need more than 4x10^9 timestamps? I don't think so :/
+    tags: BTreeMap<Time, Vec<(Ident, String)>>,
     identifier: HashMap<String, Ident>,
-    timestamp: u32,
+    timestamp: Time,
 }
 
 impl Vcd {
@@ -92,18 +64,33 @@ impl Vcd {
         }
     }
 
-    // TODO: replace this by a trait function
-    /// Serializes a struct which holds `Port`s. This function will dump all ports it contains.
-    pub fn serialize_ports(&mut self, ports: &impl IterPorts) {
-        ports.iter_ports(|n, p: &Port<Ieee1164, Output>| {
-            p.iter_values(|v| self.serialize_ieee1164(n, *v));
+    /// Serializes a [`Component`]. This function will dump all ports it contains, including the
+    /// ones of its children. The ports of a child are named by their path, e.g. `adder.s`.
+    pub fn serialize_ports<C: Component + ?Sized>(&mut self, component: &C) {
+        component.visit_ports(&mut |path, port| {
+            let mut name = path.join(".");
+            if !name.is_empty() {
+                name.push('.');
+            }
+            name.push_str(port.name());
+            match port.value() {
+                Value::Ieee1164(v) => self.serialize_ieee1164(&name, v),
+                Value::LogicVector(v) => self.serialize_logivector(&name, &v),
+            }
         });
     }
 
     /// Ticks this dumper. This will increment the inner time to the next value.
     pub fn tick(&mut self) {
-        self.timestamp += 1;
-        self.tags.insert(self.timestamp, vec![]);
+        self.set_time(self.timestamp + 1);
+    }
+
+    /// Sets the inner time to `time` (in picoseconds), e.g. to the
+    /// [`Circuit::now`](crate::Circuit::now) of a simulated circuit. Every value that is serialized
+    /// afterwards is recorded at that time.
+    pub fn set_time(&mut self, time: Time) {
+        self.timestamp = time;
+        self.tags.entry(time).or_default();
     }
 
     /// Serializes a `LogicVector`, but won't write anything to a file. It just stores the value
@@ -124,7 +111,7 @@ impl Vcd {
         self.tags
             .get_mut(&self.timestamp)
             .unwrap()
-            .push((ident, value.to_string()));
+            .push((ident, format!("{:b}", value)));
     }
 
     /// Serializes an `Ieee1164`, but won't write anything to a file yet. It just stores the value
@@ -76,20 +76,29 @@
 #[macro_use]
 extern crate pretty_assertions;
 
+// allows the derive macros to refer to `::logical` inside of this crate, too
+extern crate self as logical;
+
 #[macro_use]
 mod mac;
 mod circuit;
+pub mod component;
 pub mod dump;
 mod logicbit;
 pub(self) mod port;
 mod signal;
+pub mod time;
 
 pub mod models;
 
-pub use self::circuit::Circuit;
-pub use self::logicbit::{Ieee1164, Ieee1164Value, LogicVector, Resolve};
-pub use self::port::Port;
-pub use self::signal::Signal;
+pub use self::circuit::{Circuit, CircuitError, Handle};
+pub use self::component::Component;
+pub use self::logicbit::{
+    Ieee1164, Ieee1164Value, LogicVector, LogicVectorConversionError, MetavalueHook, Resolve, Width,
+};
+pub use self::port::{Port, PortId};
+pub use self::signal::{ConnectionError, Signal};
+pub use self::time::{Delay, Time};
 
 #[allow(unused_imports)]
 use self::direction::{InOut, Input, Output, PortDirection};
@@ -109,4 +118,72 @@ pub trait Updateable {
     ///
     /// Returns `true` if the output value of the updatable object was changed.
     fn update(&mut self) -> bool;
+
+    /// The same as [`Updateable::update`], but the current simulated [`Time`] is known, so delays
+    /// can be modelled. A [`Circuit`] will always call this function instead of `update`.
+    ///
+    /// The default implementation ignores the time and calls [`Updateable::update`].
+    fn update_at(&mut self, _now: Time) -> bool {
+        self.update()
+    }
+
+    /// Returns the point in simulated time at which this struct wants to be updated again, e.g.
+    /// because a delayed value change becomes visible then. The default is `None`.
+    fn next_event(&self) -> Option<Time> {
+        None
+    }
+
+    /// Returns a human readable name of this struct, which is used e.g. in the errors of a
+    /// [`Circuit`]. The default is the name of the type.
+    fn name(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+
+    /// Returns the [`Port`]s this struct reads from, also known as its sensitivity list. A
+    /// [`Circuit`] will only re-evaluate this struct, if one of these ports has changed.
+    ///
+    /// An empty list (the default) means that the sensitivity is unknown and the struct will be
+    /// updated on every [`Circuit::step`], unless it is [time-driven](Updateable::time_driven).
+    fn sensitivity(&self) -> Vec<PortId> {
+        vec![]
+    }
+
+    /// Returns `true` if this struct does not read any [`Port`] and only changes at the points in
+    /// simulated time it requests with [`Updateable::next_event`], e.g. a clock generator. A
+    /// [`Circuit`] will then update it only at these points in time, instead of on every
+    /// [`Circuit::step`]. The default is `false`.
+    fn time_driven(&self) -> bool {
+        false
+    }
+
+    /// Returns the [`Port`]s this struct writes to. If [`Updateable::update`] returns `true`,
+    /// everything that is sensitive to one of these ports will be re-evaluated in the next delta
+    /// cycle.
+    ///
+    /// An empty list (the default) means that the fan-out is unknown and a change will cause every
+    /// element of the [`Circuit`] to be re-evaluated.
+    fn drives(&self) -> Vec<PortId> {
+        vec![]
+    }
+
+    /// Returns the [`Port`]s whose changes reach the [driven ports](Updateable::drives) without
+    /// waiting for a clock edge. A [`Circuit`] uses them to search for combinational loops, see
+    /// [`Circuit::check_loops`].
+    ///
+    /// The default is the [sensitivity](Updateable::sensitivity) list. Edge-triggered models only
+    /// return their clock and asynchronous inputs, because e.g. the data input of a flip-flop is
+    /// only sampled on a clock edge, so a feedback through it is not a combinational loop.
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        self.sensitivity()
+    }
+
+    /// Returns a counter that changes whenever the [sensitivity](Updateable::sensitivity) or the
+    /// [driven ports](Updateable::drives) of this struct change behind a shared reference, e.g.
+    /// because a [`Port`] has been connected to a cloned [`Signal`]. A [`Circuit`] collects the
+    /// connections of its elements again, if one of these counters has changed.
+    ///
+    /// The default is `0`, which is fine for structs that can only be rewired through `&mut self`.
+    fn wiring_generation(&self) -> usize {
+        0
+    }
 }
@@ -1,6 +1,6 @@
 #![allow(clippy::just_underscores_and_digits)]
 
-use super::{Ieee1164Value, Resolve};
+use super::{Ieee1164Value, Resolve, Width};
 use std::convert::TryFrom;
 use std::fmt;
 use std::ops::{BitAnd, BitOr, BitXor, Not};
@@ -209,6 +209,12 @@ fn resolve(a: &Ieee1164, b: &Ieee1164) -> Ieee1164 {
 }
 expand_op_ieee1164!(resolve, Resolve, resolve);
 
+impl Width for Ieee1164 {
+    fn width(&self) -> usize {
+        1
+    }
+}
+
 impl fmt::Display for Ieee1164 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", char::from(*self))
@@ -300,6 +306,81 @@ impl Ieee1164 {
     }
 }
 
+impl Ieee1164 {
+    /// Strips the strength of this value, like `To_X01` of `ieee.std_logic_1164`: [`Ieee1164::_L`]
+    /// becomes [`Ieee1164::_0`], [`Ieee1164::_H`] becomes [`Ieee1164::_1`] and every metavalue
+    /// becomes [`Ieee1164::_X`].
+    ///
+    /// ```rust
+    /// # use logical::Ieee1164;
+    /// assert_eq!(Ieee1164::_1, Ieee1164::_H.to_x01());
+    /// assert_eq!(Ieee1164::_X, Ieee1164::_Z.to_x01());
+    /// ```
+    pub fn to_x01(self) -> Ieee1164 {
+        if self.is_1H() {
+            _1
+        } else if self.is_0L() {
+            _0
+        } else {
+            _X
+        }
+    }
+
+    /// Like [`Ieee1164::to_x01`], but [`Ieee1164::_Z`] is kept (`To_X01Z`).
+    pub fn to_x01z(self) -> Ieee1164 {
+        if self == _Z {
+            _Z
+        } else {
+            self.to_x01()
+        }
+    }
+
+    /// Like [`Ieee1164::to_x01`], but [`Ieee1164::_U`] is kept (`To_UX01`).
+    pub fn to_ux01(self) -> Ieee1164 {
+        if self == _U {
+            _U
+        } else {
+            self.to_x01()
+        }
+    }
+
+    /// Compares two values like `std_match` of `ieee.numeric_std`. A [`Ieee1164::_D`] matches
+    /// every value, otherwise both values have to be a logic high or both a logic low, regardless
+    /// of their strength. A metavalue never matches anything but a don't-care.
+    ///
+    /// ```rust
+    /// # use logical::Ieee1164;
+    /// assert!(Ieee1164::_H.std_match(Ieee1164::_1));
+    /// assert!(Ieee1164::_D.std_match(Ieee1164::_U));
+    /// assert!(!Ieee1164::_X.std_match(Ieee1164::_X));
+    /// ```
+    pub fn std_match(self, other: Ieee1164) -> bool {
+        if self == _D || other == _D {
+            return true;
+        }
+        let (a, b) = (self.to_x01(), other.to_x01());
+        a != _X && a == b
+    }
+
+    /// Returns `true` if the change from `previous` to `current` is a rising edge, i.e. a change
+    /// from a logic low to a logic high, like `rising_edge` of `ieee.std_logic_1164`.
+    ///
+    /// ```rust
+    /// # use logical::Ieee1164;
+    /// assert!(Ieee1164::rising_edge(Ieee1164::_L, Ieee1164::_1));
+    /// assert!(!Ieee1164::rising_edge(Ieee1164::_X, Ieee1164::_1));
+    /// ```
+    pub fn rising_edge(previous: Ieee1164, current: Ieee1164) -> bool {
+        previous.to_x01() == _0 && current.to_x01() == _1
+    }
+
+    /// Returns `true` if the change from `previous` to `current` is a falling edge, i.e. a change
+    /// from a logic high to a logic low, like `falling_edge` of `ieee.std_logic_1164`.
+    pub fn falling_edge(previous: Ieee1164, current: Ieee1164) -> bool {
+        previous.to_x01() == _1 && current.to_x01() == _0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -354,6 +435,43 @@ mod tests {
         assert!(Ieee1164::_X.is_UXZ());
     }
 
+    #[test]
+    fn strength_reduction() {
+        let all = [_U, _X, _0, _1, _Z, _W, _L, _H, _D];
+        let x01 = [_X, _X, _0, _1, _X, _X, _0, _1, _X];
+        let x01z = [_X, _X, _0, _1, _Z, _X, _0, _1, _X];
+        let ux01 = [_U, _X, _0, _1, _X, _X, _0, _1, _X];
+        for (i, v) in all.iter().enumerate() {
+            assert_eq!(x01[i], v.to_x01(), "{}", v);
+            assert_eq!(x01z[i], v.to_x01z(), "{}", v);
+            assert_eq!(ux01[i], v.to_ux01(), "{}", v);
+        }
+    }
+
+    #[test]
+    fn std_match() {
+        let all = [_U, _X, _0, _1, _Z, _W, _L, _H, _D];
+        for a in &all {
+            for b in &all {
+                let expected =
+                    a.is_D() || b.is_D() || (a.is_0L() && b.is_0L()) || (a.is_1H() && b.is_1H());
+                assert_eq!(expected, a.std_match(*b), "{} {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn edges() {
+        assert!(Ieee1164::rising_edge(_0, _1));
+        assert!(Ieee1164::rising_edge(_L, _H));
+        assert!(!Ieee1164::rising_edge(_U, _1));
+        assert!(!Ieee1164::rising_edge(_0, _Z));
+        assert!(!Ieee1164::rising_edge(_1, _1));
+        assert!(Ieee1164::falling_edge(_H, _0));
+        assert!(!Ieee1164::falling_edge(_X, _0));
+        assert!(!Ieee1164::falling_edge(_0, _1));
+    }
+
     #[test]
     fn check_associated_consts() {
         // this testcase seems useless, but I want to make sure, that the associated consts do match
@@ -0,0 +1,682 @@
+//! Arithmetic on [`LogicVector`]s, which follows the rules of the VHDL package `numeric_std`:
+//! the bits are interpreted as an unsigned (or, for the `signed_*` functions, as a two's
+//! complement signed) number, and if any operand contains a metavalue, i.e. anything else than
+//! `0`, `1`, `L` or `H`, the whole result is `X`.
+
+use std::cmp::Ordering;
+use std::ops::{Div, Mul, Neg, Rem, Sub};
+use std::sync::RwLock;
+
+use super::masks::words;
+use super::LogicVector;
+use crate::Ieee1164;
+
+/// A function that is called with a warning, whenever an arithmetic operation returns all `X`
+/// because of a metavalue, see [`LogicVector::set_metavalue_hook`].
+pub type MetavalueHook = Box<dyn Fn(&str) + Send + Sync>;
+
+static METAVALUE_HOOK: RwLock<Option<MetavalueHook>> = RwLock::new(None);
+
+/// Reports `message` to the installed hook, see [`LogicVector::set_metavalue_hook`].
+fn warn(message: &str) {
+    if let Some(hook) = METAVALUE_HOOK
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        hook(message);
+    }
+}
+
+/// Reports a metavalue for the operator `op` and returns an all `X` vector.
+fn metavalue(op: &str, width: usize) -> LogicVector {
+    warn(&format!(
+        "NUMERIC_STD.\"{}\": metavalue detected, returning X",
+        op
+    ));
+    LogicVector::from_ieee(Ieee1164::_X, width)
+}
+
+/// Splits little endian 128 bit words into `n` little endian 64 bit limbs.
+fn to_limbs(lv: &LogicVector, n: usize) -> Option<Vec<u64>> {
+    let mut limbs: Vec<u64> = lv
+        .to_words()?
+        .into_iter()
+        .flat_map(|w| vec![w as u64, (w >> 64) as u64])
+        .collect();
+    limbs.resize(n, 0);
+    Some(limbs)
+}
+
+/// Converts limbs to a `LogicVector` of `width` bits, all bits above are dropped.
+fn from_limbs(limbs: &[u64], width: usize) -> LogicVector {
+    let mut value: Vec<u128> = limbs
+        .chunks(2)
+        .map(|c| u128::from(c[0]) | c.get(1).map_or(0, |h| u128::from(*h) << 64))
+        .collect();
+    value.resize(words(width), 0);
+    if let Some(last) = value.last_mut() {
+        *last &= super::masks::top_mask(width);
+    }
+    LogicVector::from_words(&value, width).unwrap()
+}
+
+/// The number of limbs needed for `width` bits plus one spare limb for carries.
+fn limbs_for(width: usize) -> usize {
+    width.div_ceil(64) + 1
+}
+
+fn bit(limbs: &[u64], index: usize) -> bool {
+    limbs
+        .get(index / 64)
+        .is_some_and(|l| (l >> (index % 64)) & 1 == 1)
+}
+
+/// Sign extends the `width` bit number in `limbs` to all limbs.
+fn sign_extend(limbs: &mut [u64], width: usize) {
+    if bit(limbs, width - 1) {
+        let (w, s) = (width / 64, width % 64);
+        if s != 0 {
+            limbs[w] |= !0 << s;
+        }
+        let start = if s != 0 { w + 1 } else { w };
+        for l in limbs.iter_mut().skip(start) {
+            *l = !0;
+        }
+    }
+}
+
+fn add(lhs: &[u64], rhs: &[u64]) -> Vec<u64> {
+    let mut carry = false;
+    lhs.iter()
+        .zip(rhs)
+        .map(|(a, b)| {
+            let (sum, c1) = a.overflowing_add(*b);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            carry = c1 || c2;
+            sum
+        })
+        .collect()
+}
+
+fn sub(lhs: &[u64], rhs: &[u64]) -> Vec<u64> {
+    let mut borrow = false;
+    lhs.iter()
+        .zip(rhs)
+        .map(|(a, b)| {
+            let (diff, b1) = a.overflowing_sub(*b);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            borrow = b1 || b2;
+            diff
+        })
+        .collect()
+}
+
+/// Multiplies two numbers, the product has as many limbs as both factors together.
+fn mul(lhs: &[u64], rhs: &[u64]) -> Vec<u64> {
+    let mut product = vec![0u64; lhs.len() + rhs.len()];
+    for (i, a) in lhs.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, b) in rhs.iter().enumerate() {
+            let t = u128::from(*a) * u128::from(*b) + u128::from(product[i + j]) + carry;
+            product[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        product[i + rhs.len()] = carry as u64;
+    }
+    product
+}
+
+fn cmp(lhs: &[u64], rhs: &[u64]) -> Ordering {
+    lhs.iter().rev().cmp(rhs.iter().rev())
+}
+
+/// Divides two numbers of the same length by shifting and subtracting, returns the quotient and
+/// the remainder. `rhs` must not be zero.
+fn divrem(lhs: &[u64], rhs: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let mut quotient = vec![0u64; lhs.len()];
+    let mut remainder = vec![0u64; lhs.len()];
+    for i in (0..lhs.len() * 64).rev() {
+        let mut carry = bit(lhs, i) as u64;
+        for r in remainder.iter_mut() {
+            let next = *r >> 63;
+            *r = (*r << 1) | carry;
+            carry = next;
+        }
+        if cmp(&remainder, rhs) != Ordering::Less {
+            remainder = sub(&remainder, rhs);
+            quotient[i / 64] |= 1 << (i % 64);
+        }
+    }
+    (quotient, remainder)
+}
+
+fn is_zero(limbs: &[u64]) -> bool {
+    limbs.iter().all(|l| *l == 0)
+}
+
+fn negate(limbs: &[u64]) -> Vec<u64> {
+    sub(&vec![0; limbs.len()], limbs)
+}
+
+fn flag(value: bool) -> Ieee1164 {
+    if value {
+        Ieee1164::_1
+    } else {
+        Ieee1164::_0
+    }
+}
+
+impl LogicVector {
+    /// Installs a hook that is called with a message, whenever an arithmetic operation gets a
+    /// metavalue as operand (or divides by zero) and therefore returns all `X`, just like the
+    /// assertions of `numeric_std`. By default nothing is reported. `None` removes the hook.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// LogicVector::set_metavalue_hook(Some(Box::new(|msg| eprintln!("warning: {}", msg))));
+    /// let sum = LogicVector::with_width(8).wrapping_add(&LogicVector::from_int(1, 8).unwrap());
+    /// assert!(sum.is_XXX());
+    /// LogicVector::set_metavalue_hook(None);
+    /// ```
+    pub fn set_metavalue_hook(hook: Option<MetavalueHook>) {
+        *METAVALUE_HOOK.write().unwrap_or_else(|e| e.into_inner()) = hook;
+    }
+
+    /// Converts a signed integer value with a given width to a `LogicVector` in two's complement.
+    ///
+    /// It will return `None` if the `width` is `0` or `value` cannot be represented with `width`
+    /// bits.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv = LogicVector::from_i128(-73, 8).unwrap();
+    /// assert_eq!(Some(0b10110111), lv.as_u128());
+    /// assert_eq!(Some(-73), lv.as_i128());
+    /// assert!(LogicVector::from_i128(-129, 8).is_none());
+    /// ```
+    pub fn from_i128(value: i128, width: usize) -> Option<Self> {
+        if width == 0 {
+            return None;
+        }
+        let limbs = [value as u64, (value >> 64) as u64];
+        let mut extended = limbs.to_vec();
+        extended.resize(limbs_for(width.max(128)), if value < 0 { !0 } else { 0 });
+        let lv = from_limbs(&extended, width);
+        if lv.as_i128() == Some(value) {
+            Some(lv)
+        } else {
+            None
+        }
+    }
+
+    fn binary_op<F>(&self, rhs: &LogicVector, op: &str, f: F) -> (LogicVector, Ieee1164)
+    where
+        F: FnOnce(Vec<u64>, Vec<u64>) -> (Vec<u64>, bool),
+    {
+        assert_eq!(self.width(), rhs.width());
+        let n = limbs_for(self.width());
+        match (to_limbs(self, n), to_limbs(rhs, n)) {
+            (Some(a), Some(b)) => {
+                let (result, flag_value) = f(a, b);
+                (from_limbs(&result, self.width()), flag(flag_value))
+            }
+            _ => (metavalue(op, self.width()), Ieee1164::_X),
+        }
+    }
+
+    /// Adds `rhs` and returns the sum together with the carry out, which is `X` if any operand
+    /// contains a metavalue.
+    ///
+    /// ```rust
+    /// # use logical::{Ieee1164, LogicVector};
+    /// let a = LogicVector::from_int(200, 8).unwrap();
+    /// let b = LogicVector::from_int(100, 8).unwrap();
+    /// assert_eq!((LogicVector::from_int(44, 8).unwrap(), Ieee1164::_1), a.overflowing_add(&b));
+    /// ```
+    pub fn overflowing_add(&self, rhs: &LogicVector) -> (LogicVector, Ieee1164) {
+        let width = self.width();
+        self.binary_op(rhs, "+", |a, b| {
+            let sum = add(&a, &b);
+            let carry = bit(&sum, width);
+            (sum, carry)
+        })
+    }
+
+    /// Subtracts `rhs` and returns the difference together with the borrow, i.e. `1` if `rhs` is
+    /// greater than `self`.
+    pub fn overflowing_sub(&self, rhs: &LogicVector) -> (LogicVector, Ieee1164) {
+        let width = self.width();
+        self.binary_op(rhs, "-", |a, b| {
+            let diff = sub(&a, &b);
+            let borrow = bit(&diff, width);
+            (diff, borrow)
+        })
+    }
+
+    /// Multiplies with `rhs` and returns the lower half of the product together with a flag that
+    /// tells whether the product did not fit into the width of the operands.
+    pub fn overflowing_mul(&self, rhs: &LogicVector) -> (LogicVector, Ieee1164) {
+        let width = self.width();
+        self.binary_op(rhs, "*", |a, b| {
+            let product = mul(&a, &b);
+            let overflow = (width..product.len() * 64).any(|i| bit(&product, i));
+            (product, overflow)
+        })
+    }
+
+    /// Adds `rhs`, both interpreted as signed numbers, and returns the sum together with a flag
+    /// that tells whether the signed result overflowed.
+    ///
+    /// ```rust
+    /// # use logical::{Ieee1164, LogicVector};
+    /// let a = LogicVector::from_i128(100, 8).unwrap();
+    /// let b = LogicVector::from_i128(-100, 8).unwrap();
+    /// assert_eq!(Ieee1164::_1, a.signed_overflowing_add(&a).1);
+    /// assert_eq!(Ieee1164::_0, a.signed_overflowing_add(&b).1);
+    /// ```
+    pub fn signed_overflowing_add(&self, rhs: &LogicVector) -> (LogicVector, Ieee1164) {
+        let sign = self.width() - 1;
+        self.binary_op(rhs, "+", |a, b| {
+            let sum = add(&a, &b);
+            let overflow = bit(&a, sign) == bit(&b, sign) && bit(&sum, sign) != bit(&a, sign);
+            (sum, overflow)
+        })
+    }
+
+    /// Subtracts `rhs`, both interpreted as signed numbers, and returns the difference together
+    /// with a flag that tells whether the signed result overflowed.
+    pub fn signed_overflowing_sub(&self, rhs: &LogicVector) -> (LogicVector, Ieee1164) {
+        let sign = self.width() - 1;
+        self.binary_op(rhs, "-", |a, b| {
+            let diff = sub(&a, &b);
+            let overflow = bit(&a, sign) != bit(&b, sign) && bit(&diff, sign) != bit(&a, sign);
+            (diff, overflow)
+        })
+    }
+
+    /// Multiplies with `rhs`, both interpreted as signed numbers, and returns the lower half of the
+    /// product together with a flag that tells whether the signed product did not fit.
+    pub fn signed_overflowing_mul(&self, rhs: &LogicVector) -> (LogicVector, Ieee1164) {
+        let width = self.width();
+        self.binary_op(rhs, "*", |mut a, mut b| {
+            a.resize(2 * a.len(), 0);
+            b.resize(2 * b.len(), 0);
+            sign_extend(&mut a, width);
+            sign_extend(&mut b, width);
+            let product = mul(&a, &b);
+            let sign = bit(&product, width - 1);
+            let overflow = (width..a.len() * 64).any(|i| bit(&product, i) != sign);
+            (product, overflow)
+        })
+    }
+
+    /// Performs a wrapping subtraction. The result is all `X` if any operand contains a metavalue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widths of both operands differ.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let a = LogicVector::from_int(3, 8).unwrap();
+    /// let b = LogicVector::from_int(5, 8).unwrap();
+    /// assert_eq!(Some(254), a.wrapping_sub(&b).as_u128());
+    /// ```
+    pub fn wrapping_sub(&self, rhs: &LogicVector) -> LogicVector {
+        self.overflowing_sub(rhs).0
+    }
+
+    /// Performs a wrapping multiplication, i.e. only the lower `self.width()` bits of the product
+    /// are kept. The result is all `X` if any operand contains a metavalue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widths of both operands differ.
+    pub fn wrapping_mul(&self, rhs: &LogicVector) -> LogicVector {
+        self.overflowing_mul(rhs).0
+    }
+
+    /// Negates this value in two's complement, `-MIN` wraps around to `MIN`. The result is all `X`
+    /// if `self` contains a metavalue.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let a = LogicVector::from_i128(5, 8).unwrap();
+    /// assert_eq!(Some(-5), a.wrapping_neg().as_i128());
+    /// ```
+    pub fn wrapping_neg(&self) -> LogicVector {
+        match to_limbs(self, limbs_for(self.width())) {
+            Some(a) => from_limbs(&negate(&a), self.width()),
+            None => metavalue("-", self.width()),
+        }
+    }
+
+    /// Multiplies with `rhs` without losing any bit, so the width of the product is the sum of
+    /// both widths, just like `"*"` of `numeric_std`. The widths of the operands may differ.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let a = LogicVector::from_int(255, 8).unwrap();
+    /// let b = LogicVector::from_int(15, 4).unwrap();
+    /// let product = a.widening_mul(&b);
+    /// assert_eq!(12, product.width());
+    /// assert_eq!(Some(3825), product.as_u128());
+    /// ```
+    pub fn widening_mul(&self, rhs: &LogicVector) -> LogicVector {
+        let width = self.width() + rhs.width();
+        let n = limbs_for(width);
+        match (to_limbs(self, n), to_limbs(rhs, n)) {
+            (Some(a), Some(b)) => from_limbs(&mul(&a, &b), width),
+            _ => metavalue("*", width),
+        }
+    }
+
+    /// Like [`LogicVector::widening_mul`], but both operands are interpreted as signed numbers.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let a = LogicVector::from_i128(-128, 8).unwrap();
+    /// let b = LogicVector::from_i128(7, 4).unwrap();
+    /// assert_eq!(Some(-896), a.signed_widening_mul(&b).as_i128());
+    /// ```
+    pub fn signed_widening_mul(&self, rhs: &LogicVector) -> LogicVector {
+        let width = self.width() + rhs.width();
+        let n = limbs_for(width);
+        match (to_limbs(self, n), to_limbs(rhs, n)) {
+            (Some(mut a), Some(mut b)) => {
+                sign_extend(&mut a, self.width());
+                sign_extend(&mut b, rhs.width());
+                from_limbs(&mul(&a, &b), width)
+            }
+            _ => metavalue("*", width),
+        }
+    }
+
+    fn divide(&self, rhs: &LogicVector, op: &str, signed: bool) -> (LogicVector, LogicVector) {
+        assert_eq!(self.width(), rhs.width());
+        let width = self.width();
+        let n = limbs_for(width);
+        let (mut a, mut b) = match (to_limbs(self, n), to_limbs(rhs, n)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => {
+                return (
+                    metavalue(op, width),
+                    LogicVector::from_ieee(Ieee1164::_X, width),
+                )
+            }
+        };
+        if is_zero(&b) {
+            warn(&format!(
+                "NUMERIC_STD.\"{}\": division by zero, returning X",
+                op
+            ));
+            return (
+                LogicVector::from_ieee(Ieee1164::_X, width),
+                LogicVector::from_ieee(Ieee1164::_X, width),
+            );
+        }
+
+        let (neg_a, neg_b) = (signed && bit(&a, width - 1), signed && bit(&b, width - 1));
+        if neg_a {
+            sign_extend(&mut a, width);
+            a = negate(&a);
+        }
+        if neg_b {
+            sign_extend(&mut b, width);
+            b = negate(&b);
+        }
+        let (mut quotient, mut remainder) = divrem(&a, &b);
+        if neg_a != neg_b {
+            quotient = negate(&quotient);
+        }
+        if neg_a {
+            remainder = negate(&remainder);
+        }
+        (from_limbs(&quotient, width), from_limbs(&remainder, width))
+    }
+
+    /// Divides by `rhs` and returns the quotient and the remainder.
+    ///
+    /// The result is all `X` if any operand contains a metavalue or `rhs` is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widths of both operands differ.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let a = LogicVector::from_int(100, 8).unwrap();
+    /// let b = LogicVector::from_int(7, 8).unwrap();
+    /// let (q, r) = a.div_rem(&b);
+    /// assert_eq!((Some(14), Some(2)), (q.as_u128(), r.as_u128()));
+    /// ```
+    pub fn div_rem(&self, rhs: &LogicVector) -> (LogicVector, LogicVector) {
+        self.divide(rhs, "/", false)
+    }
+
+    /// Like [`LogicVector::div_rem`], but both operands are interpreted as signed numbers. The
+    /// quotient is rounded towards zero and the remainder has the sign of `self`, like `"/"` and
+    /// `"rem"` of `numeric_std`.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let a = LogicVector::from_i128(-100, 8).unwrap();
+    /// let b = LogicVector::from_i128(7, 8).unwrap();
+    /// let (q, r) = a.signed_div_rem(&b);
+    /// assert_eq!((Some(-14), Some(-2)), (q.as_i128(), r.as_i128()));
+    /// ```
+    pub fn signed_div_rem(&self, rhs: &LogicVector) -> (LogicVector, LogicVector) {
+        self.divide(rhs, "/", true)
+    }
+
+    /// Compares `self` and `other` as signed numbers. Returns `None` if the widths differ or any of
+    /// them contains a metavalue.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// # use std::cmp::Ordering;
+    /// let a = LogicVector::from_i128(-1, 8).unwrap();
+    /// let b = LogicVector::from_i128(1, 8).unwrap();
+    /// assert_eq!(Some(Ordering::Greater), a.partial_cmp(&b));
+    /// assert_eq!(Some(Ordering::Less), a.signed_partial_cmp(&b));
+    /// ```
+    pub fn signed_partial_cmp(&self, other: &LogicVector) -> Option<Ordering> {
+        if self.width() != other.width() {
+            return None;
+        }
+        let sign = self.width() - 1;
+        let n = limbs_for(self.width());
+        let (a, b) = (to_limbs(self, n)?, to_limbs(other, n)?);
+        Some(match (bit(&a, sign), bit(&b, sign)) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => cmp(&a, &b),
+        })
+    }
+}
+
+fn unsigned_sub(lhs: &LogicVector, rhs: &LogicVector) -> LogicVector {
+    lhs.wrapping_sub(rhs)
+}
+expand_op!(
+    unsigned_sub,
+    Sub,
+    sub,
+    LogicVector,
+    LogicVector,
+    LogicVector
+);
+
+fn unsigned_mul(lhs: &LogicVector, rhs: &LogicVector) -> LogicVector {
+    lhs.wrapping_mul(rhs)
+}
+expand_op!(
+    unsigned_mul,
+    Mul,
+    mul,
+    LogicVector,
+    LogicVector,
+    LogicVector
+);
+
+fn unsigned_div(lhs: &LogicVector, rhs: &LogicVector) -> LogicVector {
+    lhs.div_rem(rhs).0
+}
+expand_op!(
+    unsigned_div,
+    Div,
+    div,
+    LogicVector,
+    LogicVector,
+    LogicVector
+);
+
+fn unsigned_rem(lhs: &LogicVector, rhs: &LogicVector) -> LogicVector {
+    lhs.divide(rhs, "rem", false).1
+}
+expand_op!(
+    unsigned_rem,
+    Rem,
+    rem,
+    LogicVector,
+    LogicVector,
+    LogicVector
+);
+
+impl Neg for LogicVector {
+    type Output = LogicVector;
+
+    fn neg(self) -> <Self as Neg>::Output {
+        self.wrapping_neg()
+    }
+}
+
+impl Neg for &LogicVector {
+    type Output = LogicVector;
+
+    fn neg(self) -> <Self as Neg>::Output {
+        self.wrapping_neg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{prop_assert, prop_assert_eq, prop_assume, proptest, proptest_helper};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    fn lv(value: i128, width: usize) -> LogicVector {
+        LogicVector::from_i128(value, width).unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn atm_unsigned(a in 0u64.., b in 0u64.., width in 1usize..65) {
+            let mask = (1u128 << width) - 1;
+            let (a, b) = (u128::from(a) & mask, u128::from(b) & mask);
+            let (la, lb) = (LogicVector::from_int(a, width).unwrap(), LogicVector::from_int(b, width).unwrap());
+
+            let (sum, carry) = la.overflowing_add(&lb);
+            prop_assert_eq!(Some((a + b) & mask), sum.as_u128());
+            prop_assert_eq!(flag(a + b > mask), carry);
+            let (diff, borrow) = la.overflowing_sub(&lb);
+            prop_assert_eq!(Some(a.wrapping_sub(b) & mask), diff.as_u128());
+            prop_assert_eq!(flag(b > a), borrow);
+            let (product, overflow) = la.overflowing_mul(&lb);
+            prop_assert_eq!(Some((a * b) & mask), product.as_u128());
+            prop_assert_eq!(flag(a * b > mask), overflow);
+            prop_assert_eq!(Some(a * b), la.widening_mul(&lb).as_u128());
+
+            prop_assume!(b != 0);
+            let (q, r) = la.div_rem(&lb);
+            prop_assert_eq!((Some(a / b), Some(a % b)), (q.as_u128(), r.as_u128()));
+        }
+
+        #[test]
+        fn atm_signed(a in proptest::num::i64::ANY, b in proptest::num::i64::ANY, width in 2usize..65) {
+            let shift = 128 - width;
+            let (a, b) = (i128::from(a) << shift >> shift, i128::from(b) << shift >> shift);
+            let (min, max) = (-(1i128 << (width - 1)), (1i128 << (width - 1)) - 1);
+            let fits = |v: i128| v >= min && v <= max;
+            let wrap = |v: i128| v << shift >> shift;
+            let (la, lb) = (lv(a, width), lv(b, width));
+
+            let (sum, overflow) = la.signed_overflowing_add(&lb);
+            prop_assert_eq!((Some(wrap(a + b)), flag(!fits(a + b))), (sum.as_i128(), overflow));
+            let (diff, overflow) = la.signed_overflowing_sub(&lb);
+            prop_assert_eq!((Some(wrap(a - b)), flag(!fits(a - b))), (diff.as_i128(), overflow));
+            let (product, overflow) = la.signed_overflowing_mul(&lb);
+            prop_assert_eq!((Some(wrap(a * b)), flag(!fits(a * b))), (product.as_i128(), overflow));
+            prop_assert_eq!(Some(a * b), la.signed_widening_mul(&lb).as_i128());
+            prop_assert_eq!(Some(wrap(-a)), (-&la).as_i128());
+            prop_assert_eq!(Some(a.cmp(&b)), la.signed_partial_cmp(&lb));
+
+            prop_assume!(b != 0);
+            let (q, r) = la.signed_div_rem(&lb);
+            prop_assert_eq!((Some(wrap(a / b)), Some(a % b)), (q.as_i128(), r.as_i128()));
+        }
+    }
+
+    #[test]
+    fn wide() {
+        let max = LogicVector::from_ieee(Ieee1164::_1, 300);
+        let one = LogicVector::from_int(1, 300).unwrap();
+        assert_eq!(
+            (LogicVector::from_ieee(Ieee1164::_0, 300), Ieee1164::_1),
+            max.overflowing_add(&one)
+        );
+        assert_eq!(Some(1), (&max * &max).as_u128());
+        assert_eq!(Some(1), (&max / &max).as_u128());
+        assert_eq!(Some(1), (-&max).as_u128());
+        assert_eq!(Some(-1), max.signed_widening_mul(&one).as_i128());
+
+        let big = LogicVector::from_words(&[0, 1], 300).unwrap();
+        let (q, r) = big.div_rem(&LogicVector::from_int(3, 300).unwrap());
+        assert_eq!(Some(u128::MAX / 3), q.as_u128());
+        assert_eq!(Some(1), r.as_u128());
+    }
+
+    #[test]
+    fn metavalues() {
+        // the hook is global, so only the messages of this thread are collected
+        let messages = Arc::new(Mutex::new(vec![]));
+        let sink = messages.clone();
+        let test_thread = thread::current().id();
+        LogicVector::set_metavalue_hook(Some(Box::new(move |msg| {
+            if thread::current().id() == test_thread {
+                sink.lock().unwrap().push(msg.to_string())
+            }
+        })));
+
+        let a: LogicVector = "10H1L01W".parse().unwrap();
+        let b: LogicVector = "000000H1".parse().unwrap();
+        let zero = LogicVector::from_int(0, 8).unwrap();
+        assert_eq!(
+            (LogicVector::from_ieee(Ieee1164::_X, 8), Ieee1164::_X),
+            a.overflowing_add(&b)
+        );
+        assert!((&a - &b).is_XXX());
+        assert!((&b * &a).is_XXX());
+        assert!((-&a).is_XXX());
+        assert!(b.widening_mul(&a).is_XXX());
+        assert!((&b / &zero).is_XXX());
+        assert!((&b % &zero).is_XXX());
+        assert!((&a % &b).is_XXX());
+        assert_eq!(Some(6), (&b + &b).as_u128());
+        LogicVector::set_metavalue_hook(None);
+
+        let messages = messages.lock().unwrap();
+        assert!(
+            messages.contains(&"NUMERIC_STD.\"+\": metavalue detected, returning X".to_string())
+        );
+        assert!(messages.contains(&"NUMERIC_STD.\"/\": division by zero, returning X".to_string()));
+        assert!(
+            messages.contains(&"NUMERIC_STD.\"rem\": division by zero, returning X".to_string())
+        );
+        assert!(
+            messages.contains(&"NUMERIC_STD.\"rem\": metavalue detected, returning X".to_string())
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Operations that move bits of a [`LogicVector`] around, like slicing, concatenation, shifts and
+//! rotates. All of them work on the bitplanes directly, so every [`Ieee1164`] value is preserved.
+
+use std::ops::{Bound, RangeBounds, Shl, Shr};
+
+use super::masks::Masks;
+use super::LogicVector;
+use crate::Ieee1164;
+
+impl LogicVector {
+    fn from_masks(masks: Masks, width: usize) -> LogicVector {
+        let lv = LogicVector { masks, width };
+        debug_assert_eq!(Ok(()), lv.sanity_check());
+        lv
+    }
+
+    /// Returns the bits in `range` as a new `LogicVector`, where bit `range.start` becomes bit `0`.
+    /// The VHDL slice `v(7 downto 4)` or the Verilog slice `v[7:4]` is `v.slice(4..=7)`.
+    ///
+    /// Returns `None` if the range is empty or reaches beyond the width.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "1010ZX01".parse().unwrap();
+    /// assert_eq!("1010", lv.slice(4..=7).unwrap().to_string());
+    /// assert_eq!("ZX0", lv.slice(1..4).unwrap().to_string());
+    /// assert_eq!(None, lv.slice(4..9));
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Option<LogicVector> {
+        let start = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => s.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(e) => e.checked_add(1)?,
+            Bound::Excluded(e) => *e,
+            Bound::Unbounded => self.width,
+        };
+        if start >= end || end > self.width {
+            return None;
+        }
+        Some(Self::from_masks(
+            self.masks.extract(start, end - start),
+            end - start,
+        ))
+    }
+
+    /// Concatenates `self` and `lower`, where `self` becomes the upper part. This is `self & lower`
+    /// in VHDL or `{self, lower}` in Verilog.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let upper: LogicVector = "10".parse().unwrap();
+    /// let lower: LogicVector = "ZZX".parse().unwrap();
+    /// assert_eq!("10ZZX", upper.concat(&lower).to_string());
+    /// ```
+    pub fn concat(&self, lower: &LogicVector) -> LogicVector {
+        let width = self.width + lower.width;
+        let mut masks = Masks::new(width);
+        masks.insert(0, &lower.masks, lower.width);
+        masks.insert(lower.width, &self.masks, self.width);
+        Self::from_masks(masks, width)
+    }
+
+    /// Shifts all bits `n` positions towards the most significant bit. The upper bits are dropped
+    /// and the lower bits are filled with [`Ieee1164::_0`].
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "1ZX1".parse().unwrap();
+    /// assert_eq!("X100", lv.shift_left(2).to_string());
+    /// assert_eq!("0000", lv.shift_left(9).to_string());
+    /// ```
+    pub fn shift_left(&self, n: usize) -> LogicVector {
+        let n = n.min(self.width);
+        let mut masks = Masks::new(self.width);
+        masks.fill(Ieee1164::_0, 0, n);
+        masks.insert(n, &self.masks, self.width - n);
+        Self::from_masks(masks, self.width)
+    }
+
+    fn shift_right_with(&self, n: usize, value: Ieee1164) -> LogicVector {
+        let n = n.min(self.width);
+        let mut masks = Masks::new(self.width);
+        masks.insert(0, &self.masks.extract(n, self.width - n), self.width - n);
+        masks.fill(value, self.width - n, self.width);
+        Self::from_masks(masks, self.width)
+    }
+
+    /// Shifts all bits `n` positions towards the least significant bit. The lower bits are dropped
+    /// and the upper bits are filled with [`Ieee1164::_0`].
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "1ZX1".parse().unwrap();
+    /// assert_eq!("001Z", lv.shift_right(2).to_string());
+    /// ```
+    pub fn shift_right(&self, n: usize) -> LogicVector {
+        self.shift_right_with(n, Ieee1164::_0)
+    }
+
+    /// Like [`LogicVector::shift_right`], but the upper bits are filled with the most significant
+    /// bit, which keeps the sign of a signed number.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "1ZX1".parse().unwrap();
+    /// assert_eq!("111Z", lv.arithmetic_shift_right(2).to_string());
+    /// let lv: LogicVector = "W001".parse().unwrap();
+    /// assert_eq!("WWW0", lv.arithmetic_shift_right(2).to_string());
+    /// ```
+    pub fn arithmetic_shift_right(&self, n: usize) -> LogicVector {
+        self.shift_right_with(n, self.masks.get(self.width - 1))
+    }
+
+    /// Rotates all bits `n` positions towards the most significant bit, the upper bits wrap around.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "10ZX".parse().unwrap();
+    /// assert_eq!("ZX10", lv.rotate_left(2).to_string());
+    /// assert_eq!("0ZX1", lv.rotate_left(5).to_string());
+    /// ```
+    pub fn rotate_left(&self, n: usize) -> LogicVector {
+        let n = n % self.width;
+        let mut masks = Masks::new(self.width);
+        masks.insert(n, &self.masks, self.width - n);
+        masks.insert(0, &self.masks.extract(self.width - n, n), n);
+        Self::from_masks(masks, self.width)
+    }
+
+    /// Rotates all bits `n` positions towards the least significant bit, the lower bits wrap around.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "10ZX".parse().unwrap();
+    /// assert_eq!("X10Z", lv.rotate_right(1).to_string());
+    /// ```
+    pub fn rotate_right(&self, n: usize) -> LogicVector {
+        self.rotate_left(self.width - n % self.width)
+    }
+
+    /// Reverses the order of the bits, so the most significant bit becomes the least significant
+    /// one.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "10ZX".parse().unwrap();
+    /// assert_eq!("XZ01", lv.reverse().to_string());
+    /// ```
+    pub fn reverse(&self) -> LogicVector {
+        let mut masks = Masks::new(self.width);
+        for i in 0..self.width {
+            masks.set(self.width - 1 - i, self.masks.get(i));
+        }
+        Self::from_masks(masks, self.width)
+    }
+
+    /// Extends this vector to `new_width` bits, the new upper bits are set to [`Ieee1164::_0`].
+    ///
+    /// Returns `None` if `new_width` is smaller than the current width.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "1X".parse().unwrap();
+    /// assert_eq!("001X", lv.zero_extend(4).unwrap().to_string());
+    /// ```
+    pub fn zero_extend(&self, new_width: usize) -> Option<LogicVector> {
+        self.extend_with(new_width, Ieee1164::_0)
+    }
+
+    /// Extends this vector to `new_width` bits, the new upper bits are set to the value of the
+    /// most significant bit, so the value of a signed number does not change.
+    ///
+    /// Returns `None` if `new_width` is smaller than the current width.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "1X".parse().unwrap();
+    /// assert_eq!("111X", lv.sign_extend(4).unwrap().to_string());
+    /// ```
+    pub fn sign_extend(&self, new_width: usize) -> Option<LogicVector> {
+        self.extend_with(new_width, self.masks.get(self.width - 1))
+    }
+
+    fn extend_with(&self, new_width: usize, value: Ieee1164) -> Option<LogicVector> {
+        if new_width < self.width {
+            return None;
+        }
+        let mut lv = self.clone();
+        lv.resize(new_width, value);
+        Some(lv)
+    }
+
+    /// Concatenates `n` copies of this vector, like `{n{self}}` in Verilog.
+    ///
+    /// Returns `None` if `n` is `0`.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "1Z".parse().unwrap();
+    /// assert_eq!("1Z1Z1Z", lv.replicate(3).unwrap().to_string());
+    /// ```
+    pub fn replicate(&self, n: usize) -> Option<LogicVector> {
+        if n == 0 {
+            return None;
+        }
+        let width = self.width * n;
+        let mut masks = Masks::new(width);
+        for i in 0..n {
+            masks.insert(i * self.width, &self.masks, self.width);
+        }
+        Some(Self::from_masks(masks, width))
+    }
+}
+
+impl Shl<usize> for LogicVector {
+    type Output = LogicVector;
+
+    fn shl(self, n: usize) -> <Self as Shl<usize>>::Output {
+        self.shift_left(n)
+    }
+}
+
+impl Shl<usize> for &LogicVector {
+    type Output = LogicVector;
+
+    fn shl(self, n: usize) -> <Self as Shl<usize>>::Output {
+        self.shift_left(n)
+    }
+}
+
+impl Shr<usize> for LogicVector {
+    type Output = LogicVector;
+
+    fn shr(self, n: usize) -> <Self as Shr<usize>>::Output {
+        self.shift_right(n)
+    }
+}
+
+impl Shr<usize> for &LogicVector {
+    type Output = LogicVector;
+
+    fn shr(self, n: usize) -> <Self as Shr<usize>>::Output {
+        self.shift_right(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{prop_assert, prop_assert_eq, proptest, proptest_helper};
+
+    proptest! {
+        #[test]
+        fn atm_split_and_merge(ref s in "[ux10whlzd]{1,300}", split in 0usize..300) {
+            let lv: LogicVector = s.parse().unwrap();
+            let width = lv.width();
+            let split = split % width;
+
+            let lower = lv.slice(..split);
+            let upper = lv.slice(split..).unwrap();
+            let merged = match lower {
+                Some(lower) => upper.concat(&lower),
+                None => upper,
+            };
+            prop_assert_eq!(&lv, &merged);
+            prop_assert_eq!(&lv, &lv.rotate_left(split).rotate_right(split));
+            prop_assert_eq!(&lv, &lv.reverse().reverse());
+            prop_assert_eq!(lv.get(split), lv.reverse().get(width - 1 - split));
+            prop_assert_eq!(lv.get(0), lv.shift_left(split).get(split));
+            prop_assert_eq!(lv.get(width - 1), lv.shift_right(split).get(width - 1 - split));
+        }
+    }
+
+    #[test]
+    fn shifts_match_integers() {
+        let lv = LogicVector::from_int(0xdead_beef, 200).unwrap();
+        assert_eq!(Some(0xdead_beef << 60), (&lv << 60).as_u128());
+        assert_eq!(Some(0xdead), (&lv >> 16).as_u128());
+        assert_eq!(Some(0xdead_beef), (&lv << 100 >> 100).as_u128());
+        assert!((&lv << 200).is_000());
+
+        let negative = LogicVector::from_i128(-1000, 150).unwrap();
+        assert_eq!(
+            Some(-1000 >> 3),
+            negative.arithmetic_shift_right(3).as_i128()
+        );
+        assert_eq!(Some(-1000), negative.sign_extend(300).unwrap().as_i128());
+        assert_eq!(None, negative.sign_extend(149));
+    }
+
+    #[test]
+    fn bus_splitter() {
+        let bus: LogicVector = "UX01ZWLH-".parse().unwrap();
+        assert_eq!("UX01", bus.slice(5..9).unwrap().to_string());
+        assert_eq!("ZWLH-", bus.slice(..5).unwrap().to_string());
+        assert_eq!("-", bus.slice(0..=0).unwrap().to_string());
+        assert_eq!(None, bus.slice(3..3));
+        assert_eq!(None, bus.slice(..=9));
+        assert_eq!(None, bus.replicate(0));
+        assert_eq!(27, bus.replicate(3).unwrap().width());
+    }
+}
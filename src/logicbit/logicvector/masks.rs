@@ -1,82 +1,330 @@
 use crate::Ieee1164;
-use std::ops::{Index, IndexMut};
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+
+/// The number of bits in a single word of a bitplane.
+pub const BITS: usize = 128;
+
+/// Returns the number of words that are needed to store `width` bits.
+pub fn words(width: usize) -> usize {
+    width.div_ceil(BITS)
+}
+
+/// Returns a mask with the lowest `len` bits set, `len` must not be greater than [`BITS`].
+pub fn low_mask(len: usize) -> u128 {
+    if len >= BITS {
+        u128::MAX
+    } else {
+        (1 << len) - 1
+    }
+}
+
+/// Returns the mask of the valid bits in the most significant word of a `width` bit wide plane.
+pub fn top_mask(width: usize) -> u128 {
+    match width % BITS {
+        0 => u128::MAX,
+        r => low_mask(r),
+    }
+}
+
+/// Reads [`BITS`] bits of `plane`, starting at bit `offset`. Bits beyond the end are `0`.
+pub fn read(plane: &[u128], offset: usize) -> u128 {
+    let (w, s) = (offset / BITS, offset % BITS);
+    let lo = plane.get(w).copied().unwrap_or(0) >> s;
+    if s == 0 {
+        lo
+    } else {
+        lo | plane.get(w + 1).copied().unwrap_or(0) << (BITS - s)
+    }
+}
+
+/// Overwrites `len` bits (at most [`BITS`]) of `plane`, starting at bit `offset`, with the lowest
+/// bits of `value`.
+pub fn write(plane: &mut [u128], offset: usize, value: u128, len: usize) {
+    let mask = low_mask(len);
+    let value = value & mask;
+    let (w, s) = (offset / BITS, offset % BITS);
+    plane[w] = (plane[w] & !(mask << s)) | value << s;
+    if s != 0 && s + len > BITS {
+        plane[w + 1] = (plane[w + 1] & !(mask >> (BITS - s))) | value >> (BITS - s);
+    }
+}
+
+/// The words of a single bitplane.
+///
+/// Most vectors are not wider than [`BITS`] bits, so a plane with at most one word keeps that word
+/// inline instead of allocating it on the heap.
+#[derive(Debug, Clone)]
+pub enum Plane {
+    Inline(Option<u128>),
+    Heap(Vec<u128>),
+}
+
+impl Plane {
+    /// Creates a plane of `len` words that are all `0`.
+    pub fn zeroed(len: usize) -> Self {
+        if len <= 1 {
+            Plane::Inline(if len == 1 { Some(0) } else { None })
+        } else {
+            Plane::Heap(vec![0; len])
+        }
+    }
+
+    /// Changes the number of words to `len`, new words are `0`.
+    pub fn resize(&mut self, len: usize) {
+        match self {
+            Plane::Inline(word) if len <= 1 => {
+                if len == 0 {
+                    *word = None;
+                } else {
+                    word.get_or_insert(0);
+                }
+            }
+            _ => {
+                let mut words = self.to_vec();
+                words.resize(len, 0);
+                *self = words.into();
+            }
+        }
+    }
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Plane::Inline(None)
+    }
+}
+
+impl From<Vec<u128>> for Plane {
+    fn from(words: Vec<u128>) -> Self {
+        if words.len() <= 1 {
+            Plane::Inline(words.first().copied())
+        } else {
+            Plane::Heap(words)
+        }
+    }
+}
+
+impl Deref for Plane {
+    type Target = [u128];
+
+    fn deref(&self) -> &[u128] {
+        match self {
+            Plane::Inline(word) => word.as_slice(),
+            Plane::Heap(words) => words,
+        }
+    }
+}
+
+impl DerefMut for Plane {
+    fn deref_mut(&mut self) -> &mut [u128] {
+        match self {
+            Plane::Inline(word) => word.as_mut_slice(),
+            Plane::Heap(words) => words,
+        }
+    }
+}
+
+impl PartialEq for Plane {
+    fn eq(&self, other: &Plane) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Plane {}
+
+impl<'a> IntoIterator for &'a Plane {
+    type Item = &'a u128;
+    type IntoIter = std::slice::Iter<'a, u128>;
+
+    fn into_iter(self) -> <Self as IntoIterator>::IntoIter {
+        self.iter()
+    }
+}
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum SanityChecked {
-    MoreThanOne(u8),
-    NoOne(u8),
-    OneAboveWidth(u8),
+    MoreThanOne(usize),
+    NoOne(usize),
+    OneAboveWidth(usize),
+    WrongLength(usize),
 }
 
+/// One bitplane per [`Ieee1164`] value. Every plane consists of the same number of words, the
+/// least significant bit is bit 0 of the first word.
 #[allow(non_snake_case)]
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Masks {
-    _U: u128,
-    _X: u128,
-    _1: u128,
-    _0: u128,
-    _W: u128,
-    _H: u128,
-    _L: u128,
-    _Z: u128,
-    _D: u128,
+    _U: Plane,
+    _X: Plane,
+    _1: Plane,
+    _0: Plane,
+    _W: Plane,
+    _H: Plane,
+    _L: Plane,
+    _Z: Plane,
+    _D: Plane,
 }
 
 impl Masks {
-    pub fn get(&self, index: u8) -> Ieee1164 {
+    /// Creates empty planes that are able to hold `width` bits, so no bit is set to any value.
+    pub fn new(width: usize) -> Self {
+        let mut masks = Masks::default();
+        for m in masks.iter_mut() {
+            *m.1 = Plane::zeroed(words(width));
+        }
+        masks
+    }
+
+    /// Creates planes where all `width` bits are set to `value`.
+    pub fn filled(value: Ieee1164, width: usize) -> Self {
+        let mut masks = Masks::new(width);
+        masks.fill(value, 0, width);
+        masks
+    }
+
+    /// Sets the bits `from..to` to `value`.
+    pub fn fill(&mut self, value: Ieee1164, from: usize, to: usize) {
+        let mut offset = from;
+        while offset < to {
+            let len = (to - offset).min(BITS);
+            for m in self.iter_mut() {
+                let bits = if m.0 == value { u128::MAX } else { 0 };
+                write(m.1, offset, bits, len);
+            }
+            offset += len;
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Ieee1164 {
         for m in self.iter() {
-            if m.1 >> index & 1 == 1 {
+            if read(m.1, index) & 1 == 1 {
                 return m.0;
             }
         }
         panic!("No bit set on {}", index)
     }
 
-    pub fn set(&mut self, index: u8, value: Ieee1164) {
+    pub fn set(&mut self, index: usize, value: Ieee1164) {
         for m in self.iter_mut() {
-            if m.0 == value {
-                *m.1 |= 1 << index;
+            write(m.1, index, (m.0 == value) as u128, 1);
+        }
+    }
+
+    pub fn sanity_check(&self, width: usize) -> Result<(), SanityChecked> {
+        let n = words(width);
+        for m in self.iter() {
+            if m.1.len() != n {
+                return Err(SanityChecked::WrongLength(m.1.len()));
+            }
+        }
+
+        for w in 0..n {
+            let valid = if w + 1 == n {
+                top_mask(width)
             } else {
-                *m.1 &= !(1 << index);
+                u128::MAX
+            };
+            let mut seen = 0;
+            for m in self.iter() {
+                let word = m.1[w];
+                if seen & word != 0 {
+                    let bit = (seen & word).trailing_zeros() as usize;
+                    return Err(SanityChecked::MoreThanOne(w * BITS + bit));
+                }
+                if word & !valid != 0 {
+                    let bit = (word & !valid).trailing_zeros() as usize;
+                    return Err(SanityChecked::OneAboveWidth(w * BITS + bit));
+                }
+                seen |= word;
+            }
+            if seen != valid {
+                let bit = (!seen & valid).trailing_zeros() as usize;
+                return Err(SanityChecked::NoOne(w * BITS + bit));
             }
         }
+
+        Ok(())
     }
 
-    pub fn sanity_check(&self, width: u8) -> Result<(), SanityChecked> {
-        for d in 0..128 {
-            let mut has_one = false;
-            for mask in self {
-                if (mask.1 >> d) & 1 == 1 {
-                    if has_one {
-                        return Err(SanityChecked::MoreThanOne(d));
-                    }
-                    if d > width {
-                        return Err(SanityChecked::OneAboveWidth(d));
-                    }
-                    has_one = true;
+    /// Combines `self` and `other` bit by bit. Every bit of the result is set to `f(a, b)` where `a`
+    /// and `b` are the values of the same bit in `self` and `other`.
+    ///
+    /// Instead of looking at every single bit, all bits with the same pair of values are handled at
+    /// once, so this needs at most 81 mask operations per word, regardless of the width.
+    pub fn combine<F: Fn(Ieee1164, Ieee1164) -> Ieee1164>(&self, other: &Masks, f: F) -> Masks {
+        let mut masks = Masks::new(self._U.len() * BITS);
+        for (a, lhs) in self.iter() {
+            for (b, rhs) in other.iter() {
+                let target = &mut masks[f(a, b)];
+                for ((t, l), r) in target.iter_mut().zip(lhs).zip(rhs) {
+                    *t |= l & r;
                 }
             }
-            if d < width && !has_one {
-                return Err(SanityChecked::NoOne(d));
+        }
+        masks
+    }
+
+    /// Maps every bit to `f(value)`.
+    pub fn map<F: Fn(Ieee1164) -> Ieee1164>(&self, f: F) -> Masks {
+        let mut masks = Masks::new(self._U.len() * BITS);
+        for (a, plane) in self.iter() {
+            for (t, w) in masks[f(a)].iter_mut().zip(plane) {
+                *t |= w;
             }
         }
+        masks
+    }
 
-        Ok(())
+    /// Returns the bits `offset..offset + len` as new planes that are `len` bits wide.
+    pub fn extract(&self, offset: usize, len: usize) -> Masks {
+        let mut masks = Masks::new(len);
+        for (m_new, m_old) in masks.iter_mut().zip(self.iter()) {
+            for (i, w) in m_new.1.iter_mut().enumerate() {
+                *w = read(m_old.1, offset + i * BITS);
+            }
+            if let Some(last) = m_new.1.last_mut() {
+                *last &= top_mask(len);
+            }
+        }
+        masks
     }
 
-    pub fn iter(&self) -> Iter {
+    /// Overwrites the bits `offset..offset + len` with the lowest `len` bits of `other`.
+    pub fn insert(&mut self, offset: usize, other: &Masks, len: usize) {
+        for (m_self, m_other) in self.iter_mut().zip(other.iter()) {
+            let mut done = 0;
+            while done < len {
+                let chunk = (len - done).min(BITS);
+                write(m_self.1, offset + done, read(m_other.1, done), chunk);
+                done += chunk;
+            }
+        }
+    }
+
+    /// Changes the number of words, so the planes can hold `width` bits. Bits above `width` are
+    /// cleared, new bits are not set to any value.
+    pub fn resize(&mut self, width: usize) {
+        for m in self.iter_mut() {
+            m.1.resize(words(width));
+            if let Some(last) = m.1.last_mut() {
+                *last &= top_mask(width);
+            }
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
         self.into_iter()
     }
 
-    pub fn iter_mut(&mut self) -> IterMut {
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
         self.into_iter()
     }
 }
 
 impl Index<Ieee1164> for Masks {
-    type Output = u128;
+    type Output = Plane;
 
-    fn index(&self, index: Ieee1164) -> &u128 {
+    fn index(&self, index: Ieee1164) -> &Plane {
         match index {
             Ieee1164::_U => &self._U,
             Ieee1164::_X => &self._X,
@@ -92,7 +340,7 @@ impl Index<Ieee1164> for Masks {
 }
 
 impl IndexMut<Ieee1164> for Masks {
-    fn index_mut(&mut self, index: Ieee1164) -> &mut u128 {
+    fn index_mut(&mut self, index: Ieee1164) -> &mut Plane {
         match index {
             Ieee1164::_U => &mut self._U,
             Ieee1164::_X => &mut self._X,
@@ -107,80 +355,54 @@ impl IndexMut<Ieee1164> for Masks {
     }
 }
 
+pub type Iter<'a> = std::array::IntoIter<(Ieee1164, &'a Plane), 9>;
+pub type IterMut<'a> = std::array::IntoIter<(Ieee1164, &'a mut Plane), 9>;
+
 impl<'a> IntoIterator for &'a Masks {
-    type Item = (Ieee1164, &'a u128);
+    type Item = (Ieee1164, &'a Plane);
     type IntoIter = Iter<'a>;
 
     fn into_iter(self) -> <Self as IntoIterator>::IntoIter {
-        Iter { mask: self, pos: 0 }
+        IntoIterator::into_iter([
+            (Ieee1164::_U, &self._U),
+            (Ieee1164::_X, &self._X),
+            (Ieee1164::_1, &self._1),
+            (Ieee1164::_0, &self._0),
+            (Ieee1164::_W, &self._W),
+            (Ieee1164::_H, &self._H),
+            (Ieee1164::_L, &self._L),
+            (Ieee1164::_Z, &self._Z),
+            (Ieee1164::_D, &self._D),
+        ])
     }
 }
 
 impl<'a> IntoIterator for &'a mut Masks {
-    type Item = (Ieee1164, &'a mut u128);
+    type Item = (Ieee1164, &'a mut Plane);
     type IntoIter = IterMut<'a>;
 
     fn into_iter(self) -> <Self as IntoIterator>::IntoIter {
-        IterMut { mask: self, pos: 0 }
-    }
-}
-
-pub struct Iter<'a> {
-    mask: &'a Masks,
-    pos: u8,
-}
-
-impl<'a> Iterator for Iter<'a> {
-    type Item = (Ieee1164, &'a u128);
-
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.pos < 9 {
-            let res = match self.pos {
-                0 => (Ieee1164::_U, &self.mask._U),
-                1 => (Ieee1164::_X, &self.mask._X),
-                2 => (Ieee1164::_1, &self.mask._1),
-                3 => (Ieee1164::_0, &self.mask._0),
-                4 => (Ieee1164::_W, &self.mask._W),
-                5 => (Ieee1164::_H, &self.mask._H),
-                6 => (Ieee1164::_L, &self.mask._L),
-                7 => (Ieee1164::_Z, &self.mask._Z),
-                8 => (Ieee1164::_D, &self.mask._D),
-                _ => unreachable!(),
-            };
-            self.pos += 1;
-            Some(res)
-        } else {
-            None
-        }
-    }
-}
-
-pub struct IterMut<'a> {
-    mask: &'a mut Masks,
-    pos: u8,
-}
-
-impl<'a> Iterator for IterMut<'a> {
-    type Item = (Ieee1164, &'a mut u128);
-
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.pos < 9 {
-            let res = match self.pos {
-                0 => (Ieee1164::_U, &mut self.mask._U),
-                1 => (Ieee1164::_X, &mut self.mask._X),
-                2 => (Ieee1164::_1, &mut self.mask._1),
-                3 => (Ieee1164::_0, &mut self.mask._0),
-                4 => (Ieee1164::_W, &mut self.mask._W),
-                5 => (Ieee1164::_H, &mut self.mask._H),
-                6 => (Ieee1164::_L, &mut self.mask._L),
-                7 => (Ieee1164::_Z, &mut self.mask._Z),
-                8 => (Ieee1164::_D, &mut self.mask._D),
-                _ => unreachable!(),
-            };
-            self.pos += 1;
-            Some(unsafe { std::mem::transmute(res) }) //FIXME: Get rid of this unsafe!
-        } else {
-            None
-        }
+        let Masks {
+            _U,
+            _X,
+            _1,
+            _0,
+            _W,
+            _H,
+            _L,
+            _Z,
+            _D,
+        } = self;
+        IntoIterator::into_iter([
+            (Ieee1164::_U, _U),
+            (Ieee1164::_X, _X),
+            (Ieee1164::_1, _1),
+            (Ieee1164::_0, _0),
+            (Ieee1164::_W, _W),
+            (Ieee1164::_H, _H),
+            (Ieee1164::_L, _L),
+            (Ieee1164::_Z, _Z),
+            (Ieee1164::_D, _D),
+        ])
     }
 }
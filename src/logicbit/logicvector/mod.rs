@@ -1,14 +1,16 @@
+mod arith;
+mod bits;
 mod masks;
-use self::masks::{Masks, SanityChecked};
+mod radix;
+pub use self::arith::MetavalueHook;
+use self::masks::{top_mask, words, Masks, SanityChecked, BITS};
 
 use std::cmp::Ordering;
-use std::convert::TryFrom;
 use std::fmt;
-use std::hint::unreachable_unchecked;
 use std::ops::{Add, BitAnd, BitOr, BitXor, Not};
 use std::str::FromStr;
 
-use crate::{Ieee1164, Resolve};
+use crate::{Ieee1164, Resolve, Width};
 
 #[allow(unused)]
 macro_rules! expand_op_logicvector {
@@ -38,36 +40,28 @@ macro_rules! unsafe_version_logicvector {
     };
 }
 
-/// Generates a bitmask from a given width.
 #[inline(always)]
-pub fn mask_from_width(width: u8) -> u128 {
-    if width != 128 {
-        ((1 << width) - 1)
-    } else {
-        std::u128::MAX
-    }
-}
-
-#[inline(always)]
-fn assert_width(width: u8) -> bool {
-    width != 0 && width <= 128
+fn assert_width(width: usize) -> bool {
+    width != 0
 }
 
 /// A logicvector is an vector containing [`Ieee1164`] as values.
 ///
+/// Internally every [`Ieee1164`] value has its own bitmask, which consists of as many 128 bit words
+/// as needed, so the width is not limited to the size of a primitive integer.
+///
 /// # Invariant
 ///
-/// There are the following invariants for this struct.
+/// There is the following invariant for this struct.
 ///
 ///   1. The width is always not equals zero.
-///   2. The width is limited to 128.
 ///
-/// If any of these limitations are violated a panic will occur.
+/// If this limitation is violated a panic will occur.
 ///
 #[derive(Debug, Clone)]
 pub struct LogicVector {
     masks: Masks,
-    width: u8,
+    width: usize,
 }
 
 impl LogicVector {
@@ -81,26 +75,25 @@ impl LogicVector {
     /// assert_eq!(8, lv.width());
     /// assert!(lv.is_000());
     /// ```
-    pub fn from_ieee(value: Ieee1164, width: u8) -> Self {
+    pub fn from_ieee(value: Ieee1164, width: usize) -> Self {
         assert!(assert_width(width));
-        let mut s = Self {
-            masks: Masks::default(),
+        let s = Self {
+            masks: Masks::filled(value, width),
             width,
         };
-        s.masks[value] = std::u128::MAX & mask_from_width(width);
         debug_assert_eq!(Ok(()), s.sanity_check());
         s
     }
 
     /// Tries to convert an integer value with a given width to a `Logicvector`.
     ///
-    /// It will return `None` if the invariants are violated (e.g. the `width` is `0` or greater
-    /// than `128`), or the binary size of `value` is greater than `width`.
+    /// It will return `None` if the invariants are violated (e.g. the `width` is `0`), or the
+    /// binary size of `value` is greater than `width`.
     ///
     /// # Examples
     ///
-    /// This example is successful because the `width` (`8`) is greater than 0, less than 129 and
-    /// the bit representation of `42` (`0b101010`) fits into 8 bits.
+    /// This example is successful because the `width` (`8`) is greater than 0 and the bit
+    /// representation of `42` (`0b101010`) fits into 8 bits.
     ///
     /// ```rust
     /// use logical::LogicVector;
@@ -116,18 +109,69 @@ impl LogicVector {
     /// let lv = LogicVector::from_int(42, 5);
     /// assert!(lv.is_none());
     /// ```
-    pub fn from_int(value: u128, width: u8) -> Option<Self> {
-        let zeros = value.leading_zeros() as u8;
-        if assert_width(width) && width >= (128 - zeros) {
-            let mut masks = Masks::default();
-            masks[Ieee1164::_1] = value;
-            masks[Ieee1164::_0] = (!value) & mask_from_width(width);
-
-            debug_assert_eq!(Ok(()), masks.sanity_check(width));
-            Some(Self { masks, width })
-        } else {
-            None
+    pub fn from_int(value: u128, width: usize) -> Option<Self> {
+        Self::from_words(&[value], width)
+    }
+
+    /// Tries to convert a number, which is given as little endian 128 bit words, with a given
+    /// width to a `LogicVector`. Missing words are treated as `0`.
+    ///
+    /// It will return `None` if the `width` is `0`, or any bit at or above `width` is set.
+    ///
+    /// ```rust
+    /// use logical::LogicVector;
+    /// let lv = LogicVector::from_words(&[0, 1], 200).unwrap();
+    /// assert_eq!(200, lv.width());
+    /// assert_eq!(Some(vec![0, 1]), lv.to_words());
+    /// assert_eq!(None, lv.as_u128());
+    /// ```
+    pub fn from_words(value: &[u128], width: usize) -> Option<Self> {
+        if !assert_width(width) {
+            return None;
+        }
+
+        let n = words(width);
+        for (i, w) in value.iter().enumerate() {
+            let valid = match i {
+                i if i + 1 < n => u128::MAX,
+                i if i + 1 == n => top_mask(width),
+                _ => 0,
+            };
+            if w & !valid != 0 {
+                return None;
+            }
+        }
+
+        let mut masks = Masks::new(width);
+        for i in 0..n {
+            let w = value.get(i).copied().unwrap_or(0);
+            let valid = if i + 1 == n {
+                top_mask(width)
+            } else {
+                u128::MAX
+            };
+            masks[Ieee1164::_1][i] = w;
+            masks[Ieee1164::_0][i] = !w & valid;
+        }
+
+        debug_assert_eq!(Ok(()), masks.sanity_check(width));
+        Some(Self { masks, width })
+    }
+
+    /// Tries to convert a number, which is given as little endian bytes, with a given width to a
+    /// `LogicVector`. See [`LogicVector::from_words`].
+    ///
+    /// ```rust
+    /// use logical::LogicVector;
+    /// let lv = LogicVector::from_le_bytes(&[0x34, 0x12], 16).unwrap();
+    /// assert_eq!(Some(0x1234), lv.as_u128());
+    /// ```
+    pub fn from_le_bytes(bytes: &[u8], width: usize) -> Option<Self> {
+        let mut value = vec![0u128; bytes.len().div_ceil(16)];
+        for (i, b) in bytes.iter().enumerate() {
+            value[i / 16] |= u128::from(*b) << (8 * (i % 16));
         }
+        Self::from_words(&value, width)
     }
 
     /// Creates a LogicVector with the given width and all values are set to [`Ieee1164::_U`]
@@ -136,7 +180,7 @@ impl LogicVector {
     /// ```text
     /// LogicVector::from_ieee(Ieee1164::_U, width);
     /// ```
-    pub fn with_width(width: u8) -> Self {
+    pub fn with_width(width: usize) -> Self {
         assert!(assert_width(width));
         Self::from_ieee(Ieee1164::default(), width)
     }
@@ -149,7 +193,7 @@ impl LogicVector {
     /// # use logical::LogicVector;
     /// assert_eq!(7, LogicVector::with_width(7).width());
     /// ```
-    pub fn width(&self) -> u8 {
+    pub fn width(&self) -> usize {
         self.width
     }
 
@@ -158,7 +202,7 @@ impl LogicVector {
     /// ```text
     /// LogicVector::resize(new_width, Ieee1164::_U);
     /// ```
-    pub fn set_width(&mut self, new_width: u8) {
+    pub fn set_width(&mut self, new_width: usize) {
         self.resize(new_width, Ieee1164::_U);
         debug_assert_eq!(Ok(()), self.sanity_check());
     }
@@ -218,44 +262,21 @@ impl LogicVector {
     ///
     /// assert_eq!(Some(0b1100101010), lv.as_u128());
     /// ```
-    pub fn resize(&mut self, new_width: u8, value: Ieee1164) -> Option<LogicVector> {
-        fn resize_mask(old: u8, new: u8) -> u128 {
-            match (old, new) {
-                (a, b) if a >= b => unreachable!("`old` cannot be greater/equal than `new`!"),
-                (128, 128) => std::u128::MAX,
-                (a, 128) => std::u128::MAX & !((1 << a) - 1),
-                (a, b) => ((1u128 << b) - 1) & !((1 << a) - 1),
-            }
-        }
-
+    pub fn resize(&mut self, new_width: usize, value: Ieee1164) -> Option<LogicVector> {
         assert!(assert_width(new_width));
         let old_width = self.width();
-        self.width = new_width as u8;
+        self.width = new_width;
 
         let res = match old_width.cmp(&new_width) {
             Ordering::Equal => None,
             Ordering::Less => {
-                let mask = resize_mask(old_width, new_width);
-
-                for m in &mut self.masks {
-                    if m.0 == value {
-                        *m.1 |= std::u128::MAX & mask;
-                    } else {
-                        *m.1 &= !(std::u128::MAX & mask);
-                    }
-                }
+                self.masks.resize(new_width);
+                self.masks.fill(value, old_width, new_width);
                 None
             }
             Ordering::Greater => {
-                let mut nv = Masks::default();
-
-                let mask_nv = resize_mask(new_width, old_width);
-                let mask_ov = mask_from_width(new_width);
-                for (m_new, m_old) in nv.iter_mut().zip(self.masks.iter_mut()) {
-                    assert_eq!(m_new.0, m_old.0);
-                    *m_new.1 = (*m_old.1 & mask_nv) >> new_width;
-                    *m_old.1 &= std::u128::MAX & mask_ov;
-                }
+                let nv = self.masks.extract(new_width, old_width - new_width);
+                self.masks.resize(new_width);
 
                 Some(LogicVector {
                     masks: nv,
@@ -283,13 +304,7 @@ impl LogicVector {
     /// assert!(lv.is_ZZZ());
     /// ```
     pub fn set_all_to(&mut self, value: Ieee1164) {
-        for mask in &mut self.masks {
-            *mask.1 = if value == mask.0 {
-                mask_from_width(self.width)
-            } else {
-                0
-            }
-        }
+        self.masks = Masks::filled(value, self.width);
         debug_assert_eq!(Ok(()), self.sanity_check());
     }
 
@@ -307,8 +322,51 @@ impl LogicVector {
         Ok(())
     }
 
+    /// Tries to convert this to little endian 128 bit words. This will fail if the LogicVector
+    /// contains any other bits than [`Ieee1164::_0`], [`Ieee1164::_1`], [`Ieee1164::_L`] and
+    /// [`Ieee1164::_H`].
+    ///
+    /// ```rust
+    /// # use logical::{Ieee1164, LogicVector};
+    /// let mut lv = LogicVector::from_ieee(Ieee1164::_1, 130);
+    /// assert_eq!(Some(vec![u128::MAX, 0b11]), lv.to_words());
+    /// lv.set(129, Ieee1164::_Z);
+    /// assert_eq!(None, lv.to_words());
+    /// ```
+    pub fn to_words(&self) -> Option<Vec<u128>> {
+        if self.has_UXZ() {
+            None
+        } else {
+            Some(
+                self.masks[Ieee1164::_1]
+                    .iter()
+                    .zip(&self.masks[Ieee1164::_H])
+                    .map(|(one, high)| one | high)
+                    .collect(),
+            )
+        }
+    }
+
+    /// Tries to convert this to little endian bytes, see [`LogicVector::to_words`]. The number of
+    /// bytes is the width divided by 8, rounded up.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv = LogicVector::from_int(0x1234, 13).unwrap();
+    /// assert_eq!(Some(vec![0x34, 0x12]), lv.to_le_bytes());
+    /// ```
+    pub fn to_le_bytes(&self) -> Option<Vec<u8>> {
+        let value = self.to_words()?;
+        Some(
+            (0..self.width.div_ceil(8))
+                .map(|i| (value[i / 16] >> (8 * (i % 16))) as u8)
+                .collect(),
+        )
+    }
+
     /// Tries to convert this to a `u128`. This will fail if the LogicVector contains any other bits
-    /// than [`Ieee1164::_0`] and [`Ieee1164::_1`].
+    /// than [`Ieee1164::_0`] and [`Ieee1164::_1`] (or their weak counterparts), or the value does
+    /// not fit into 128 bits.
     ///
     /// ```rust
     /// # use logical::LogicVector;
@@ -324,15 +382,17 @@ impl LogicVector {
     /// assert_eq!(None, lv.as_u128());
     /// ```
     pub fn as_u128(&self) -> Option<u128> {
-        if self.has_UXZ() {
-            None
+        let value = self.to_words()?;
+        if value[1..].iter().all(|w| *w == 0) {
+            Some(value[0])
         } else {
-            Some(self.masks[Ieee1164::_1])
+            None
         }
     }
 
     /// Tries to convert this instance to a `i128`. This will fail if the LogicVector contains any
-    /// other bits than [`Ieee1164::_0`] and [`Ieee1164::_1`].
+    /// other bits than [`Ieee1164::_0`] and [`Ieee1164::_1`] (or their weak counterparts), or the
+    /// value does not fit into 128 bits.
     ///
     /// ```rust
     /// # use logical::LogicVector;
@@ -346,21 +406,25 @@ impl LogicVector {
     /// assert_eq!(Some(-73), lv.as_i128());
     /// ```
     pub fn as_i128(&self) -> Option<i128> {
-        if let Some(mut unsigned) = self.as_u128() {
-            Some(match (self.width(), (unsigned >> (self.width() - 1)) & 1) {
-                (128, _) => unsigned as i128,
-                (_, 0) => unsigned as i128,
-                (_, 1) => {
-                    for i in 0..unsigned.leading_zeros() {
-                        unsigned |= 1 << (127 - i);
-                    }
-                    unsigned as i128
-                }
-                (_, _) => {
-                    debug_assert!(false, "This point of code should not be reached, ever!");
-                    unsafe { unreachable_unchecked() }
-                }
-            })
+        let value = self.to_words()?;
+        if self.width <= BITS {
+            let shift = BITS - self.width;
+            return Some(((value[0] << shift) as i128) >> shift);
+        }
+
+        let top = self.width - 1;
+        let negative = (value[top / BITS] >> (top % BITS)) & 1 == 1;
+        let n = value.len();
+        let extended = value[1..]
+            .iter()
+            .enumerate()
+            .all(|(i, w)| match (negative, i + 2 == n) {
+                (false, _) => *w == 0,
+                (true, false) => *w == u128::MAX,
+                (true, true) => *w == top_mask(self.width),
+            });
+        if extended && (value[0] as i128 >= 0) != negative {
+            Some(value[0] as i128)
         } else {
             None
         }
@@ -370,7 +434,7 @@ impl LogicVector {
     ///
     /// If the index is out of range (`index >= self.width()`) then this function will return `None`,
     /// else `Some(value)` where value is the bit set at that bit position.
-    pub fn get(&self, index: u8) -> Option<Ieee1164> {
+    pub fn get(&self, index: usize) -> Option<Ieee1164> {
         if index < self.width() {
             Some(self.masks.get(index))
         } else {
@@ -390,7 +454,7 @@ impl LogicVector {
     /// lv.set(2, Ieee1164::_0);
     /// assert_eq!(Some(11), lv.as_u128());
     /// ```
-    pub fn set(&mut self, index: u8, value: Ieee1164) {
+    pub fn set(&mut self, index: usize, value: Ieee1164) {
         if index < self.width() {
             self.masks.set(index, value);
             debug_assert_eq!(Ok(()), self.sanity_check());
@@ -404,82 +468,37 @@ impl LogicVector {
     }
 }
 
-fn and(lhs: &LogicVector, rhs: &LogicVector) -> Option<LogicVector> {
+/// Applies `f` on every pair of bits of `lhs` and `rhs`, or returns `None` if their widths differ.
+fn bitwise<F>(lhs: &LogicVector, rhs: &LogicVector, f: F) -> Option<LogicVector>
+where
+    F: Fn(Ieee1164, Ieee1164) -> Ieee1164,
+{
     if lhs.width() != rhs.width() {
         return None;
     }
 
-    let mut masks = Masks::default();
-
-    if lhs.has_UXZ() || rhs.has_UXZ() {
-        for _ in 0..lhs.width {
-            unimplemented!()
-        }
-    } else {
-        let idx_1 = Ieee1164::_1;
-        let idx_0 = Ieee1164::_0;
-        masks[idx_1] = lhs.masks[idx_1] & rhs.masks[idx_1];
-        masks[idx_0] = lhs.masks[idx_0] & rhs.masks[idx_0];
-    }
-
+    let masks = lhs.masks.combine(&rhs.masks, f);
+    debug_assert_eq!(Ok(()), masks.sanity_check(lhs.width));
     Some(LogicVector {
         masks,
         width: lhs.width,
     })
 }
+
+fn and(lhs: &LogicVector, rhs: &LogicVector) -> Option<LogicVector> {
+    bitwise(lhs, rhs, BitAnd::bitand)
+}
 unsafe_version_logicvector!(and, unsafe_and);
 expand_op_logicvector!(unsafe_and, BitAnd, bitand);
 
 fn or(lhs: &LogicVector, rhs: &LogicVector) -> Option<LogicVector> {
-    if lhs.width() != rhs.width() {
-        return None;
-    }
-
-    let mut masks = Masks::default();
-
-    if lhs.has_UXZ() || rhs.has_UXZ() {
-        for _ in 0..lhs.width {
-            unimplemented!()
-        }
-    } else {
-        let idx_1 = Ieee1164::_1;
-        let idx_0 = Ieee1164::_0;
-        masks[idx_1] = lhs.masks[idx_1] | rhs.masks[idx_1];
-        masks[idx_0] = lhs.masks[idx_0] | rhs.masks[idx_0];
-    }
-
-    Some(LogicVector {
-        masks,
-        width: lhs.width,
-    })
+    bitwise(lhs, rhs, BitOr::bitor)
 }
 unsafe_version_logicvector!(or, unsafe_or);
 expand_op_logicvector!(unsafe_or, BitOr, bitor);
 
 fn xor(lhs: &LogicVector, rhs: &LogicVector) -> Option<LogicVector> {
-    if lhs.width() != rhs.width() {
-        return None;
-    }
-
-    let mut masks = Masks::default();
-
-    if lhs.has_UXZ() || rhs.has_UXZ() {
-        for _ in 0..lhs.width {
-            unimplemented!()
-        }
-    } else {
-        let idx_1 = Ieee1164::_1;
-        let idx_0 = Ieee1164::_0;
-        masks[idx_1] = lhs.masks[idx_1] ^ rhs.masks[idx_1];
-        masks[idx_0] = lhs.masks[idx_0] ^ rhs.masks[idx_0];
-    }
-
-    Some(LogicVector {
-        masks,
-        width: lhs.width,
-    })
-
-    //TODO maybe replace by macro and only provide & | ^
+    bitwise(lhs, rhs, BitXor::bitxor)
 }
 unsafe_version_logicvector!(xor, unsafe_xor);
 expand_op_logicvector!(unsafe_xor, BitXor, bitxor);
@@ -500,24 +519,17 @@ impl LogicVector {
         if self.width() != rhs.width() {
             return None;
         }
-        let width = self.width();
-        if let (Some(a), Some(b)) = (self.as_u128(), rhs.as_u128()) {
-            LogicVector::from_int((a + b) & mask_from_width(width), width)
-        } else {
-            Some(LogicVector::with_width(width))
-        }
+        Some(self.wrapping_add(rhs))
     }
 
-    /// Performa a wrapping addition
+    /// Performs a wrapping addition. The result is all `X` if any operand contains a metavalue,
+    /// see [`LogicVector::set_metavalue_hook`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widths of both operands differ.
     pub fn wrapping_add(&self, rhs: &LogicVector) -> LogicVector {
-        assert_eq!(self.width(), rhs.width());
-
-        let width = self.width();
-        if let (Some(a), Some(b)) = (self.as_u128(), rhs.as_u128()) {
-            LogicVector::from_int((a.wrapping_add(b)) & mask_from_width(width), width).unwrap()
-        } else {
-            LogicVector::with_width(width)
-        }
+        self.overflowing_add(rhs).0
     }
 
     /// Takes this value, increments it by one and returns it.
@@ -534,26 +546,25 @@ impl LogicVector {
 }
 
 fn add(lhs: &LogicVector, rhs: &LogicVector) -> LogicVector {
-    //fast, unsafe version
-    let width = lhs.width();
-    assert_eq!(width, rhs.width());
-
-    LogicVector::from_int(
-        (lhs.as_u128().unwrap() + rhs.as_u128().unwrap()) & mask_from_width(width),
-        width,
-    )
-    .unwrap()
+    lhs.wrapping_add(rhs)
 }
 expand_op_logicvector!(add, Add, add);
 
-fn resolve(_lhs: &LogicVector, _rhs: &LogicVector) -> LogicVector {
-    unimplemented!()
+fn resolve(lhs: &LogicVector, rhs: &LogicVector) -> LogicVector {
+    assert_eq!(lhs.width(), rhs.width());
+    bitwise(lhs, rhs, Ieee1164::resolve).unwrap()
 }
 expand_op!(resolve, Resolve, resolve, LogicVector, LogicVector, LogicVector);
 
+impl Width for LogicVector {
+    fn width(&self) -> usize {
+        self.width
+    }
+}
+
 impl PartialEq for LogicVector {
     fn eq(&self, other: &LogicVector) -> bool {
-        self.masks == other.masks
+        self.width == other.width && self.masks == other.masks
     }
 }
 
@@ -571,18 +582,7 @@ impl Not for &LogicVector {
     type Output = LogicVector;
 
     fn not(self) -> <Self as Not>::Output {
-        let mut masks = Masks::default();
-        masks[Ieee1164::_U] = self.masks[Ieee1164::_U];
-        masks[Ieee1164::_X] = self.masks[Ieee1164::_X];
-        masks[Ieee1164::_X] |= self.masks[Ieee1164::_Z];
-        masks[Ieee1164::_X] |= self.masks[Ieee1164::_W];
-        masks[Ieee1164::_X] |= self.masks[Ieee1164::_D];
-
-        masks[Ieee1164::_0] = self.masks[Ieee1164::_1];
-        masks[Ieee1164::_1] = self.masks[Ieee1164::_0];
-        masks[Ieee1164::_1] |= self.masks[Ieee1164::_L];
-        masks[Ieee1164::_0] |= self.masks[Ieee1164::_H];
-
+        let masks = self.masks.map(Not::not);
         debug_assert_eq!(Ok(()), masks.sanity_check(self.width()));
 
         LogicVector {
@@ -645,14 +645,18 @@ impl LogicVector {
     ///
     /// Returns true if so, false if that bit is not present in this LogicVector.
     pub fn has_ieee1164(&self, value: Ieee1164) -> bool {
-        self.masks[value] != 0
+        self.masks[value].iter().any(|w| *w != 0)
     }
 
     /// Checks if all bits are set to `value`.
     ///
     /// Returns true if so, false if even one single bit is not set to `value` in this LogicVector.
     pub fn is_ieee1164(&self, value: Ieee1164) -> bool {
-        self.masks[value] == std::u128::MAX & mask_from_width(self.width)
+        let plane = &self.masks[value];
+        let (last, rest) = plane
+            .split_last()
+            .expect("A LogicVector has at least one word");
+        *last == top_mask(self.width) && rest.iter().all(|w| *w == u128::MAX)
     }
 
     /// Checks wether any of the bits does not represent a logic high or logic low. This is only
@@ -682,60 +686,157 @@ impl LogicVector {
     gen_is!(is_DDD, Ieee1164::_D);
 }
 
+impl LogicVector {
+    fn map(&self, f: fn(Ieee1164) -> Ieee1164) -> LogicVector {
+        let masks = self.masks.map(f);
+        debug_assert_eq!(Ok(()), masks.sanity_check(self.width));
+        LogicVector {
+            masks,
+            width: self.width,
+        }
+    }
+
+    /// Applies [`Ieee1164::to_x01`] on every bit.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv: LogicVector = "UX01ZWLH-".parse().unwrap();
+    /// assert_eq!("XX01XX01X", lv.to_x01().to_string());
+    /// ```
+    pub fn to_x01(&self) -> LogicVector {
+        self.map(Ieee1164::to_x01)
+    }
+
+    /// Applies [`Ieee1164::to_x01z`] on every bit.
+    pub fn to_x01z(&self) -> LogicVector {
+        self.map(Ieee1164::to_x01z)
+    }
+
+    /// Applies [`Ieee1164::to_ux01`] on every bit.
+    pub fn to_ux01(&self) -> LogicVector {
+        self.map(Ieee1164::to_ux01)
+    }
+
+    /// Returns `true` if every bit matches the same bit of `other`, see [`Ieee1164::std_match`].
+    /// Vectors of different widths never match.
+    ///
+    /// This is handy to compare the output of a model against an expected value with don't-cares.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let expected: LogicVector = "10--".parse().unwrap();
+    /// assert!("HL0X".parse::<LogicVector>().unwrap().std_match(&expected));
+    /// assert!(!"X000".parse::<LogicVector>().unwrap().std_match(&expected));
+    /// ```
+    pub fn std_match(&self, other: &LogicVector) -> bool {
+        bitwise(self, other, |a, b| {
+            if a.std_match(b) {
+                Ieee1164::_1
+            } else {
+                Ieee1164::_0
+            }
+        })
+        .is_some_and(|m| m.is_111())
+    }
+
+    /// Returns a vector which has a [`Ieee1164::_1`] at every bit that has a rising edge from
+    /// `previous` to `current` and a [`Ieee1164::_0`] everywhere else, see
+    /// [`Ieee1164::rising_edge`]. Returns `None` if the widths differ.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let previous: LogicVector = "00L1X".parse().unwrap();
+    /// let current: LogicVector = "01H11".parse().unwrap();
+    /// assert_eq!("01100", LogicVector::rising_edges(&previous, &current).unwrap().to_string());
+    /// ```
+    pub fn rising_edges(previous: &LogicVector, current: &LogicVector) -> Option<LogicVector> {
+        bitwise(previous, current, |p, c| {
+            if Ieee1164::rising_edge(p, c) {
+                Ieee1164::_1
+            } else {
+                Ieee1164::_0
+            }
+        })
+    }
+
+    /// Like [`LogicVector::rising_edges`], but for falling edges, see [`Ieee1164::falling_edge`].
+    pub fn falling_edges(previous: &LogicVector, current: &LogicVector) -> Option<LogicVector> {
+        bitwise(previous, current, |p, c| {
+            if Ieee1164::falling_edge(p, c) {
+                Ieee1164::_1
+            } else {
+                Ieee1164::_0
+            }
+        })
+    }
+}
+
+/// Errors that can occur when parsing a [`LogicVector`] from a string.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum LogicVectorConversionError {
+    /// The string contains a character that is not allowed at that position.
     InalidChar(char),
+    /// The width would be zero, or the value does not fit into the given width.
     InvalidWidth,
+    /// The string looks like a Verilog or VHDL literal, but the size, base or digits are malformed.
+    InvalidLiteral,
+}
+
+impl fmt::Display for LogicVectorConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogicVectorConversionError::InalidChar(c) => write!(f, "invalid character '{}'", c),
+            LogicVectorConversionError::InvalidWidth => write!(f, "invalid width"),
+            LogicVectorConversionError::InvalidLiteral => write!(f, "malformed literal"),
+        }
+    }
 }
 
+impl std::error::Error for LogicVectorConversionError {}
+
 impl From<Vec<Ieee1164>> for LogicVector {
     fn from(v: Vec<Ieee1164>) -> LogicVector {
         let len = v.len();
-        assert!(assert_width(u8::try_from(len).unwrap()));
+        assert!(assert_width(len));
 
-        let mut masks = Masks::default();
+        let mut masks = Masks::new(len);
         for (i, v) in v.into_iter().enumerate() {
-            masks[v] |= 1 << (len - (i + 1));
+            masks.set(len - (i + 1), v);
         }
 
-        debug_assert_eq!(Ok(()), masks.sanity_check(len as u8));
-        LogicVector {
-            masks,
-            width: len as u8,
-        }
+        debug_assert_eq!(Ok(()), masks.sanity_check(len));
+        LogicVector { masks, width: len }
     }
 }
 
+/// Parses a `LogicVector` from one of the following formats:
+///
+///  - the bit characters of [`Ieee1164`], the most significant bit first, e.g. `"10ZX"`, which is
+///    also the format of `Display`
+///  - a Verilog literal, e.g. `"16'hdead"`, `"4'b10zx"`, `"8'd42"` or `"'o17"` (32 bits wide)
+///  - a VHDL bit string literal, e.g. `x"FF"`, `b"01_10"`, `"0110"` or the sized `12x"FF"`
+///
+/// ```rust
+/// # use logical::LogicVector;
+/// let lv: LogicVector = "8'hX3".parse().unwrap();
+/// assert_eq!("XXXX0011", lv.to_string());
+/// assert_eq!(lv, "X\"X3\"".parse::<LogicVector>().unwrap());
+/// ```
 impl FromStr for LogicVector {
     type Err = LogicVectorConversionError;
 
     fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
-        if !assert_width(u8::try_from(s.len()).map_err(|_| LogicVectorConversionError::InvalidWidth)?) {
-            Err(LogicVectorConversionError::InvalidWidth)
-        } else {
-            s.chars()
-                .try_fold(vec![], |mut v, c| {
-                    v.push(Ieee1164::try_from(c).map_err(|_| LogicVectorConversionError::InalidChar(c))?);
-                    Ok(v)
-                })
-                .map(|v| v.into())
-        }
+        radix::parse(s)
     }
 }
 
 impl fmt::Display for LogicVector {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        //TODO real formatting like padding etc
-        let mut s = String::new();
-        for i in (0..self.width).rev() {
-            for mask in &self.masks {
-                if (mask.1 >> i) & 1 == 1 {
-                    s.push(mask.0.into());
-                    continue;
-                }
-            }
-        }
-        write!(f, "{}", s)
+        let s: String = (0..self.width)
+            .rev()
+            .map(|i| char::from(self.masks.get(i)))
+            .collect();
+        f.pad(&s)
     }
 }
 
@@ -744,16 +845,17 @@ impl PartialOrd for LogicVector {
         if self.width() != other.width() {
             return None;
         }
-        if self.has_UXZ() || other.has_UXZ() {
-            return None;
-        }
 
-        self.as_u128().partial_cmp(&other.as_u128())
+        match (self.to_words(), other.to_words()) {
+            (Some(a), Some(b)) => Some(a.iter().rev().cmp(b.iter().rev())),
+            _ => None,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::masks::low_mask;
     use super::*;
     use proptest::{prop_assert, prop_assert_eq, prop_assume, proptest, proptest_helper};
 
@@ -777,7 +879,7 @@ mod tests {
             prop_assert_eq!(v.clone(), val as u128);
             v.resize(64, Ieee1164::_0);
             v.resize(128, Ieee1164::_1);
-            prop_assert_eq!(v.clone(), ((std::u64::MAX as u128 )<< 64) | (val as u128));
+            prop_assert_eq!(v.clone(), ((u64::MAX as u128 )<< 64) | (val as u128));
         }
 
         #[test]
@@ -811,11 +913,43 @@ mod tests {
         }
 
         #[test]
-        fn atm_not(a1 in 0u64.., a2 in 0..64, bitwidth in 1u8..129) {
-            let a = ((a1 as u128) << 64 | (a2 as u128)) & mask_from_width(bitwidth);
+        fn atm_resolve(ref a in "[ux10whlzd]{128}", ref b in "[ux10whlzd]{128}", width in 1usize..129) {
+            let lhs = a[..width].parse::<LogicVector>().unwrap();
+            let rhs = b[..width].parse::<LogicVector>().unwrap();
+            let res = (&lhs).resolve(&rhs);
+
+            prop_assert_eq!(Ok(()), res.sanity_check());
+            prop_assert_eq!(&res, &(&rhs).resolve(&lhs));
+            for i in 0..width {
+                prop_assert_eq!(lhs.get(i).unwrap().resolve(rhs.get(i).unwrap()), res.get(i).unwrap());
+            }
+        }
+
+        #[test]
+        fn atm_bitwise(ref a in "[ux10whlzd]{128}", ref b in "[ux10whlzd]{128}", width in 1usize..129) {
+            let lhs = a[..width].parse::<LogicVector>().unwrap();
+            let rhs = b[..width].parse::<LogicVector>().unwrap();
+            let and = &lhs & &rhs;
+            let or = &lhs | &rhs;
+            let xor = &lhs ^ &rhs;
+
+            prop_assert_eq!(Ok(()), and.sanity_check());
+            prop_assert_eq!(Ok(()), or.sanity_check());
+            prop_assert_eq!(Ok(()), xor.sanity_check());
+            for i in 0..width {
+                let (l, r) = (lhs.get(i).unwrap(), rhs.get(i).unwrap());
+                prop_assert_eq!(l & r, and.get(i).unwrap());
+                prop_assert_eq!(l | r, or.get(i).unwrap());
+                prop_assert_eq!(l ^ r, xor.get(i).unwrap());
+            }
+        }
+
+        #[test]
+        fn atm_not(a1 in 0u64.., a2 in 0..64, bitwidth in 1usize..129) {
+            let a = ((a1 as u128) << 64 | (a2 as u128)) & low_mask(bitwidth);
             let lv = LogicVector::from_int(a, bitwidth).unwrap();
 
-            prop_assert_eq!(Some((!a) & mask_from_width(bitwidth)), (!lv).as_u128());
+            prop_assert_eq!(Some((!a) & low_mask(bitwidth)), (!lv).as_u128());
         }
     }
 
@@ -918,6 +1052,135 @@ mod tests {
         assert_eq!(v, 0b1111101010);
     }
 
+    #[test]
+    fn resolve_bus() {
+        let driver1: LogicVector = "ZZZZ1010".parse().unwrap();
+        let driver2: LogicVector = "0110ZZZZ".parse().unwrap();
+        let pull_up: LogicVector = "HHHHHHHH".parse().unwrap();
+        let driver3: LogicVector = "1001ZZZZ".parse().unwrap();
+        assert_eq!(
+            "01101010".parse::<LogicVector>().unwrap(),
+            (&driver1).resolve(&driver2)
+        );
+        assert_eq!(
+            "0110HHHH".parse::<LogicVector>().unwrap(),
+            (&driver2).resolve(&pull_up)
+        );
+        assert_eq!(
+            "HHHH1010".parse::<LogicVector>().unwrap(),
+            (&driver1).resolve(&pull_up)
+        );
+        assert_eq!(
+            "XXXX1010".parse::<LogicVector>().unwrap(),
+            driver1.resolve(driver2).resolve(driver3)
+        );
+    }
+
+    #[test]
+    fn bitwise_metavalues() {
+        let lhs: LogicVector = "UX01ZWLH-".parse().unwrap();
+        let rhs: LogicVector = "L01HZ1HL0".parse().unwrap();
+        assert_eq!("0001XX000".parse::<LogicVector>().unwrap(), &lhs & &rhs);
+        assert_eq!("UX11X111X".parse::<LogicVector>().unwrap(), &lhs | &rhs);
+        assert_eq!("UX10XX11X".parse::<LogicVector>().unwrap(), &lhs ^ &rhs);
+    }
+
+    #[test]
+    fn wide_words() {
+        let words = [0xdead_beef, u128::MAX, 0x1234];
+        let lv = LogicVector::from_words(&words, 300).unwrap();
+        assert_eq!(Ok(()), lv.sanity_check());
+        assert_eq!(Some(words.to_vec()), lv.to_words());
+        assert_eq!(None, lv.as_u128());
+        assert_eq!(Some(Ieee1164::_1), lv.get(128));
+        assert_eq!(Some(Ieee1164::_0), lv.get(299));
+        assert_eq!(None, lv.get(300));
+
+        assert!(LogicVector::from_words(&words, 268).is_none());
+        assert!(LogicVector::from_words(&words, 269).is_some());
+        assert!(LogicVector::from_words(&[0, 0, 1], 256).is_none());
+        assert_eq!(
+            Some(vec![5, 0]),
+            LogicVector::from_words(&[5], 256).unwrap().to_words()
+        );
+
+        let bytes: Vec<u8> = (0..40).collect();
+        let lv = LogicVector::from_le_bytes(&bytes, 320).unwrap();
+        assert_eq!(Some(bytes), lv.to_le_bytes());
+    }
+
+    #[test]
+    fn wide_resize() {
+        let mut lv = LogicVector::from_int(u128::MAX, 128).unwrap();
+        lv.resize(200, Ieee1164::_Z);
+        assert_eq!(200, lv.width());
+        assert_eq!(Ok(()), lv.sanity_check());
+        assert_eq!(Some(Ieee1164::_1), lv.get(127));
+        assert_eq!(Some(Ieee1164::_Z), lv.get(128));
+        assert_eq!(Some(Ieee1164::_Z), lv.get(199));
+
+        let cropped = lv.resize(100, Ieee1164::_U).unwrap();
+        assert_eq!(100, cropped.width());
+        assert_eq!(Ok(()), cropped.sanity_check());
+        assert_eq!(Some(Ieee1164::_1), cropped.get(27));
+        assert_eq!(Some(Ieee1164::_Z), cropped.get(28));
+        assert_eq!(Some(u128::MAX >> 28), lv.as_u128());
+        assert_eq!(LogicVector::from_int(u128::MAX >> 28, 100), Some(lv));
+    }
+
+    #[test]
+    fn wide_string() {
+        let s: String = "10ZXUWLH-".chars().cycle().take(300).collect();
+        let lv: LogicVector = s.parse().unwrap();
+        assert_eq!(300, lv.width());
+        assert_eq!(Ok(()), lv.sanity_check());
+        assert_eq!(s, lv.to_string());
+        assert_eq!(Some(Ieee1164::_1), lv.get(299));
+        assert_eq!(!lv.clone(), !(!(!lv)));
+    }
+
+    #[test]
+    fn wide_add() {
+        let a = LogicVector::from_words(&[u128::MAX, 0], 200).unwrap();
+        let b = LogicVector::from_int(1, 200).unwrap();
+        assert_eq!(Some(vec![0, 1]), (&a + &b).to_words());
+
+        let max = LogicVector::from_ieee(Ieee1164::_1, 200);
+        assert!((&max + &b).is_000());
+        assert!(a < max);
+        assert_eq!(Some(-1), max.as_i128());
+        assert_eq!(None, a.as_i128());
+        assert_eq!(
+            Some(std::i128::MAX),
+            LogicVector::from_int(std::i128::MAX as u128, 200)
+                .unwrap()
+                .as_i128()
+        );
+    }
+
+    #[test]
+    fn strength_and_edges() {
+        let lv: LogicVector = "UX01ZWLH-".parse().unwrap();
+        assert_eq!("XX01ZX01X", lv.to_x01z().to_string());
+        assert_eq!("UX01XX01X", lv.to_ux01().to_string());
+        assert!(lv.std_match(&"---------".parse().unwrap()));
+        assert!(!lv.std_match(&lv));
+        assert!(!lv.std_match(&"-".parse().unwrap()));
+
+        let previous: LogicVector = "1HH0X".parse().unwrap();
+        let current: LogicVector = "0L1L0".parse().unwrap();
+        assert_eq!(
+            "11000",
+            LogicVector::falling_edges(&previous, &current)
+                .unwrap()
+                .to_string()
+        );
+        assert!(LogicVector::rising_edges(&previous, &current)
+            .unwrap()
+            .is_000());
+        assert_eq!(None, LogicVector::rising_edges(&previous, &lv));
+    }
+
     #[test]
     fn add() {}
 
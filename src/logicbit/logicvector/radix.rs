@@ -0,0 +1,462 @@
+//! Formatting of [`LogicVector`]s in other radixes than binary and parsing of Verilog and VHDL
+//! literals.
+//!
+//! A digit covers several bits. If all of them are `Z` the digit is `z`, if any of them is neither
+//! a logic high nor a logic low the digit is `x`, just like Verilog does it.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::{LogicVector, LogicVectorConversionError};
+use crate::Ieee1164;
+
+/// Returns the digits of `lv` in the radix `1 << bits`, the most significant digit first.
+fn digits(lv: &LogicVector, bits: usize, upper: bool) -> String {
+    let width = lv.width();
+    (0..width.div_ceil(bits))
+        .rev()
+        .map(|d| {
+            let (lo, hi) = (d * bits, ((d + 1) * bits).min(width));
+            let (mut value, mut all_z, mut unknown) = (0, true, false);
+            for i in lo..hi {
+                match lv.masks.get(i) {
+                    Ieee1164::_0 | Ieee1164::_L => all_z = false,
+                    Ieee1164::_1 | Ieee1164::_H => {
+                        value |= 1 << (i - lo);
+                        all_z = false;
+                    }
+                    Ieee1164::_Z => unknown = true,
+                    _ => {
+                        unknown = true;
+                        all_z = false;
+                    }
+                }
+            }
+            let c = match (all_z, unknown) {
+                (true, _) => 'z',
+                (false, true) => 'x',
+                (false, false) => std::char::from_digit(value, 16).unwrap(),
+            };
+            if upper {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Writes `lv` with the Verilog base `base`, the alternate flag (`#`) adds a size and base prefix,
+/// e.g. `8'h`.
+fn write_radix(
+    lv: &LogicVector,
+    f: &mut fmt::Formatter,
+    bits: usize,
+    base: char,
+    upper: bool,
+) -> fmt::Result {
+    let prefix = format!("{}'{}", lv.width(), base);
+    f.pad_integral(true, &prefix, &digits(lv, bits, upper))
+}
+
+impl fmt::Binary for LogicVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_radix(self, f, 1, 'b', false)
+    }
+}
+
+impl fmt::Octal for LogicVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_radix(self, f, 3, 'o', false)
+    }
+}
+
+impl fmt::LowerHex for LogicVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_radix(self, f, 4, 'h', false)
+    }
+}
+
+impl fmt::UpperHex for LogicVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_radix(self, f, 4, 'h', true)
+    }
+}
+
+/// Converts little endian words to a decimal string.
+fn decimal(words: &[u128]) -> String {
+    const CHUNK: u64 = 10_000_000_000_000_000_000;
+    let mut limbs: Vec<u64> = words
+        .iter()
+        .flat_map(|w| vec![*w as u64, (w >> 64) as u64])
+        .collect();
+    let mut chunks = vec![];
+    while limbs.iter().any(|l| *l != 0) {
+        let mut remainder = 0u128;
+        for l in limbs.iter_mut().rev() {
+            let cur = (remainder << 64) | u128::from(*l);
+            *l = (cur / u128::from(CHUNK)) as u64;
+            remainder = cur % u128::from(CHUNK);
+        }
+        chunks.push(remainder as u64);
+    }
+
+    match chunks.split_last() {
+        None => "0".to_string(),
+        Some((first, rest)) => rest
+            .iter()
+            .rev()
+            .fold(first.to_string(), |s, c| format!("{}{:019}", s, c)),
+    }
+}
+
+impl LogicVector {
+    /// Returns the value as an unsigned decimal number. If any bit is a metavalue, the result is
+    /// `z` if all bits are `Z` and `x` otherwise.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// let lv = LogicVector::from_words(&[0, 1], 200).unwrap();
+    /// assert_eq!("340282366920938463463374607431768211456", lv.to_dec_string());
+    /// assert_eq!("x", "10X1".parse::<LogicVector>().unwrap().to_dec_string());
+    /// ```
+    pub fn to_dec_string(&self) -> String {
+        match self.to_words() {
+            Some(words) => decimal(&words),
+            None if self.is_ZZZ() => "z".to_string(),
+            None => "x".to_string(),
+        }
+    }
+
+    /// Like [`LogicVector::to_dec_string`], but the value is interpreted as a signed number.
+    ///
+    /// ```rust
+    /// # use logical::LogicVector;
+    /// assert_eq!("-73", LogicVector::from_i128(-73, 8).unwrap().to_dec_string_signed());
+    /// ```
+    pub fn to_dec_string_signed(&self) -> String {
+        match self.to_words() {
+            Some(_) if self.masks.get(self.width() - 1).is_1H() => {
+                format!("-{}", decimal(&self.wrapping_neg().to_words().unwrap()))
+            }
+            _ => self.to_dec_string(),
+        }
+    }
+}
+
+/// Expands the digit `c` into `bits` values, the most significant one first. Digits that are no
+/// number are replicated, e.g. `Z` becomes `ZZZZ` in a hexadecimal literal.
+fn expand_digit(
+    c: char,
+    bits: usize,
+    verilog: bool,
+) -> Result<Vec<Ieee1164>, LogicVectorConversionError> {
+    if let Some(value) = c.to_digit(1 << bits) {
+        return Ok((0..bits)
+            .rev()
+            .map(|i| {
+                if (value >> i) & 1 == 1 {
+                    Ieee1164::_1
+                } else {
+                    Ieee1164::_0
+                }
+            })
+            .collect());
+    }
+
+    let value = match c {
+        'x' | 'X' => Ieee1164::_X,
+        'z' | 'Z' | '?' => Ieee1164::_Z,
+        _ if !verilog => {
+            Ieee1164::try_from(c).map_err(|_| LogicVectorConversionError::InalidChar(c))?
+        }
+        _ => return Err(LogicVectorConversionError::InalidChar(c)),
+    };
+    Ok(vec![value; bits])
+}
+
+/// Parses the digits of a literal, e.g. `dead` of `16'hdead`. Returns the bits, the most
+/// significant one first.
+fn expand_digits(
+    digits: &str,
+    base: char,
+    width: Option<usize>,
+    verilog: bool,
+) -> Result<Vec<Ieee1164>, LogicVectorConversionError> {
+    let digits: Vec<char> = digits.chars().filter(|c| *c != '_').collect();
+    if digits.is_empty() {
+        return Err(LogicVectorConversionError::InvalidLiteral);
+    }
+
+    let bits = match base.to_ascii_lowercase() {
+        'b' => 1,
+        'o' => 3,
+        'h' | 'x' => 4,
+        'd' => {
+            let width = width.ok_or(LogicVectorConversionError::InvalidLiteral)?;
+            return decimal_digits(&digits, width, verilog);
+        }
+        _ => return Err(LogicVectorConversionError::InvalidLiteral),
+    };
+
+    let mut values = vec![];
+    for c in digits {
+        values.extend(expand_digit(c, bits, verilog)?);
+    }
+    Ok(values)
+}
+
+/// Parses decimal digits into a `width` bit wide number, a Verilog literal may also consist of a
+/// single `x` or `z`.
+fn decimal_digits(
+    digits: &[char],
+    width: usize,
+    verilog: bool,
+) -> Result<Vec<Ieee1164>, LogicVectorConversionError> {
+    if verilog && digits.len() == 1 && !digits[0].is_ascii_digit() {
+        return Ok(vec![expand_digit(digits[0], 1, true)?[0]; width]);
+    }
+
+    let mut limbs = vec![0u64; width.div_ceil(64) + 1];
+    for c in digits {
+        let digit = c
+            .to_digit(10)
+            .ok_or(LogicVectorConversionError::InalidChar(*c))?;
+        let mut carry = u128::from(digit);
+        for l in limbs.iter_mut() {
+            let cur = u128::from(*l) * 10 + carry;
+            *l = cur as u64;
+            carry = cur >> 64;
+        }
+        if carry != 0 {
+            return Err(LogicVectorConversionError::InvalidWidth);
+        }
+    }
+    let words: Vec<u128> = limbs
+        .chunks(2)
+        .map(|c| u128::from(c[0]) | c.get(1).map_or(0, |h| u128::from(*h) << 64))
+        .collect();
+    let lv =
+        LogicVector::from_words(&words, width).ok_or(LogicVectorConversionError::InvalidWidth)?;
+    Ok((0..width).rev().map(|i| lv.masks.get(i)).collect())
+}
+
+/// Brings `values` to `width` bits. Missing bits are filled with `fill`, dropped bits have to be
+/// `0`.
+fn fit(
+    mut values: Vec<Ieee1164>,
+    width: usize,
+    fill: Ieee1164,
+) -> Result<LogicVector, LogicVectorConversionError> {
+    if width == 0 {
+        return Err(LogicVectorConversionError::InvalidWidth);
+    }
+    if values.len() > width {
+        let dropped = values.len() - width;
+        if values[..dropped].iter().any(|v| *v != Ieee1164::_0) {
+            return Err(LogicVectorConversionError::InvalidWidth);
+        }
+        values.drain(..dropped);
+    }
+    let mut padded = vec![fill; width - values.len()];
+    padded.extend(values);
+    Ok(padded.into())
+}
+
+fn parse_width(s: &str) -> Result<Option<usize>, LogicVectorConversionError> {
+    if s.is_empty() {
+        Ok(None)
+    } else {
+        s.parse()
+            .map(Some)
+            .map_err(|_| LogicVectorConversionError::InvalidLiteral)
+    }
+}
+
+/// Parses a Verilog literal like `8'hX3`, `4'b10zx` or `'d42`, where `s` is everything after the
+/// size.
+fn verilog(size: &str, s: &str) -> Result<LogicVector, LogicVectorConversionError> {
+    let width = parse_width(size.trim())?.unwrap_or(32);
+    let s = s.strip_prefix(|c| c == 's' || c == 'S').unwrap_or(s);
+    let base = s
+        .chars()
+        .next()
+        .ok_or(LogicVectorConversionError::InvalidLiteral)?;
+    let values = expand_digits(&s[base.len_utf8()..], base, Some(width), true)?;
+    let fill = match values[0] {
+        v @ Ieee1164::_X | v @ Ieee1164::_Z => v,
+        _ => Ieee1164::_0,
+    };
+    fit(values, width, fill)
+}
+
+/// Parses a VHDL bit string literal like `x"FF"`, `b"01_10"`, `"0101"` or `12d"42"`.
+fn vhdl(s: &str) -> Result<LogicVector, LogicVectorConversionError> {
+    let open = s
+        .find('"')
+        .ok_or(LogicVectorConversionError::InvalidLiteral)?;
+    if open + 1 >= s.len() {
+        return Err(LogicVectorConversionError::InvalidLiteral);
+    }
+    let (prefix, digits) = (&s[..open], &s[open + 1..s.len() - 1]);
+    let size_len = prefix
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(prefix.len());
+    let width = parse_width(&prefix[..size_len])?;
+    let base = match &prefix[size_len..] {
+        "" => 'b',
+        b if b.len() == 1 => b.chars().next().unwrap(),
+        _ => return Err(LogicVectorConversionError::InvalidLiteral),
+    };
+    let values = expand_digits(digits, base, width, false)?;
+    let width = width.unwrap_or(values.len());
+    fit(values, width, Ieee1164::_0)
+}
+
+/// Parses any of the formats that are accepted by `LogicVector::from_str`.
+pub(super) fn parse(s: &str) -> Result<LogicVector, LogicVectorConversionError> {
+    if let Some(pos) = s.find('\'') {
+        verilog(&s[..pos], &s[pos + 1..])
+    } else if s.ends_with('"') {
+        vhdl(s)
+    } else if s.is_empty() {
+        Err(LogicVectorConversionError::InvalidWidth)
+    } else {
+        s.chars()
+            .try_fold(vec![], |mut v, c| {
+                v.push(
+                    Ieee1164::try_from(c).map_err(|_| LogicVectorConversionError::InalidChar(c))?,
+                );
+                Ok(v)
+            })
+            .map(|v| v.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lv(s: &str) -> LogicVector {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn format_radix() {
+        let v = lv("10100011");
+        assert_eq!("a3", format!("{:x}", v));
+        assert_eq!("A3", format!("{:X}", v));
+        assert_eq!("243", format!("{:o}", v));
+        assert_eq!("10100011", format!("{:b}", v));
+        assert_eq!("8'ha3", format!("{:#x}", v));
+        assert_eq!("0x0a3", format!("0x{:03x}", v));
+        assert_eq!("163", v.to_dec_string());
+        assert_eq!("-93", v.to_dec_string_signed());
+
+        assert_eq!("8'hX3", format!("{:#X}", lv("X0W10011")));
+        assert_eq!("zx", format!("{:x}", lv("ZZZZZ0HL")));
+        assert_eq!("10xz01x", format!("{:b}", lv("10UZLHW")));
+        assert_eq!("1z", format!("{:o}", lv("1ZZZ")));
+        assert_eq!("z", lv("ZZZ").to_dec_string());
+        assert_eq!("  1X", format!("{:>4}", lv("1X")));
+    }
+
+    #[test]
+    fn format_wide() {
+        let v = LogicVector::from_ieee(Ieee1164::_1, 256);
+        assert_eq!("f".repeat(64), format!("{:x}", v));
+        assert_eq!(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+            v.to_dec_string()
+        );
+        assert_eq!("-1", v.to_dec_string_signed());
+        assert_eq!("0", LogicVector::from_int(0, 300).unwrap().to_dec_string());
+    }
+
+    #[test]
+    fn parse_verilog() {
+        assert_eq!(LogicVector::from_int(0xdead, 16).unwrap(), lv("16'hdead"));
+        assert_eq!(lv("10ZX"), lv("4'b10zx"));
+        assert_eq!(lv("0000010Z"), lv("8'b10?"));
+        assert_eq!(lv("XXXX0011"), lv("8'hx3"));
+        assert_eq!(lv("ZZZZZZZZ"), lv("8'hz"));
+        assert_eq!(lv("XXXXXX"), lv("6'dx"));
+        assert_eq!(LogicVector::from_int(42, 7).unwrap(), lv("7'd42"));
+        assert_eq!(LogicVector::from_int(0o17, 6).unwrap(), lv("6'o17"));
+        assert_eq!(LogicVector::from_int(5, 32).unwrap(), lv("'d5"));
+        assert_eq!(LogicVector::from_int(0xff, 8).unwrap(), lv("8'shFF"));
+        assert_eq!(
+            LogicVector::from_int(0x1_0000, 20).unwrap(),
+            lv("20'h1_0000")
+        );
+        assert_eq!(LogicVector::from_int(5, 3).unwrap(), lv("3'h5"));
+
+        assert_eq!(
+            Err(LogicVectorConversionError::InvalidWidth),
+            "3'hf".parse::<LogicVector>()
+        );
+        assert_eq!(
+            Err(LogicVectorConversionError::InvalidWidth),
+            "4'd16".parse::<LogicVector>()
+        );
+        assert_eq!(
+            Err(LogicVectorConversionError::InalidChar('g')),
+            "8'hg".parse::<LogicVector>()
+        );
+        assert_eq!(
+            Err(LogicVectorConversionError::InalidChar('w')),
+            "8'bw".parse::<LogicVector>()
+        );
+        assert_eq!(
+            Err(LogicVectorConversionError::InvalidLiteral),
+            "8'q1".parse::<LogicVector>()
+        );
+        assert_eq!(
+            Err(LogicVectorConversionError::InvalidLiteral),
+            "8'h".parse::<LogicVector>()
+        );
+    }
+
+    #[test]
+    fn parse_vhdl() {
+        assert_eq!(LogicVector::from_int(0xff, 8).unwrap(), lv("x\"FF\""));
+        assert_eq!(lv("0110"), lv("b\"01_10\""));
+        assert_eq!(lv("0110"), lv("\"0110\""));
+        assert_eq!(lv("001111"), lv("O\"17\""));
+        assert_eq!(lv("ZZZZ0011"), lv("X\"Z3\""));
+        assert_eq!(lv("UUUU-"), lv("B\"UUUU-\""));
+        assert_eq!(lv("000011111111"), lv("12x\"FF\""));
+        assert_eq!(lv("111"), lv("3x\"7\""));
+        assert_eq!(LogicVector::from_int(300, 10).unwrap(), lv("10d\"300\""));
+
+        assert_eq!(
+            Err(LogicVectorConversionError::InvalidWidth),
+            "3x\"F\"".parse::<LogicVector>()
+        );
+        assert_eq!(
+            Err(LogicVectorConversionError::InvalidLiteral),
+            "d\"300\"".parse::<LogicVector>()
+        );
+        assert_eq!(
+            Err(LogicVectorConversionError::InvalidLiteral),
+            "q\"1\"".parse::<LogicVector>()
+        );
+        assert_eq!(
+            Err(LogicVectorConversionError::InalidChar('Q')),
+            "x\"Q\"".parse::<LogicVector>()
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for s in &["10UZLHW-X", "1", "ZZZZ0000XXXX1111"] {
+            let v = lv(s);
+            assert_eq!(v, lv(&format!("{}", v)));
+            let binary = format!("{:#b}", v);
+            assert_eq!(binary, format!("{:#b}", lv(&binary)));
+        }
+        let v = LogicVector::from_words(&[0xdead_beef, 42], 200).unwrap();
+        assert_eq!(v, lv(&format!("{:#x}", v)));
+        assert_eq!(v, lv(&format!("{}'d{}", v.width(), v.to_dec_string())));
+    }
+}
@@ -3,7 +3,7 @@ mod logicvector;
 mod tvlogic;
 
 pub use self::ieee1164::Ieee1164;
-pub use self::logicvector::{mask_from_width, LogicVector};
+pub use self::logicvector::{LogicVector, LogicVectorConversionError, MetavalueHook};
 pub use self::tvlogic::Ieee1164Value;
 
 /// This trait is similar to `Add`, `Sub`, `Mul`, ... and is used to describe how values on the
@@ -19,3 +19,10 @@ pub trait Resolve<RHS = Self> {
     /// The type is not restricted and can be freely chosen.
     fn resolve(self, rhs: RHS) -> Self::Output;
 }
+
+/// Describes the number of bits of a value. Only [`Port`](crate::Port)s with the same width can be
+/// connected to the same [`Signal`](crate::Signal).
+pub trait Width {
+    /// Returns the number of bits of `self`.
+    fn width(&self) -> usize;
+}
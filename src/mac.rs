@@ -55,13 +55,33 @@ macro_rules! signal {
     }
 }
 
+/// A helper macro to create a [`Circuit`](crate::Circuit) from multiple models and signals. The
+/// `Circuit` takes the ownership of all of them, see [`Circuit::add`](crate::Circuit::add).
+///
+/// # Example
+///
+/// ```rust
+/// # use logical::{circuit, signal, Circuit, Ieee1164, Port, Signal};
+/// # use logical::direction::{Input, Output};
+/// # use logical::models::gates::Inverter;
+/// let input = Port::<_, Output>::new(Ieee1164::_0);
+/// let output = Port::<_, Input>::default();
+/// let inv = Inverter::default();
+///
+/// let sig_in = signal!(input, inv.a);
+/// let sig_out = signal!(inv.z, output);
+///
+/// let mut circuit = circuit!(sig_in, inv, sig_out);
+/// circuit.step().unwrap();
+/// assert_eq!(Ieee1164::_1, output.value());
+/// ```
 #[macro_export]
 macro_rules! circuit {
     ( $( $x:expr ),* ) => {
         {
             let mut circuit = Circuit::default();
             $(
-                circuit.add_updater(&$x);
+                circuit.add($x);
             )*
             circuit
         }
@@ -1,45 +1,105 @@
 //! This module provides logic gates that work with [`Ieee1164`](crate::Ieee1164), e.g.
 //! [`AndGate`](crate::models::gates::AndGate), [`OrGate`](crate::models::gates::OrGate),
 //! [`Buffer`](crate::models::gates::Buffer), [`Mux`](crate::models::gates::Mux), etc.
+//!
+//! Besides the 2-input gates there are gates with any number of inputs, e.g.
+//! [`WideAndGate`](crate::models::gates::WideAndGate), gates that work bitwise on two
+//! [`LogicVector`](crate::LogicVector)s, e.g. [`VectorAndGate`](crate::models::gates::VectorAndGate),
+//! and gates that reduce a [`LogicVector`](crate::LogicVector) to a single bit, e.g.
+//! [`XorReduce`](crate::models::gates::XorReduce).
+
+macro_rules! impl_gate_delay {
+    ($name:ident) => {
+        impl $name {
+            /// Creates this gate with the given propagation [`Delay`] on its output.
+            pub fn with_delay(delay: Delay) -> Self {
+                let mut gate = Self::default();
+                gate.set_delay(delay);
+                gate
+            }
+        }
+
+        impl_gate_delay!(@accessors $name);
+    };
+    (@accessors $name:ident) => {
+        impl $name {
+            /// Returns the propagation [`Delay`] of this gate.
+            pub fn delay(&self) -> Delay {
+                self.driver.delay()
+            }
+
+            /// Sets the propagation [`Delay`] of this gate. Already scheduled changes are not
+            /// affected.
+            pub fn set_delay(&mut self, delay: Delay) {
+                self.driver.set_delay(delay);
+            }
+        }
+    };
+}
 
 mod mux;
+mod reduce;
 mod tri;
+mod vector;
+mod wide;
 
 pub use self::mux::Mux;
+pub use self::reduce::{AndReduce, NandReduce, NorReduce, OrReduce, XnorReduce, XorReduce};
 pub use self::tri::TriBuffer;
+pub use self::vector::{
+    VectorAndGate, VectorInverter, VectorNandGate, VectorNorGate, VectorOrGate, VectorXnorGate,
+    VectorXorGate,
+};
+pub use self::wide::{
+    WideAndGate, WideNandGate, WideNorGate, WideOrGate, WideXnorGate, WideXorGate,
+};
 
 use crate::direction::{Input, Output};
 
-use crate::dump::IterPorts;
-use crate::{Ieee1164, Port, Updateable};
+use crate::time::Waveform;
+use crate::Component;
+use crate::{Delay, Ieee1164, Port, PortId, Time, Updateable};
 
 macro_rules! create_simple_1i1o_gate {
     ($name:ident, $func:ident, $doc:tt) => {
-        #[derive(Debug, Default, Clone)] //TODO: remove Clone!
+        #[derive(Debug, Default, Component)]
         #[doc = $doc]
         pub struct $name {
             /// Input `Port`
+            #[port(input)]
             pub a: Port<Ieee1164, Input>,
             /// Output `Port`
+            #[port(output)]
             pub z: Port<Ieee1164, Output>,
+            driver: Waveform<Ieee1164>,
             _private: (),
         }
 
+        impl_gate_delay!($name);
+
         impl Updateable for $name {
             fn update(&mut self) -> bool {
                 let new_value = $func(self.a.value());
+                self.driver.force(new_value);
                 let old_value = self.z.replace(new_value);
                 old_value != new_value
             }
-        }
 
-        impl IterPorts for $name {
-            fn iter_ports<F>(&self, mut f: F)
-            where
-                F: FnMut(&str, &Port<Ieee1164, Output>),
-            {
-                f("a", &Port::new_with_arc(self.a.inner.clone()));
-                f("z", &Port::new_with_arc(self.z.inner.clone()));
+            fn update_at(&mut self, now: Time) -> bool {
+                let new_value = $func(self.a.value());
+                self.driver.drive(now, new_value, &mut self.z)
+            }
+
+            fn next_event(&self) -> Option<Time> {
+                self.driver.next_event()
+            }
+
+            fn sensitivity(&self) -> Vec<PortId> {
+                self.input_ids()
+            }
+
+            fn drives(&self) -> Vec<PortId> {
+                self.output_ids()
             }
         }
     };
@@ -47,34 +107,47 @@ macro_rules! create_simple_1i1o_gate {
 
 macro_rules! create_simple_2i1o_gate {
     ($name:ident, $func:ident, $doc:tt) => {
-        #[derive(Debug, Default, Clone)] //TODO: remove Clone!
+        #[derive(Debug, Default, Component)]
         #[doc = $doc]
         pub struct $name {
             /// First input port
+            #[port(input)]
             pub a: Port<Ieee1164, Input>,
             /// Second input port
+            #[port(input)]
             pub b: Port<Ieee1164, Input>,
             /// Output port
+            #[port(output)]
             pub z: Port<Ieee1164, Output>,
+            driver: Waveform<Ieee1164>,
             _private: (),
         }
 
+        impl_gate_delay!($name);
+
         impl Updateable for $name {
             fn update(&mut self) -> bool {
                 let new_value = $func(self.a.value(), self.b.value());
+                self.driver.force(new_value);
                 let old_value = self.z.replace(new_value);
                 old_value != new_value
             }
-        }
 
-        impl IterPorts for $name {
-            fn iter_ports<F>(&self, mut f: F)
-            where
-                F: FnMut(&str, &Port<Ieee1164, Output>),
-            {
-                f("a", &Port::new_with_arc(self.a.inner.clone()));
-                f("b", &Port::new_with_arc(self.b.inner.clone()));
-                f("z", &Port::new_with_arc(self.z.inner.clone()));
+            fn update_at(&mut self, now: Time) -> bool {
+                let new_value = $func(self.a.value(), self.b.value());
+                self.driver.drive(now, new_value, &mut self.z)
+            }
+
+            fn next_event(&self) -> Option<Time> {
+                self.driver.next_event()
+            }
+
+            fn sensitivity(&self) -> Vec<PortId> {
+                self.input_ids()
+            }
+
+            fn drives(&self) -> Vec<PortId> {
+                self.output_ids()
             }
         }
     };
@@ -1,6 +1,7 @@
 use crate::direction::{Input, Output};
-use crate::dump::IterPorts;
-use crate::{Ieee1164, Port, Updateable};
+use crate::time::Waveform;
+use crate::Component;
+use crate::{Delay, Ieee1164, Port, PortId, Time, Updateable};
 
 /// A Multiplexer can be seen as an `if`-statement. If [`Mux::s`] is driven low, [`Mux::a`] is
 /// outputted on [`Mux::z`], if [`Mux::s`] is driven high [`Mux::b`] will be outputted.
@@ -36,60 +37,79 @@ use crate::{Ieee1164, Port, Updateable};
 /// sig_z.connect(&mux.z);
 ///
 /// let mut circuit = Circuit::default();
-/// circuit.add_updater(&sig_a);
-/// circuit.add_updater(&sig_b);
-/// circuit.add_updater(&sig_s);
-/// circuit.add_updater(&mux);
-/// circuit.add_updater(&sig_z);
+/// circuit.add(sig_a);
+/// circuit.add(sig_b);
+/// circuit.add(sig_s);
+/// circuit.add(mux);
+/// circuit.add(sig_z);
 ///
-/// circuit.tick();
+/// circuit.step().unwrap();
 /// assert_eq!(Ieee1164::_X, port_z.value());
 ///
 /// port_s.replace(Ieee1164::_0);
-/// circuit.tick();
+/// circuit.step().unwrap();
 /// assert_eq!(Ieee1164::_H, port_z.value());
 ///
 /// port_s.replace(Ieee1164::_1);
-/// circuit.tick();
+/// circuit.step().unwrap();
 /// assert_eq!(Ieee1164::_L, port_z.value());
 /// ```
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Component)]
 pub struct Mux {
     /// First input `Port`
+    #[port(input)]
     pub a: Port<Ieee1164, Input>,
     /// Second input `Port`
+    #[port(input)]
     pub b: Port<Ieee1164, Input>,
     /// Selector `Port`
+    #[port(input)]
     pub s: Port<Ieee1164, Input>,
     /// Output `Port`
+    #[port(output)]
     pub z: Port<Ieee1164, Output>,
+    driver: Waveform<Ieee1164>,
     _private: (),
 }
 
-impl Updateable for Mux {
-    fn update(&mut self) -> bool {
-        let new_value = if self.s.value().is_1H() {
+impl_gate_delay!(Mux);
+
+impl Mux {
+    fn select(&self) -> Ieee1164 {
+        if self.s.value().is_1H() {
             self.b.value()
         } else if self.s.value().is_0L() {
             self.a.value()
         } else {
             Ieee1164::_X
-        };
+        }
+    }
+}
+
+impl Updateable for Mux {
+    fn update(&mut self) -> bool {
+        let new_value = self.select();
+        self.driver.force(new_value);
 
         let old_value = self.z.replace(new_value);
 
         old_value != new_value
     }
-}
 
-impl IterPorts for Mux {
-    fn iter_ports<F>(&self, mut f: F)
-    where
-        F: FnMut(&str, &Port<Ieee1164, Output>),
-    {
-        f("a", &Port::new_with_arc(self.a.inner.clone()));
-        f("b", &Port::new_with_arc(self.b.inner.clone()));
-        f("s", &Port::new_with_arc(self.s.inner.clone()));
-        f("z", &Port::new_with_arc(self.z.inner.clone()));
+    fn update_at(&mut self, now: Time) -> bool {
+        let new_value = self.select();
+        self.driver.drive(now, new_value, &mut self.z)
+    }
+
+    fn next_event(&self) -> Option<Time> {
+        self.driver.next_event()
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
     }
 }
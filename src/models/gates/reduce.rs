@@ -0,0 +1,158 @@
+use super::{and, buf, inv, or, xor};
+use crate::direction::{Input, Output};
+use crate::time::Waveform;
+use crate::Component;
+use crate::{Delay, Ieee1164, LogicVector, Port, PortId, Time, Updateable};
+
+macro_rules! create_reduce_gate {
+    ($name:ident, $func:ident, $post:ident, $doc:tt) => {
+        #[derive(Debug, Component)]
+        #[doc = $doc]
+        pub struct $name {
+            /// Input `Port`
+            #[port(input)]
+            pub a: Port<LogicVector, Input>,
+            /// Output `Port`
+            #[port(output)]
+            pub z: Port<Ieee1164, Output>,
+            driver: Waveform<Ieee1164>,
+            _private: (),
+        }
+
+        impl $name {
+            /// Creates this gate with an input that is `width` bits wide.
+            pub fn with_width(width: usize) -> Self {
+                Self {
+                    a: Port::new(LogicVector::with_width(width)),
+                    z: Port::default(),
+                    driver: Waveform::new(Delay::default()),
+                    _private: (),
+                }
+            }
+
+            fn calc(&self) -> Ieee1164 {
+                let a = self.a.value();
+                let first = a.get(0).unwrap_or(Ieee1164::_U);
+                $post((1..a.width()).filter_map(|i| a.get(i)).fold(first, $func))
+            }
+        }
+
+        impl_gate_delay!(@accessors $name);
+
+        impl Updateable for $name {
+            fn update(&mut self) -> bool {
+                let new_value = self.calc();
+                self.driver.force(new_value);
+                let old_value = self.z.replace(new_value);
+                old_value != new_value
+            }
+
+            fn update_at(&mut self, now: Time) -> bool {
+                let new_value = self.calc();
+                self.driver.drive(now, new_value, &mut self.z)
+            }
+
+            fn next_event(&self) -> Option<Time> {
+                self.driver.next_event()
+            }
+
+            fn sensitivity(&self) -> Vec<PortId> {
+                self.input_ids()
+            }
+
+            fn drives(&self) -> Vec<PortId> {
+                self.output_ids()
+            }
+        }
+    };
+}
+
+create_reduce_gate!(
+    AndReduce,
+    and,
+    buf,
+    "Reduces a [`LogicVector`] with the logical AND operation, i.e. the output is only high if all \
+     bits of the input are high."
+);
+
+create_reduce_gate!(
+    NandReduce,
+    and,
+    inv,
+    "Reduces a [`LogicVector`] with the logical NAND operation, i.e. the output is only low if all \
+     bits of the input are high."
+);
+
+create_reduce_gate!(
+    OrReduce,
+    or,
+    buf,
+    "Reduces a [`LogicVector`] with the logical OR operation, i.e. the output is high if any bit of \
+     the input is high."
+);
+
+create_reduce_gate!(
+    NorReduce,
+    or,
+    inv,
+    "Reduces a [`LogicVector`] with the logical NOR operation, i.e. the output is low if any bit of \
+     the input is high."
+);
+
+create_reduce_gate!(
+    XorReduce,
+    xor,
+    buf,
+    "Reduces a [`LogicVector`] with the logical XOR operation, i.e. the output is the parity of the \
+     input."
+);
+
+create_reduce_gate!(
+    XnorReduce,
+    xor,
+    inv,
+    "Reduces a [`LogicVector`] with the logical XNOR operation, i.e. the output is the negated \
+     parity of the input."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    #[test]
+    fn reduce() {
+        let and = AndReduce::with_width(4);
+        let nor = NorReduce::with_width(4);
+        let xor = XorReduce::with_width(4);
+        let mut a = Port::<_, Output>::new("1101".parse::<LogicVector>().unwrap());
+        let z_and = Port::<_, Input>::default();
+        let z_nor = Port::<_, Input>::default();
+        let z_xor = Port::<_, Input>::default();
+
+        let mut circuit = circuit!(
+            signal!(a, and.a, nor.a, xor.a),
+            signal!(and.z, z_and),
+            signal!(nor.z, z_nor),
+            signal!(xor.z, z_xor)
+        );
+        circuit.add(and);
+        circuit.add(nor);
+        circuit.add(xor);
+
+        circuit.step().unwrap();
+        assert_eq!((_0, _0, _1), (z_and.value(), z_nor.value(), z_xor.value()));
+
+        a.replace("11H1".parse().unwrap());
+        circuit.step().unwrap();
+        assert_eq!((_1, _0, _0), (z_and.value(), z_nor.value(), z_xor.value()));
+
+        a.replace("0X00".parse().unwrap());
+        circuit.step().unwrap();
+        assert_eq!((_0, _X, _X), (z_and.value(), z_nor.value(), z_xor.value()));
+    }
+}
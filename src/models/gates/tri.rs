@@ -1,45 +1,65 @@
 use crate::direction::{Input, Output};
-use crate::dump::IterPorts;
-use crate::{Ieee1164, Port, Updateable};
+use crate::time::Waveform;
+use crate::Component;
+use crate::{Delay, Ieee1164, Port, PortId, Time, Updateable};
 
 /// A `Tristate-buffer` can be used if you need multiple signals to drive a single [`Signal`](crate::Signal).
 ///
 /// This is often used if multiple signals must drive a single one, e.g. a s.c. bus. If [`TriBuffer::s`] is
 /// driven high, [`TriBuffer::z`] will be the value of [`TriBuffer::a`]. If it's driven low, it will
 /// output [`'Ieee1164::_Z`]. In other cases it will be [`Ieee1164::_X`].
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Component)]
 pub struct TriBuffer {
     /// Input `Port`
+    #[port(input)]
     pub a: Port<Ieee1164, Input>,
     /// Enable `Port`
+    #[port(input)]
     pub s: Port<Ieee1164, Input>,
     /// Output `Port`
+    #[port(output)]
     pub z: Port<Ieee1164, Output>,
+    driver: Waveform<Ieee1164>,
     _private: (),
 }
 
-impl Updateable for TriBuffer {
-    fn update(&mut self) -> bool {
-        let new_value = if self.s.value().is_1H() {
+impl_gate_delay!(TriBuffer);
+
+impl TriBuffer {
+    fn select(&self) -> Ieee1164 {
+        if self.s.value().is_1H() {
             self.a.value()
         } else if self.s.value().is_0L() {
             Ieee1164::_Z
         } else {
             Ieee1164::_X
-        };
-        let old_value =  self.z.replace(new_value);
+        }
+    }
+}
+
+impl Updateable for TriBuffer {
+    fn update(&mut self) -> bool {
+        let new_value = self.select();
+        self.driver.force(new_value);
+        let old_value = self.z.replace(new_value);
 
         old_value != new_value
     }
-}
 
-impl IterPorts for TriBuffer {
-    fn iter_ports<F>(&self, mut f: F)
-    where
-        F: FnMut(&str, &Port<Ieee1164, Output>),
-    {
-        f("a", &Port::new_with_arc(self.a.inner.clone()));
-        f("s", &Port::new_with_arc(self.s.inner.clone()));
-        f("z", &Port::new_with_arc(self.z.inner.clone()));
+    fn update_at(&mut self, now: Time) -> bool {
+        let new_value = self.select();
+        self.driver.drive(now, new_value, &mut self.z)
+    }
+
+    fn next_event(&self) -> Option<Time> {
+        self.driver.next_event()
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
     }
 }
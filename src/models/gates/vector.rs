@@ -0,0 +1,294 @@
+use crate::direction::{Input, Output};
+use crate::time::Waveform;
+use crate::Component;
+use crate::{Delay, Ieee1164, LogicVector, Port, PortId, Time, Updateable};
+
+macro_rules! create_vector_2i1o_gate {
+    ($name:ident, $func:ident, $doc:tt) => {
+        #[derive(Debug, Component)]
+        #[doc = $doc]
+        ///
+        /// If the width of an input differs from the width of the gate, every bit of the output is
+        /// [`Ieee1164::_X`].
+        pub struct $name {
+            /// First input port
+            #[port(input)]
+            pub a: Port<LogicVector, Input>,
+            /// Second input port
+            #[port(input)]
+            pub b: Port<LogicVector, Input>,
+            /// Output port
+            #[port(output)]
+            pub z: Port<LogicVector, Output>,
+            width: usize,
+            driver: Waveform<LogicVector>,
+            _private: (),
+        }
+
+        impl $name {
+            /// Creates this gate with inputs and output that are `width` bits wide.
+            pub fn with_width(width: usize) -> Self {
+                Self {
+                    a: Port::new(LogicVector::with_width(width)),
+                    b: Port::new(LogicVector::with_width(width)),
+                    z: Port::new(LogicVector::with_width(width)),
+                    width,
+                    driver: Waveform::new(Delay::default()),
+                    _private: (),
+                }
+            }
+
+            fn calc(&self) -> LogicVector {
+                let (a, b) = (self.a.value(), self.b.value());
+                if a.width() == self.width && b.width() == self.width {
+                    $func(&a, &b)
+                } else {
+                    LogicVector::from_ieee(Ieee1164::_X, self.width)
+                }
+            }
+        }
+
+        impl_gate_delay!(@accessors $name);
+
+        impl Updateable for $name {
+            fn update(&mut self) -> bool {
+                let new_value = self.calc();
+                self.driver.force(new_value.clone());
+                let old_value = self.z.replace(new_value.clone());
+                old_value != new_value
+            }
+
+            fn update_at(&mut self, now: Time) -> bool {
+                let new_value = self.calc();
+                self.driver.drive(now, new_value, &mut self.z)
+            }
+
+            fn next_event(&self) -> Option<Time> {
+                self.driver.next_event()
+            }
+
+            fn sensitivity(&self) -> Vec<PortId> {
+                self.input_ids()
+            }
+
+            fn drives(&self) -> Vec<PortId> {
+                self.output_ids()
+            }
+        }
+    };
+}
+
+fn and(a: &LogicVector, b: &LogicVector) -> LogicVector {
+    a & b
+}
+create_vector_2i1o_gate!(
+    VectorAndGate,
+    and,
+    "A bitwise AND Gate for two [`LogicVector`]s. It performs the logical AND operation on every \
+     pair of bits of both inputs."
+);
+
+fn nand(a: &LogicVector, b: &LogicVector) -> LogicVector {
+    !(a & b)
+}
+create_vector_2i1o_gate!(
+    VectorNandGate,
+    nand,
+    "A bitwise NAND Gate for two [`LogicVector`]s. It performs the logical NAND operation on every \
+     pair of bits of both inputs."
+);
+
+fn or(a: &LogicVector, b: &LogicVector) -> LogicVector {
+    a | b
+}
+create_vector_2i1o_gate!(
+    VectorOrGate,
+    or,
+    "A bitwise OR Gate for two [`LogicVector`]s. It performs the logical OR operation on every \
+     pair of bits of both inputs."
+);
+
+fn nor(a: &LogicVector, b: &LogicVector) -> LogicVector {
+    !(a | b)
+}
+create_vector_2i1o_gate!(
+    VectorNorGate,
+    nor,
+    "A bitwise NOR Gate for two [`LogicVector`]s. It performs the logical NOR operation on every \
+     pair of bits of both inputs."
+);
+
+fn xor(a: &LogicVector, b: &LogicVector) -> LogicVector {
+    a ^ b
+}
+create_vector_2i1o_gate!(
+    VectorXorGate,
+    xor,
+    "A bitwise XOR Gate for two [`LogicVector`]s. It performs the logical XOR operation on every \
+     pair of bits of both inputs."
+);
+
+fn xnor(a: &LogicVector, b: &LogicVector) -> LogicVector {
+    !(a ^ b)
+}
+create_vector_2i1o_gate!(
+    VectorXnorGate,
+    xnor,
+    "A bitwise XNOR Gate for two [`LogicVector`]s. It performs the logical XNOR operation on every \
+     pair of bits of both inputs."
+);
+
+/// A bitwise Not Gate for a [`LogicVector`]. It outputs the negation of every bit of its input.
+///
+/// If the width of the input differs from the width of the gate, every bit of the output is
+/// [`Ieee1164::_X`].
+#[derive(Debug, Component)]
+pub struct VectorInverter {
+    /// Input `Port`
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Output `Port`
+    #[port(output)]
+    pub z: Port<LogicVector, Output>,
+    width: usize,
+    driver: Waveform<LogicVector>,
+    _private: (),
+}
+
+impl VectorInverter {
+    /// Creates an inverter whose input and output are `width` bits wide.
+    pub fn with_width(width: usize) -> Self {
+        Self {
+            a: Port::new(LogicVector::with_width(width)),
+            z: Port::new(LogicVector::with_width(width)),
+            width,
+            driver: Waveform::new(Delay::default()),
+            _private: (),
+        }
+    }
+
+    fn calc(&self) -> LogicVector {
+        let a = self.a.value();
+        if a.width() == self.width {
+            !a
+        } else {
+            LogicVector::from_ieee(Ieee1164::_X, self.width)
+        }
+    }
+}
+
+impl_gate_delay!(@accessors VectorInverter);
+
+impl Updateable for VectorInverter {
+    fn update(&mut self) -> bool {
+        let new_value = self.calc();
+        self.driver.force(new_value.clone());
+        let old_value = self.z.replace(new_value.clone());
+        old_value != new_value
+    }
+
+    fn update_at(&mut self, now: Time) -> bool {
+        let new_value = self.calc();
+        self.driver.drive(now, new_value, &mut self.z)
+    }
+
+    fn next_event(&self) -> Option<Time> {
+        self.driver.next_event()
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    fn lv(s: &str) -> LogicVector {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn bitwise() {
+        let and = VectorAndGate::with_width(4);
+        let xnor = VectorXnorGate::with_width(4);
+        let inv = VectorInverter::with_width(4);
+        let mut a = Port::<_, Output>::new(lv("01XZ"));
+        let b = Port::<_, Output>::new(lv("0110"));
+        let z_and = Port::<_, Input>::new(LogicVector::with_width(4));
+        let z_xnor = Port::<_, Input>::new(LogicVector::with_width(4));
+        let z_inv = Port::<_, Input>::new(LogicVector::with_width(4));
+
+        let mut circuit = circuit!(
+            signal!(a, and.a, xnor.a, inv.a),
+            signal!(b, and.b, xnor.b),
+            signal!(and.z, z_and),
+            signal!(xnor.z, z_xnor),
+            signal!(inv.z, z_inv)
+        );
+        circuit.add(and);
+        circuit.add(xnor);
+        circuit.add(inv);
+
+        circuit.step().unwrap();
+        assert_eq!(lv("01X0"), z_and.value());
+        assert_eq!(lv("11XX"), z_xnor.value());
+        assert_eq!(lv("10XX"), z_inv.value());
+
+        a.replace(lv("1111"));
+        circuit.step().unwrap();
+        assert_eq!(lv("0110"), z_and.value());
+        assert_eq!(lv("0110"), z_xnor.value());
+        assert_eq!(lv("0000"), z_inv.value());
+    }
+
+    #[test]
+    fn width_mismatch() {
+        let or = VectorOrGate::with_width(4);
+        let a = Port::<_, Output>::new(lv("0000"));
+        let mut b = Port::<_, Output>::new(lv("0001"));
+        let z = Port::<_, Input>::new(LogicVector::with_width(4));
+
+        let mut circuit = circuit!(signal!(a, or.a), signal!(b, or.b), signal!(or.z, z));
+        circuit.add(or);
+
+        circuit.step().unwrap();
+        assert_eq!(lv("0001"), z.value());
+
+        b.replace(lv("01"));
+        circuit.step().unwrap();
+        assert_eq!(lv("XXXX"), z.value());
+    }
+
+    #[test]
+    fn delay_follows_the_changing_bits() {
+        let mut and = VectorAndGate::with_width(4);
+        and.set_delay(Delay::transport(3, 5));
+        let mut a = Port::<_, Output>::new(lv("0011"));
+        let b = Port::<_, Output>::new(lv("0101"));
+        let z = Port::<_, Input>::new(LogicVector::with_width(4));
+        let mut circuit = circuit!(signal!(a, and.a), signal!(b, and.b), signal!(and.z, z));
+        circuit.add(and);
+
+        circuit.run_until(10).unwrap();
+        assert_eq!(lv("0001"), z.value());
+
+        a.replace(lv("0111"));
+        circuit.run_until(12).unwrap();
+        assert_eq!(lv("0001"), z.value());
+        circuit.run_until(13).unwrap();
+        assert_eq!(lv("0101"), z.value());
+
+        a.replace(lv("0011"));
+        circuit.run_until(17).unwrap();
+        assert_eq!(lv("0101"), z.value());
+        circuit.run_until(18).unwrap();
+        assert_eq!(lv("0001"), z.value());
+    }
+}
@@ -0,0 +1,206 @@
+use super::{and, buf, inv, or, xor};
+use crate::component::PortInfo;
+use crate::direction::{Input, Output};
+use crate::time::Waveform;
+use crate::Component;
+use crate::{Delay, Ieee1164, Port, PortId, Time, Updateable};
+
+macro_rules! create_wide_gate {
+    ($name:ident, $func:ident, $post:ident, $doc:tt) => {
+        #[derive(Debug)]
+        #[doc = $doc]
+        ///
+        /// The number of inputs is chosen on creation. The input `Port`s are listed as `a0`, `a1`,
+        /// ... by [`Component::ports`], followed by the output `z`.
+        pub struct $name {
+            /// Input `Port`s
+            pub a: Vec<Port<Ieee1164, Input>>,
+            /// Output `Port`
+            pub z: Port<Ieee1164, Output>,
+            driver: Waveform<Ieee1164>,
+            _private: (),
+        }
+
+        impl $name {
+            /// Creates this gate with `inputs` input `Port`s.
+            ///
+            /// Returns `None` if `inputs` is `0`.
+            pub fn new(inputs: usize) -> Option<Self> {
+                if inputs == 0 {
+                    return None;
+                }
+                Some(Self {
+                    a: (0..inputs).map(|_| Port::default()).collect(),
+                    z: Port::default(),
+                    driver: Waveform::default(),
+                    _private: (),
+                })
+            }
+
+            fn calc(&self) -> Ieee1164 {
+                let mut values = self.a.iter().map(Port::value);
+                let first = values.next().unwrap_or(Ieee1164::_U);
+                $post(values.fold(first, $func))
+            }
+        }
+
+        impl_gate_delay!(@accessors $name);
+
+        impl Component for $name {
+            fn ports(&self) -> Vec<PortInfo> {
+                let mut ports: Vec<_> = self
+                    .a
+                    .iter()
+                    .enumerate()
+                    .map(|(i, a)| PortInfo::new(&format!("a{}", i), a))
+                    .collect();
+                ports.push(PortInfo::new("z", &self.z));
+                ports
+            }
+        }
+
+        impl Updateable for $name {
+            fn update(&mut self) -> bool {
+                let new_value = self.calc();
+                self.driver.force(new_value);
+                let old_value = self.z.replace(new_value);
+                old_value != new_value
+            }
+
+            fn update_at(&mut self, now: Time) -> bool {
+                let new_value = self.calc();
+                self.driver.drive(now, new_value, &mut self.z)
+            }
+
+            fn next_event(&self) -> Option<Time> {
+                self.driver.next_event()
+            }
+
+            fn sensitivity(&self) -> Vec<PortId> {
+                self.a.iter().map(Port::id).collect()
+            }
+
+            fn drives(&self) -> Vec<PortId> {
+                vec![self.z.id()]
+            }
+        }
+    };
+}
+
+create_wide_gate!(
+    WideAndGate,
+    and,
+    buf,
+    "An AND Gate with any number of inputs. It outputs the logical AND of all inputs."
+);
+
+create_wide_gate!(
+    WideNandGate,
+    and,
+    inv,
+    "A NAND Gate with any number of inputs. It outputs the negated logical AND of all inputs."
+);
+
+create_wide_gate!(
+    WideOrGate,
+    or,
+    buf,
+    "An OR Gate with any number of inputs. It outputs the logical OR of all inputs."
+);
+
+create_wide_gate!(
+    WideNorGate,
+    or,
+    inv,
+    "A NOR Gate with any number of inputs. It outputs the negated logical OR of all inputs."
+);
+
+create_wide_gate!(
+    WideXorGate,
+    xor,
+    buf,
+    "An XOR Gate with any number of inputs. It outputs the parity of all inputs, i.e. `1` if an \
+     odd number of inputs is high."
+);
+
+create_wide_gate!(
+    WideXnorGate,
+    xor,
+    inv,
+    "An XNOR Gate with any number of inputs. It outputs the negated parity of all inputs, i.e. `1` \
+     if an even number of inputs is high."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    #[test]
+    fn no_inputs() {
+        assert!(WideAndGate::new(0).is_none());
+        assert_eq!(1, WideOrGate::new(1).unwrap().sensitivity().len());
+    }
+
+    #[test]
+    fn ports_are_numbered() {
+        let gate = WideXorGate::new(3).unwrap();
+        let names: Vec<_> = gate.ports().iter().map(|p| p.name().to_string()).collect();
+        assert_eq!(vec!["a0", "a1", "a2", "z"], names);
+    }
+
+    #[test]
+    fn eight_input_and() {
+        let gate = WideAndGate::new(8).unwrap();
+        let mut inputs: Vec<_> = (0..8).map(|_| Port::<_, Output>::new(_1)).collect();
+        let z = Port::<_, Input>::default();
+
+        let mut circuit = Circuit::default();
+        for (input, a) in inputs.iter().zip(&gate.a) {
+            circuit.add(signal!(*input, *a));
+        }
+        circuit.add(signal!(gate.z, z));
+        circuit.add(gate);
+
+        circuit.step().unwrap();
+        assert_eq!(_1, z.value());
+
+        inputs[5].replace(_X);
+        circuit.step().unwrap();
+        assert_eq!(_X, z.value());
+
+        inputs[2].replace(_0);
+        circuit.step().unwrap();
+        assert_eq!(_0, z.value());
+    }
+
+    #[test]
+    fn parity() {
+        let xor = WideXorGate::new(3).unwrap();
+        let xnor = WideXnorGate::new(3).unwrap();
+        let mut inputs: Vec<_> = [_1, _1, _0]
+            .iter()
+            .map(|&v| Port::<_, Output>::new(v))
+            .collect();
+        let odd = Port::<_, Input>::default();
+        let even = Port::<_, Input>::default();
+
+        let mut circuit = circuit!(signal!(xor.z, odd), signal!(xnor.z, even));
+        for ((input, a), b) in inputs.iter().zip(&xor.a).zip(&xnor.a) {
+            circuit.add(signal!(*input, *a, *b));
+        }
+        circuit.add(xor);
+        circuit.add(xnor);
+
+        circuit.step().unwrap();
+        assert_eq!((_0, _1), (odd.value(), even.value()));
+
+        inputs[2].replace(_1);
+        circuit.step().unwrap();
+        assert_eq!((_1, _0), (odd.value(), even.value()));
+    }
+}
@@ -0,0 +1,220 @@
+use super::next_tick;
+use crate::direction::Output;
+use crate::models::drive;
+use crate::time::Time;
+use crate::Component;
+use crate::{Ieee1164, Port, PortId, Updateable};
+
+/// A periodic clock generator.
+///
+/// The output [`Clock::clk`] is [`Ieee1164::_0`] until `phase`, rises to [`Ieee1164::_1`] at
+/// `phase` and every `period` afterwards and stays high for `high` of each period.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{Circuit, Ieee1164, Port, Signal};
+/// use logical::direction::Input;
+/// use logical::models::inputs::Clock;
+///
+/// let clock = Clock::new(10, 3, 5).unwrap();
+/// let clk = Port::<_, Input>::default();
+/// let mut sig = Signal::default();
+/// sig.connect(&clock.clk).unwrap();
+/// sig.connect(&clk).unwrap();
+///
+/// let mut circuit = Circuit::default();
+/// circuit.add(clock);
+/// circuit.add(sig);
+///
+/// circuit.run_until(4).unwrap();
+/// assert_eq!(Ieee1164::_0, clk.value());
+/// circuit.run_until(7).unwrap();
+/// assert_eq!(Ieee1164::_1, clk.value());
+/// circuit.run_until(8).unwrap();
+/// assert_eq!(Ieee1164::_0, clk.value());
+/// circuit.run_until(15).unwrap();
+/// assert_eq!(Ieee1164::_1, clk.value());
+/// ```
+#[derive(Debug, Component)]
+pub struct Clock {
+    /// Output `Port`
+    #[port(output)]
+    pub clk: Port<Ieee1164, Output>,
+    period: Time,
+    high: Time,
+    phase: Time,
+    now: Option<Time>,
+    _private: (),
+}
+
+impl Clock {
+    /// Creates a clock with the given `period`, which is high for `high` of every period and has
+    /// its first rising edge at `phase`.
+    ///
+    /// Returns `None` if `high` is `0` or not smaller than `period`, because the clock would never
+    /// toggle.
+    pub fn new(period: Time, high: Time, phase: Time) -> Option<Self> {
+        if high == 0 || high >= period {
+            return None;
+        }
+        Some(Self {
+            clk: Port::default(),
+            period,
+            high,
+            phase,
+            now: None,
+            _private: (),
+        })
+    }
+
+    /// Creates a clock with a duty cycle of 50% and the first rising edge at `0`.
+    ///
+    /// Returns `None` if `period` is smaller than `2`.
+    pub fn with_period(period: Time) -> Option<Self> {
+        Self::new(period, period / 2, 0)
+    }
+
+    /// Returns the period of this clock.
+    pub fn period(&self) -> Time {
+        self.period
+    }
+
+    /// Returns the time this clock is high in every period.
+    pub fn high(&self) -> Time {
+        self.high
+    }
+
+    /// Returns the time of the first rising edge of this clock.
+    pub fn phase(&self) -> Time {
+        self.phase
+    }
+
+    fn value_at(&self, time: Time) -> Ieee1164 {
+        if time >= self.phase && (time - self.phase) % self.period < self.high {
+            Ieee1164::_1
+        } else {
+            Ieee1164::_0
+        }
+    }
+}
+
+impl Updateable for Clock {
+    fn update(&mut self) -> bool {
+        match next_tick(self.now, self.next_event()) {
+            Some(time) => self.update_at(time),
+            None => false,
+        }
+    }
+
+    fn update_at(&mut self, now: Time) -> bool {
+        self.now = Some(now);
+        let value = self.value_at(now);
+        drive(&mut self.clk, value)
+    }
+
+    fn next_event(&self) -> Option<Time> {
+        let now = self.now?;
+        if now < self.phase {
+            return Some(self.phase);
+        }
+        let offset = (now - self.phase) % self.period;
+        if offset < self.high {
+            Some(now - offset + self.high)
+        } else {
+            Some(now - offset + self.period)
+        }
+    }
+
+    fn time_driven(&self) -> bool {
+        true
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_duty_cycle() {
+        assert!(Clock::new(10, 0, 0).is_none());
+        assert!(Clock::new(10, 10, 0).is_none());
+        assert!(Clock::with_period(1).is_none());
+        assert!(Clock::with_period(2).is_some());
+    }
+
+    #[test]
+    fn ticks_toggle() {
+        let mut clock = Clock::new(10, 3, 4).unwrap();
+        let mut times = vec![];
+        let mut values = vec![];
+        for _ in 0..6 {
+            clock.update();
+            times.push(clock.now.unwrap());
+            values.push(clock.value_at(clock.now.unwrap()));
+        }
+        assert_eq!(vec![0, 4, 7, 14, 17, 24], times);
+        assert_eq!(
+            vec![
+                Ieee1164::_0,
+                Ieee1164::_1,
+                Ieee1164::_0,
+                Ieee1164::_1,
+                Ieee1164::_0,
+                Ieee1164::_1
+            ],
+            values
+        );
+    }
+
+    #[test]
+    fn clocks_a_flip_flop() {
+        use crate::direction::Input;
+        use crate::models::gates::Inverter;
+        use crate::models::rtlib::sequential::DFlipFlop;
+        use crate::{Circuit, Signal};
+
+        // a toggle flip-flop divides the clock by two
+        let clock = Clock::with_period(10).unwrap();
+        let dff = DFlipFlop::default();
+        let inv = Inverter::default();
+        let mut reset = Port::<_, Output>::new(Ieee1164::_1);
+        let q = Port::<_, Input>::default();
+        let mut circuit = circuit!(
+            signal!(clock.clk, dff.clk),
+            signal!(reset, dff.reset),
+            signal!(dff.q, inv.a, q),
+            signal!(inv.z, dff.d)
+        );
+        circuit.add(clock);
+        circuit.add(dff);
+        circuit.add(inv);
+        assert_eq!(Ok(()), circuit.check_loops());
+
+        circuit.run_until(1).unwrap();
+        reset.replace(Ieee1164::_0);
+
+        let mut values = vec![];
+        for t in (2..80).step_by(10) {
+            circuit.run_until(t).unwrap();
+            values.push(q.value());
+        }
+        assert_eq!(
+            vec![
+                Ieee1164::_0,
+                Ieee1164::_1,
+                Ieee1164::_0,
+                Ieee1164::_1,
+                Ieee1164::_0,
+                Ieee1164::_1,
+                Ieee1164::_0,
+                Ieee1164::_1
+            ],
+            values
+        );
+    }
+}
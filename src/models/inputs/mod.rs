@@ -1,9 +1,35 @@
 //! This module provides generic inputs.
+//!
+//! Besides the [`Switch`], which has to be flipped by hand, there are generators that change their
+//! output on their own, e.g. a [`Clock`]. They can be used in two ways:
+//!
+//! * Inside of a [`Circuit`](crate::Circuit) that advances the simulated time, e.g. with
+//!   [`Circuit::run_until`](crate::Circuit::run_until), the output follows the simulated time.
+//! * Every call to [`Updateable::update`](crate::Updateable::update), e.g. by
+//!   [`Circuit::tick`](crate::Circuit::tick), advances the generator to its next change.
+
+mod clock;
+mod pattern;
+mod reset;
+
+pub use self::clock::Clock;
+pub use self::pattern::Pattern;
+pub use self::reset::ResetPulse;
 
 use crate::direction::Output;
+use crate::time::Time;
 use crate::{Ieee1164, Port};
 
 /// This struct can be used as an user-output.
 ///
 /// This can be used in an graphical environment for example to display the state of a signal.
 pub type Switch = Port<Ieee1164, Output>;
+
+/// Returns the time a generator has to be evaluated at, if it is advanced by a call to
+/// [`Updateable::update`](crate::Updateable::update). The very first call evaluates time `0`.
+fn next_tick(now: Option<Time>, next_event: Option<Time>) -> Option<Time> {
+    match now {
+        None => Some(0),
+        Some(_) => next_event,
+    }
+}
@@ -0,0 +1,196 @@
+use super::next_tick;
+use crate::component::PortValue;
+use crate::direction::Output;
+use crate::models::drive;
+use crate::time::Time;
+use crate::Component;
+use crate::{Port, PortId, Updateable};
+
+/// A generator that replays a sequence of values, e.g. [`Ieee1164`](crate::Ieee1164)s or
+/// [`LogicVector`](crate::LogicVector)s.
+///
+/// Every value is outputted on [`Pattern::data`] for `interval`, starting with the first one at
+/// time `0`. After the last value the sequence either starts again or the last value is held.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{Circuit, LogicVector, Port, Signal};
+/// use logical::direction::Input;
+/// use logical::models::inputs::Pattern;
+///
+/// let values = (1..=3).map(|i| LogicVector::from_int(i, 4).unwrap()).collect();
+/// let pattern = Pattern::once(values, 10).unwrap();
+/// let data = Port::<_, Input>::new(LogicVector::with_width(4));
+/// let mut sig = Signal::default();
+/// sig.connect(&pattern.data).unwrap();
+/// sig.connect(&data).unwrap();
+///
+/// let mut circuit = Circuit::default();
+/// circuit.add(pattern);
+/// circuit.add(sig);
+///
+/// circuit.run_until(9).unwrap();
+/// assert_eq!(Some(1), data.value().as_u128());
+/// circuit.run_until(20).unwrap();
+/// assert_eq!(Some(3), data.value().as_u128());
+/// circuit.run_until(1000).unwrap();
+/// assert_eq!(Some(3), data.value().as_u128());
+/// ```
+#[derive(Debug, Component)]
+pub struct Pattern<T: PortValue + PartialEq> {
+    /// Output `Port`
+    #[port(output)]
+    pub data: Port<T, Output>,
+    values: Vec<T>,
+    interval: Time,
+    repeat: bool,
+    now: Option<Time>,
+    _private: (),
+}
+
+impl<T: PortValue + PartialEq> Pattern<T> {
+    /// Creates a pattern that outputs each of `values` for `interval` and holds the last value
+    /// afterwards.
+    ///
+    /// Returns `None` if `values` is empty or `interval` is `0`.
+    pub fn once(values: Vec<T>, interval: Time) -> Option<Self> {
+        Self::new(values, interval, false)
+    }
+
+    /// Creates a pattern that outputs each of `values` for `interval` and starts over after the
+    /// last value.
+    ///
+    /// Returns `None` if `values` is empty or `interval` is `0`.
+    pub fn repeat(values: Vec<T>, interval: Time) -> Option<Self> {
+        Self::new(values, interval, true)
+    }
+
+    fn new(values: Vec<T>, interval: Time, repeat: bool) -> Option<Self> {
+        if values.is_empty() || interval == 0 {
+            return None;
+        }
+        Some(Self {
+            data: Port::new(values[0].clone()),
+            values,
+            interval,
+            repeat,
+            now: None,
+            _private: (),
+        })
+    }
+
+    /// Returns the values of this pattern.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns how long each value is outputted.
+    pub fn interval(&self) -> Time {
+        self.interval
+    }
+
+    fn index_at(&self, time: Time) -> usize {
+        let index = time / self.interval;
+        let len = self.values.len() as Time;
+        if self.repeat {
+            (index % len) as usize
+        } else {
+            index.min(len - 1) as usize
+        }
+    }
+}
+
+impl<T: PortValue + PartialEq> Updateable for Pattern<T> {
+    fn update(&mut self) -> bool {
+        match next_tick(self.now, self.next_event()) {
+            Some(time) => self.update_at(time),
+            None => false,
+        }
+    }
+
+    fn update_at(&mut self, now: Time) -> bool {
+        self.now = Some(now);
+        let value = self.values[self.index_at(now)].clone();
+        drive(&mut self.data, value)
+    }
+
+    fn next_event(&self) -> Option<Time> {
+        let next = (self.now? / self.interval + 1) * self.interval;
+        if self.repeat || next < self.values.len() as Time * self.interval {
+            Some(next)
+        } else {
+            None
+        }
+    }
+
+    fn time_driven(&self) -> bool {
+        true
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Input;
+    use crate::{Ieee1164, Signal};
+
+    #[test]
+    fn invalid_pattern() {
+        assert!(Pattern::<Ieee1164>::once(vec![], 10).is_none());
+        assert!(Pattern::once(vec![Ieee1164::_1], 0).is_none());
+    }
+
+    #[test]
+    fn ticks_replay_values() {
+        let values = vec![Ieee1164::_0, Ieee1164::_1, Ieee1164::_X];
+        let mut pattern = Pattern::repeat(values.clone(), 5).unwrap();
+        let data = Port::<_, Input>::default();
+        let mut sig = Signal::default();
+        sig.connect(&pattern.data).unwrap();
+        sig.connect(&data).unwrap();
+
+        let mut replayed = vec![];
+        for _ in 0..7 {
+            pattern.update();
+            sig.update();
+            replayed.push(data.value());
+        }
+        assert_eq!(
+            vec![
+                Ieee1164::_0,
+                Ieee1164::_1,
+                Ieee1164::_X,
+                Ieee1164::_0,
+                Ieee1164::_1,
+                Ieee1164::_X,
+                Ieee1164::_0
+            ],
+            replayed
+        );
+        assert_eq!(Some(35), pattern.next_event());
+
+        let mut pattern = Pattern::once(values, 5).unwrap();
+        for _ in 0..3 {
+            pattern.update();
+        }
+        assert_eq!(None, pattern.next_event());
+        assert!(!pattern.update());
+    }
+
+    #[test]
+    fn follows_simulated_time() {
+        let mut pattern = Pattern::repeat(vec![Ieee1164::_0, Ieee1164::_1], 10).unwrap();
+        assert!(!pattern.update_at(3));
+        assert_eq!(Some(10), pattern.next_event());
+        assert!(pattern.update_at(15));
+        assert_eq!(Some(20), pattern.next_event());
+        assert!(!pattern.update_at(35));
+        assert!(pattern.update_at(41));
+        assert_eq!(Some(50), pattern.next_event());
+    }
+}
@@ -0,0 +1,143 @@
+use super::next_tick;
+use crate::direction::Output;
+use crate::models::drive;
+use crate::time::Time;
+use crate::Component;
+use crate::{Ieee1164, Port, PortId, Updateable};
+
+/// A generator for a single reset pulse at the start of the simulation.
+///
+/// The output [`ResetPulse::reset`] is active from time `0` until `duration` and inactive
+/// afterwards.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{Circuit, Ieee1164, Port, Signal};
+/// use logical::direction::Input;
+/// use logical::models::inputs::ResetPulse;
+///
+/// let pulse = ResetPulse::active_low(20);
+/// let n_reset = Port::<_, Input>::default();
+/// let mut sig = Signal::default();
+/// sig.connect(&pulse.reset).unwrap();
+/// sig.connect(&n_reset).unwrap();
+///
+/// let mut circuit = Circuit::default();
+/// circuit.add(pulse);
+/// circuit.add(sig);
+///
+/// circuit.run_until(19).unwrap();
+/// assert_eq!(Ieee1164::_0, n_reset.value());
+/// circuit.run_until(20).unwrap();
+/// assert_eq!(Ieee1164::_1, n_reset.value());
+/// ```
+#[derive(Debug, Component)]
+pub struct ResetPulse {
+    /// Output `Port`
+    #[port(output)]
+    pub reset: Port<Ieee1164, Output>,
+    active: Ieee1164,
+    duration: Time,
+    now: Option<Time>,
+    _private: (),
+}
+
+impl ResetPulse {
+    /// Creates an active-high reset pulse, which is [`Ieee1164::_1`] for `duration`.
+    pub fn active_high(duration: Time) -> Self {
+        Self::with_level(Ieee1164::_1, duration)
+    }
+
+    /// Creates an active-low reset pulse, which is [`Ieee1164::_0`] for `duration`.
+    pub fn active_low(duration: Time) -> Self {
+        Self::with_level(Ieee1164::_0, duration)
+    }
+
+    fn with_level(active: Ieee1164, duration: Time) -> Self {
+        Self {
+            reset: Port::default(),
+            active,
+            duration,
+            now: None,
+            _private: (),
+        }
+    }
+
+    /// Returns how long the reset is active.
+    pub fn duration(&self) -> Time {
+        self.duration
+    }
+
+    fn value_at(&self, time: Time) -> Ieee1164 {
+        if time < self.duration {
+            self.active
+        } else {
+            !self.active
+        }
+    }
+}
+
+impl Updateable for ResetPulse {
+    fn update(&mut self) -> bool {
+        match next_tick(self.now, self.next_event()) {
+            Some(time) => self.update_at(time),
+            None => false,
+        }
+    }
+
+    fn update_at(&mut self, now: Time) -> bool {
+        self.now = Some(now);
+        let value = self.value_at(now);
+        drive(&mut self.reset, value)
+    }
+
+    fn next_event(&self) -> Option<Time> {
+        if self.now? < self.duration {
+            Some(self.duration)
+        } else {
+            None
+        }
+    }
+
+    fn time_driven(&self) -> bool {
+        true
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Input;
+    use crate::Signal;
+
+    #[test]
+    fn ticks_release_reset() {
+        let mut pulse = ResetPulse::active_high(100);
+        let reset = Port::<_, Input>::default();
+        let mut sig = Signal::default();
+        sig.connect(&pulse.reset).unwrap();
+        sig.connect(&reset).unwrap();
+
+        assert!(pulse.update());
+        sig.update();
+        assert_eq!(Ieee1164::_1, reset.value());
+
+        assert!(pulse.update());
+        sig.update();
+        assert_eq!(Ieee1164::_0, reset.value());
+        assert_eq!(None, pulse.next_event());
+        assert!(!pulse.update());
+    }
+
+    #[test]
+    fn zero_duration() {
+        let mut pulse = ResetPulse::active_high(0);
+        assert!(pulse.update_at(0));
+        assert_eq!(None, pulse.next_event());
+    }
+}
@@ -1,7 +1,7 @@
 //! This module will contain different models that can be used to perform certain calculations. E.g.
 //! Gates, like [`AndGate`](crate::models::gates::AndGate), [`Mux`](crate::models::gates::Mux),
 //! [`Switch`](crate::models::inputs::Switch), but also complex gates, like
-//! [`Rom1kx8`](crate::models::rtlib::memory::rom::Rom1kx8).
+//! [`Rom1kx8`](crate::models::rtlib::memory::Rom1kx8).
 
 pub mod gates;
 pub mod inputs;
@@ -14,3 +14,44 @@ use self::{
     inputs::Switch,
     rtlib::memory::Rom1kx8,
 };
+
+use crate::direction::Output;
+use crate::{Ieee1164, LogicVector, Port};
+
+/// Returns `value`, or a vector of [`Ieee1164::_X`] if it is not `width` bits wide.
+pub(crate) fn checked(value: LogicVector, width: usize) -> LogicVector {
+    if value.width() == width {
+        value
+    } else {
+        LogicVector::from_ieee(Ieee1164::_X, width)
+    }
+}
+
+/// Replaces the value of `port` with `value` and returns `true` if it has changed.
+pub(crate) fn drive<T: PartialEq>(port: &mut Port<T, Output>, value: T) -> bool {
+    port.with_value_mut(|v| {
+        let changed = *v != value;
+        *v = value;
+        changed
+    })
+}
+
+/// Returns the number of bits that are needed to select one of `count` items, which is at least
+/// `1`.
+pub(crate) fn select_width(count: usize) -> usize {
+    (usize::BITS - count.saturating_sub(1).leading_zeros()).max(1) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_widths() {
+        assert_eq!(1, select_width(1));
+        assert_eq!(1, select_width(2));
+        assert_eq!(2, select_width(3));
+        assert_eq!(3, select_width(8));
+        assert_eq!(4, select_width(9));
+    }
+}
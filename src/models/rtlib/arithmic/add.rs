@@ -1,34 +1,55 @@
 use crate::direction::{Input, Output};
-use crate::logicbit::mask_from_width;
-use crate::{Ieee1164, LogicVector, Port, Updateable};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
 
 /// This models an actual adder that will add up both inputs.
 ///
 /// This struct ensures that all inputs will always have the same length.
-#[derive(Debug)]
+#[derive(Debug, Component)]
 pub struct Add {
     /// First input `Port`
+    #[port(input)]
     pub a: Port<LogicVector, Input>,
     /// Second input `Port`
+    #[port(input)]
     pub b: Port<LogicVector, Input>,
     /// Output `Port`, sum of [`Add::a`] and [`Add::b`]
+    #[port(output)]
     pub s: Port<LogicVector, Output>,
     _private: (),
 }
 
+impl Add {
+    /// Creates an adder whose inputs and output are `width` bits wide.
+    pub fn with_width(width: usize) -> Self {
+        Self {
+            a: Port::new(LogicVector::with_width(width)),
+            b: Port::new(LogicVector::with_width(width)),
+            s: Port::new(LogicVector::with_width(width)),
+            _private: (),
+        }
+    }
+}
+
 impl Updateable for Add {
     fn update(&mut self) -> bool {
         let a = self.a.value();
         let b = self.b.value();
         self.s.with_value_mut(|v| {
             let old_value = v.clone();
-            match (a.as_u128(), b.as_u128()) {
-                (Some(a), Some(b)) => v
-                    .replace_with_int(a.wrapping_add(b) & mask_from_width(v.width()))
-                    .unwrap(),
+            match a.safe_add(&b) {
+                Some(sum) if sum.width() == v.width() => *v = sum,
                 _ => v.set_all_to(Ieee1164::_U),
             };
-            old_value == *v
+            old_value != *v
         })
     }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
 }
@@ -0,0 +1,508 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+use crate::direction::{Input, Output};
+use crate::models::{drive, select_width};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// An operation of an [`Alu`].
+///
+/// `a` and `b` are the operands of the [`Alu`], `c` is its carry input. All operations that
+/// subtract use the carry flag as a borrow, i.e. it is `1` if `b` is greater than `a`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AluOp {
+    /// Outputs `a`
+    PassA,
+    /// Outputs `b`
+    PassB,
+    /// Outputs `a + b`
+    Add,
+    /// Outputs `a + b + c`
+    AddCarry,
+    /// Outputs `a - b`
+    Sub,
+    /// Outputs `a - b - c`
+    SubBorrow,
+    /// Outputs `a & b`
+    And,
+    /// Outputs `a | b`
+    Or,
+    /// Outputs `a ^ b`
+    Xor,
+    /// Outputs `!a`
+    Not,
+    /// Shifts `a` by `b` bits towards the most significant bit, the carry flag is the last bit
+    /// shifted out
+    ShiftLeft,
+    /// Shifts `a` by `b` bits towards the least significant bit and fills with `0`, the carry flag
+    /// is the last bit shifted out
+    ShiftRight,
+    /// Shifts `a` by `b` bits towards the least significant bit and fills with the sign of `a`,
+    /// the carry flag is the last bit shifted out
+    ArithmeticShiftRight,
+    /// Outputs `a`, but sets the flags like [`AluOp::Sub`]
+    Compare,
+    /// Outputs `1` if `a` is less than `b` as signed numbers, otherwise `0`. The flags are set like
+    /// [`AluOp::Sub`]
+    SetLessThan,
+    /// Outputs `1` if `a` is less than `b` as unsigned numbers, otherwise `0`. The flags are set
+    /// like [`AluOp::Sub`]
+    SetLessThanUnsigned,
+}
+
+impl AluOp {
+    /// All operations, in the order of their opcodes of [`Alu::with_width`].
+    pub const ALL: [AluOp; 16] = [
+        AluOp::PassA,
+        AluOp::PassB,
+        AluOp::Add,
+        AluOp::AddCarry,
+        AluOp::Sub,
+        AluOp::SubBorrow,
+        AluOp::And,
+        AluOp::Or,
+        AluOp::Xor,
+        AluOp::Not,
+        AluOp::ShiftLeft,
+        AluOp::ShiftRight,
+        AluOp::ArithmeticShiftRight,
+        AluOp::Compare,
+        AluOp::SetLessThan,
+        AluOp::SetLessThanUnsigned,
+    ];
+}
+
+/// The result of an operation: the output, the value the zero and negative flags are derived from
+/// and the carry and overflow flags.
+struct Outcome {
+    y: LogicVector,
+    flags_of: LogicVector,
+    carry: Ieee1164,
+    overflow: Ieee1164,
+}
+
+impl Outcome {
+    fn logic(y: LogicVector) -> Self {
+        Outcome {
+            flags_of: y.clone(),
+            y,
+            carry: Ieee1164::_0,
+            overflow: Ieee1164::_0,
+        }
+    }
+
+    fn unknown(width: usize) -> Self {
+        let x = LogicVector::from_ieee(Ieee1164::_X, width);
+        Outcome {
+            flags_of: x.clone(),
+            y: x,
+            carry: Ieee1164::_X,
+            overflow: Ieee1164::_X,
+        }
+    }
+}
+
+fn msb(v: &LogicVector) -> Ieee1164 {
+    v.get(v.width() - 1).unwrap()
+}
+
+fn carry_vector(c: Ieee1164, width: usize) -> LogicVector {
+    let mut v = LogicVector::from_ieee(Ieee1164::_0, width);
+    v.set(0, c.to_x01());
+    v
+}
+
+/// Calculates `a + b + c` (or `a - b - c`) together with the carry (borrow) and the signed
+/// overflow.
+fn add_sub(a: &LogicVector, b: &LogicVector, c: Ieee1164, subtract: bool) -> Outcome {
+    let c = carry_vector(c, a.width());
+    let (y, carry) = if subtract {
+        let (d, b1) = a.overflowing_sub(b);
+        let (d, b2) = d.overflowing_sub(&c);
+        (d, b1 | b2)
+    } else {
+        let (s, c1) = a.overflowing_add(b);
+        let (s, c2) = s.overflowing_add(&c);
+        (s, c1 | c2)
+    };
+    // the operands of a subtraction overflow if their signs differ, the ones of an addition if
+    // their signs are equal, and the sign of the result differs from the one of `a`
+    let same_sign = !(msb(a) ^ msb(b));
+    let overflow = (if subtract { !same_sign } else { same_sign }) & (msb(&y) ^ msb(a));
+    Outcome {
+        flags_of: y.clone(),
+        y,
+        carry,
+        overflow,
+    }
+}
+
+fn shift(op: AluOp, a: &LogicVector, b: &LogicVector) -> Outcome {
+    let width = a.width();
+    let n = match b.as_u128() {
+        Some(n) => usize::try_from(n).unwrap_or(usize::MAX),
+        None => return Outcome::unknown(width),
+    };
+    let (y, carry) = match op {
+        AluOp::ShiftLeft => (
+            a.shift_left(n),
+            n.checked_sub(1)
+                .and_then(|n| a.get(width.wrapping_sub(n + 1))),
+        ),
+        AluOp::ShiftRight => (a.shift_right(n), n.checked_sub(1).and_then(|n| a.get(n))),
+        _ => (
+            a.arithmetic_shift_right(n),
+            n.checked_sub(1).map(|n| a.get(n).unwrap_or_else(|| msb(a))),
+        ),
+    };
+    Outcome {
+        carry: carry.unwrap_or(Ieee1164::_0),
+        ..Outcome::logic(y)
+    }
+}
+
+fn compare(op: AluOp, a: &LogicVector, b: &LogicVector) -> Outcome {
+    let flags = add_sub(a, b, Ieee1164::_0, true);
+    let y = match op {
+        AluOp::Compare => a.clone(),
+        _ => {
+            let order = match op {
+                AluOp::SetLessThan => a.signed_partial_cmp(b),
+                _ => a.partial_cmp(b),
+            };
+            match order {
+                Some(Ordering::Less) => LogicVector::from_int(1, a.width()).unwrap(),
+                Some(_) => LogicVector::from_int(0, a.width()).unwrap(),
+                None => LogicVector::from_ieee(Ieee1164::_X, a.width()),
+            }
+        }
+    };
+    Outcome { y, ..flags }
+}
+
+fn execute(op: AluOp, a: &LogicVector, b: &LogicVector, c: Ieee1164) -> Outcome {
+    match op {
+        AluOp::PassA => Outcome::logic(a.clone()),
+        AluOp::PassB => Outcome::logic(b.clone()),
+        AluOp::Add => add_sub(a, b, Ieee1164::_0, false),
+        AluOp::AddCarry => add_sub(a, b, c, false),
+        AluOp::Sub => add_sub(a, b, Ieee1164::_0, true),
+        AluOp::SubBorrow => add_sub(a, b, c, true),
+        AluOp::And => Outcome::logic(a & b),
+        AluOp::Or => Outcome::logic(a | b),
+        AluOp::Xor => Outcome::logic(a ^ b),
+        AluOp::Not => Outcome::logic(!a),
+        AluOp::ShiftLeft | AluOp::ShiftRight | AluOp::ArithmeticShiftRight => shift(op, a, b),
+        AluOp::Compare | AluOp::SetLessThan | AluOp::SetLessThanUnsigned => compare(op, a, b),
+    }
+}
+
+/// Returns [`Ieee1164::_1`] if all bits of `v` are `0`, [`Ieee1164::_0`] if any bit is `1` and
+/// [`Ieee1164::_X`] otherwise.
+fn is_zero(v: &LogicVector) -> Ieee1164 {
+    (0..v.width())
+        .map(|i| !v.get(i).unwrap())
+        .fold(Ieee1164::_1, |zero, bit| zero & bit)
+        .to_x01()
+}
+
+/// An arithmetic logic unit, that performs the operation selected by [`Alu::op`] on
+/// [`Alu::a`] and [`Alu::b`] and outputs the result on [`Alu::y`].
+///
+/// Which opcode selects which [`AluOp`] is configurable, see [`Alu::with_ops`]. The `op` input is
+/// as wide as needed for all opcodes. If it is unknown or selects no operation, all outputs are
+/// [`Ieee1164::_X`].
+///
+/// Besides the result, the ALU outputs four flags:
+///
+/// * [`Alu::zero`] is high if the result is `0`
+/// * [`Alu::negative`] is the most significant bit of the result
+/// * [`Alu::carry`] is the carry out of an addition, the borrow of a subtraction or the last bit
+///   shifted out, otherwise `0`
+/// * [`Alu::overflow`] is high if an addition or subtraction overflows for signed numbers,
+///   otherwise `0`
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::arithmic::{Alu, AluOp};
+///
+/// let alu = Alu::with_width(8).unwrap();
+/// let op = Port::<_, Output>::new(alu.opcode(AluOp::Add).unwrap());
+/// let a = Port::<_, Output>::new(LogicVector::from_int(200, 8).unwrap());
+/// let b = Port::<_, Output>::new(LogicVector::from_int(100, 8).unwrap());
+/// let y = Port::<_, Input>::new(LogicVector::with_width(8));
+/// let carry = Port::<_, Input>::default();
+///
+/// let mut circuit = circuit!(
+///     signal!(op, alu.op),
+///     signal!(a, alu.a),
+///     signal!(b, alu.b),
+///     signal!(alu.y, y),
+///     signal!(alu.carry, carry)
+/// );
+/// circuit.add(alu);
+///
+/// circuit.step().unwrap();
+/// assert_eq!(Some(44), y.value().as_u128());
+/// assert_eq!(Ieee1164::_1, carry.value());
+/// ```
+#[derive(Debug, Component)]
+pub struct Alu {
+    /// Opcode input `Port`, selects the operation
+    #[port(input)]
+    pub op: Port<LogicVector, Input>,
+    /// First operand `Port`
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Second operand `Port`
+    #[port(input)]
+    pub b: Port<LogicVector, Input>,
+    /// Carry (or borrow) input `Port`, `0` by default
+    #[port(input)]
+    pub carry_in: Port<Ieee1164, Input>,
+    /// Result `Port`
+    #[port(output)]
+    pub y: Port<LogicVector, Output>,
+    /// Zero flag `Port`
+    #[port(output)]
+    pub zero: Port<Ieee1164, Output>,
+    /// Negative flag `Port`
+    #[port(output)]
+    pub negative: Port<Ieee1164, Output>,
+    /// Carry flag `Port`
+    #[port(output)]
+    pub carry: Port<Ieee1164, Output>,
+    /// Overflow flag `Port`
+    #[port(output)]
+    pub overflow: Port<Ieee1164, Output>,
+    ops: Vec<AluOp>,
+    width: usize,
+    _private: (),
+}
+
+impl Alu {
+    /// Creates an ALU with operands that are `width` bits wide, which supports all operations in
+    /// the order of [`AluOp::ALL`], i.e. the opcode of [`AluOp::PassA`] is `0` and the one of
+    /// [`AluOp::SetLessThanUnsigned`] is `15`.
+    ///
+    /// Returns `None` if `width` is `0`.
+    pub fn with_width(width: usize) -> Option<Self> {
+        Self::with_ops(width, &AluOp::ALL)
+    }
+
+    /// Creates an ALU with operands that are `width` bits wide. The opcode of an operation is its
+    /// index in `ops`.
+    ///
+    /// Returns `None` if `width` is `0` or `ops` is empty.
+    pub fn with_ops(width: usize, ops: &[AluOp]) -> Option<Self> {
+        if width == 0 || ops.is_empty() {
+            return None;
+        }
+        Some(Self {
+            op: Port::new(LogicVector::with_width(select_width(ops.len()))),
+            a: Port::new(LogicVector::with_width(width)),
+            b: Port::new(LogicVector::with_width(width)),
+            carry_in: Port::new(Ieee1164::_0),
+            y: Port::new(LogicVector::with_width(width)),
+            zero: Port::default(),
+            negative: Port::default(),
+            carry: Port::default(),
+            overflow: Port::default(),
+            ops: ops.to_vec(),
+            width,
+            _private: (),
+        })
+    }
+
+    /// Returns the operations of this ALU, ordered by their opcode.
+    pub fn ops(&self) -> &[AluOp] {
+        &self.ops
+    }
+
+    /// Returns the value that has to be applied to [`Alu::op`] to select `op`, or `None` if this
+    /// ALU does not support it.
+    pub fn opcode(&self, op: AluOp) -> Option<LogicVector> {
+        let index = self.ops.iter().position(|&o| o == op)?;
+        LogicVector::from_int(index as u128, select_width(self.ops.len()))
+    }
+
+    fn calc(&self) -> Outcome {
+        let (a, b) = (self.a.value(), self.b.value());
+        if a.width() != self.width || b.width() != self.width {
+            return Outcome::unknown(self.width);
+        }
+        let op = self
+            .op
+            .value()
+            .as_u128()
+            .and_then(|i| usize::try_from(i).ok())
+            .and_then(|i| self.ops.get(i));
+        match op {
+            Some(&op) => execute(op, &a, &b, self.carry_in.value()),
+            None => Outcome::unknown(self.width),
+        }
+    }
+}
+
+impl Updateable for Alu {
+    fn update(&mut self) -> bool {
+        let outcome = self.calc();
+        let zero = is_zero(&outcome.flags_of);
+        let negative = msb(&outcome.flags_of).to_x01();
+        [
+            drive(&mut self.y, outcome.y),
+            drive(&mut self.zero, zero),
+            drive(&mut self.negative, negative),
+            drive(&mut self.carry, outcome.carry.to_x01()),
+            drive(&mut self.overflow, outcome.overflow.to_x01()),
+        ]
+        .contains(&true)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    fn lv(value: i128) -> LogicVector {
+        LogicVector::from_i128(value, 8)
+            .or_else(|| LogicVector::from_int(value as u128, 8))
+            .unwrap()
+    }
+
+    /// Returns `(y, zero, negative, carry, overflow)`
+    fn run(op: AluOp, a: LogicVector, b: LogicVector, c: Ieee1164) -> (LogicVector, [Ieee1164; 4]) {
+        let alu = Alu::with_width(8).unwrap();
+        let ports = (
+            Port::<_, Output>::new(alu.opcode(op).unwrap()),
+            Port::<_, Output>::new(a),
+            Port::<_, Output>::new(b),
+            Port::<_, Output>::new(c),
+        );
+        let y = Port::<_, Input>::new(LogicVector::with_width(8));
+        let flags: Vec<_> = (0..4).map(|_| Port::<_, Input>::default()).collect();
+
+        let mut circuit = circuit!(
+            signal!(ports.0, alu.op),
+            signal!(ports.1, alu.a),
+            signal!(ports.2, alu.b),
+            signal!(ports.3, alu.carry_in),
+            signal!(alu.y, y),
+            signal!(alu.zero, flags[0]),
+            signal!(alu.negative, flags[1]),
+            signal!(alu.carry, flags[2]),
+            signal!(alu.overflow, flags[3])
+        );
+        circuit.add(alu);
+        circuit.step().unwrap();
+        (
+            y.value(),
+            [
+                flags[0].value(),
+                flags[1].value(),
+                flags[2].value(),
+                flags[3].value(),
+            ],
+        )
+    }
+
+    #[test]
+    fn add_and_sub() {
+        assert_eq!(
+            (lv(0), [_1, _0, _1, _0]),
+            run(AluOp::Add, lv(0xff), lv(1), _0)
+        );
+        assert_eq!(
+            (lv(-128), [_0, _1, _0, _1]),
+            run(AluOp::Add, lv(127), lv(1), _0)
+        );
+        assert_eq!(
+            (lv(3), [_0, _0, _1, _0]),
+            run(AluOp::AddCarry, lv(0xff), lv(3), _1)
+        );
+        assert_eq!(
+            (lv(-1), [_0, _1, _1, _0]),
+            run(AluOp::Sub, lv(3), lv(4), _0)
+        );
+        assert_eq!(
+            (lv(127), [_0, _0, _0, _1]),
+            run(AluOp::Sub, lv(-128), lv(1), _0)
+        );
+        assert_eq!(
+            (lv(0), [_1, _0, _0, _0]),
+            run(AluOp::SubBorrow, lv(5), lv(4), _1)
+        );
+        assert_eq!(lv(4), run(AluOp::AddCarry, lv(1), lv(2), Ieee1164::_H).0);
+    }
+
+    #[test]
+    fn logic_and_shifts() {
+        assert_eq!(
+            (lv(0b1000), [_0, _0, _0, _0]),
+            run(AluOp::And, lv(0b1100), lv(0b1010), _1)
+        );
+        assert_eq!(
+            (lv(0b1110), [_0, _0, _0, _0]),
+            run(AluOp::Or, lv(0b1100), lv(0b1010), _0)
+        );
+        assert_eq!(
+            (lv(0), [_1, _0, _0, _0]),
+            run(AluOp::Xor, lv(0x5a), lv(0x5a), _0)
+        );
+        assert_eq!(
+            (lv(!0x5a), [_0, _1, _0, _0]),
+            run(AluOp::Not, lv(0x5a), lv(0), _0)
+        );
+        assert_eq!(
+            (lv(0x40), [_0, _0, _1, _0]),
+            run(AluOp::ShiftLeft, lv(0x85), lv(6), _0)
+        );
+        assert_eq!(
+            (lv(0x20), [_0, _0, _0, _0]),
+            run(AluOp::ShiftRight, lv(0x81), lv(2), _0)
+        );
+        assert_eq!(
+            (lv(-32), [_0, _1, _0, _0]),
+            run(AluOp::ArithmeticShiftRight, lv(0x81), lv(2), _0)
+        );
+        assert_eq!(
+            (lv(-1), [_0, _1, _1, _0]),
+            run(AluOp::ArithmeticShiftRight, lv(0x81), lv(9), _0)
+        );
+        assert_eq!(
+            (lv(0), [_1, _0, _0, _0]),
+            run(AluOp::ShiftLeft, lv(0x81), lv(9), _0)
+        );
+    }
+
+    #[test]
+    fn compare() {
+        assert_eq!(
+            (lv(3), [_0, _1, _1, _0]),
+            run(AluOp::Compare, lv(3), lv(4), _0)
+        );
+        assert_eq!(
+            (lv(5), [_0, _0, _0, _0]),
+            run(AluOp::PassB, lv(3), lv(5), _1)
+        );
+        assert_eq!(lv(1), run(AluOp::SetLessThan, lv(-1), lv(1), _0).0);
+        assert_eq!(lv(0), run(AluOp::SetLessThanUnsigned, lv(-1), lv(1), _0).0);
+    }
+}
@@ -0,0 +1,288 @@
+use super::{Action, Sequencer};
+use crate::direction::{Input, Output};
+use crate::models::rtlib::sequential::Storable;
+use crate::models::{checked, drive};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// A sequential restoring divider for unsigned numbers, that needs one clock cycle per bit of the
+/// dividend.
+///
+/// A high [`Divider::start`] on a rising edge of [`Divider::clk`] samples [`Divider::dividend`]
+/// and [`Divider::divisor`]. `width` rising edges later the quotient and the remainder are
+/// outputted on [`Divider::quotient`] and [`Divider::remainder`] and [`Divider::done`] is set.
+/// Meanwhile [`Divider::busy`] is high and `start` is ignored.
+///
+/// Every step shifts the next bit of the dividend into the partial remainder and subtracts the
+/// divisor. If the difference is negative the partial remainder is restored, otherwise a `1` is
+/// shifted into the quotient. Just like the hardware, a division by zero results in a quotient
+/// with all bits set and the dividend as remainder.
+///
+/// Uncertain clock edges or control inputs make the state and the outputs [`Ieee1164::_X`], until
+/// the divider is reset or started again.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::arithmic::Divider;
+///
+/// let div = Divider::with_width(8).unwrap();
+/// let dividend = Port::<_, Output>::new(LogicVector::from_int(200, 8).unwrap());
+/// let divisor = Port::<_, Output>::new(LogicVector::from_int(7, 8).unwrap());
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let mut start = Port::<_, Output>::new(Ieee1164::_1);
+/// let quotient = Port::<_, Input>::new(LogicVector::with_width(8));
+/// let remainder = Port::<_, Input>::new(LogicVector::with_width(8));
+/// let busy = Port::<_, Input>::default();
+///
+/// let mut circuit = circuit!(
+///     signal!(dividend, div.dividend),
+///     signal!(divisor, div.divisor),
+///     signal!(clk, div.clk),
+///     signal!(start, div.start),
+///     signal!(div.quotient, quotient),
+///     signal!(div.remainder, remainder),
+///     signal!(div.busy, busy)
+/// );
+/// circuit.add(div);
+///
+/// circuit.step().unwrap();
+/// for _ in 0..=8 {
+///     clk.replace(Ieee1164::_1);
+///     circuit.step().unwrap();
+///     start.replace(Ieee1164::_0);
+///     clk.replace(Ieee1164::_0);
+///     circuit.step().unwrap();
+/// }
+/// assert_eq!(Ieee1164::_0, busy.value());
+/// assert_eq!(Some(28), quotient.value().as_u128());
+/// assert_eq!(Some(4), remainder.value().as_u128());
+/// ```
+#[derive(Debug, Component)]
+pub struct Divider {
+    /// Dividend `Port`
+    #[port(input)]
+    pub dividend: Port<LogicVector, Input>,
+    /// Divisor `Port`
+    #[port(input)]
+    pub divisor: Port<LogicVector, Input>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high start `Port`, samples the operands on a rising edge
+    #[port(input)]
+    pub start: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Quotient `Port`
+    #[port(output)]
+    pub quotient: Port<LogicVector, Output>,
+    /// Remainder `Port`
+    #[port(output)]
+    pub remainder: Port<LogicVector, Output>,
+    /// Output `Port`, high while a division is in progress
+    #[port(output)]
+    pub busy: Port<Ieee1164, Output>,
+    /// Output `Port`, high when the result of the last division is available
+    #[port(output)]
+    pub done: Port<Ieee1164, Output>,
+    sequencer: Sequencer,
+    width: usize,
+    partial: LogicVector,
+    shifted: LogicVector,
+    divisor_reg: LogicVector,
+    _private: (),
+}
+
+impl Divider {
+    /// Creates a divider whose operands and results are `width` bits wide.
+    ///
+    /// Returns `None` if `width` is `0`.
+    pub fn with_width(width: usize) -> Option<Self> {
+        if width == 0 {
+            return None;
+        }
+        Some(Self {
+            dividend: Port::new(LogicVector::with_width(width)),
+            divisor: Port::new(LogicVector::with_width(width)),
+            clk: Port::default(),
+            start: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            quotient: Port::new(LogicVector::with_width(width)),
+            remainder: Port::new(LogicVector::with_width(width)),
+            busy: Port::default(),
+            done: Port::default(),
+            sequencer: Sequencer::new(width),
+            width,
+            partial: LogicVector::with_width(width + 1),
+            shifted: LogicVector::with_width(width),
+            divisor_reg: LogicVector::with_width(width + 1),
+            _private: (),
+        })
+    }
+
+    /// Returns the number of clock cycles between sampling the operands and the result being
+    /// available.
+    pub fn latency(&self) -> usize {
+        self.width
+    }
+
+    fn load(&mut self) {
+        // the partial remainder is one bit wider than the divisor, so the subtraction never wraps
+        self.partial = LogicVector::from_ieee(Ieee1164::_0, self.width + 1);
+        self.shifted = checked(self.dividend.value(), self.width);
+        self.divisor_reg = checked(self.divisor.value(), self.width)
+            .zero_extend(self.width + 1)
+            .unwrap();
+    }
+
+    /// Shifts the most significant bit of the dividend into the partial remainder and subtracts
+    /// the divisor, if possible. The quotient is shifted in from the other side of the dividend
+    /// register.
+    fn step(&mut self) {
+        let msb = self.shifted.get(self.width - 1).unwrap();
+        self.partial = self.partial.shift_left(1);
+        self.partial.set(0, msb);
+        self.shifted = self.shifted.shift_left(1);
+
+        let (difference, borrow) = self.partial.overflowing_sub(&self.divisor_reg);
+        let quotient_bit = !borrow.to_x01();
+        self.partial = match quotient_bit {
+            Ieee1164::_1 => difference,
+            Ieee1164::_0 => self.partial.clone(),
+            _ => self.partial.merge(&difference),
+        };
+        self.shifted.set(0, quotient_bit);
+    }
+}
+
+impl Updateable for Divider {
+    fn update(&mut self) -> bool {
+        let mut result = None;
+        match self
+            .sequencer
+            .clock(self.clk.value(), self.start.value(), self.reset.value())
+        {
+            Action::Nothing => {}
+            Action::Reset => {
+                result = Some((
+                    self.shifted.filled(Ieee1164::_0),
+                    self.shifted.filled(Ieee1164::_0),
+                ))
+            }
+            Action::Load => self.load(),
+            Action::Step { last, .. } => {
+                self.step();
+                if last {
+                    result = Some((
+                        self.shifted.clone(),
+                        self.partial.slice(..self.width).unwrap(),
+                    ));
+                }
+            }
+            Action::Corrupt => {
+                result = Some((
+                    self.shifted.filled(Ieee1164::_X),
+                    self.shifted.filled(Ieee1164::_X),
+                ))
+            }
+        }
+
+        let result_changed = match result {
+            Some((quotient, remainder)) => {
+                let quotient_changed = drive(&mut self.quotient, quotient);
+                drive(&mut self.remainder, remainder) || quotient_changed
+            }
+            None => false,
+        };
+        let busy_changed = drive(&mut self.busy, self.sequencer.busy());
+        drive(&mut self.done, self.sequencer.done()) || busy_changed || result_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.reset.id()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+
+    /// Returns `(quotient, remainder)` after `latency` cycles
+    fn divide(width: usize, dividend: u128, divisor: u128) -> (Option<u128>, Option<u128>) {
+        let div = Divider::with_width(width).unwrap();
+        let latency = div.latency();
+        let dividend = Port::<_, Output>::new(LogicVector::from_int(dividend, width).unwrap());
+        let divisor = Port::<_, Output>::new(LogicVector::from_int(divisor, width).unwrap());
+        let mut clk = Port::<_, Output>::new(_0);
+        let mut start = Port::<_, Output>::new(_1);
+        let quotient = Port::<_, Input>::new(LogicVector::with_width(width));
+        let remainder = Port::<_, Input>::new(LogicVector::with_width(width));
+        let busy = Port::<_, Input>::default();
+        let done = Port::<_, Input>::default();
+
+        let mut circuit = circuit!(
+            signal!(dividend, div.dividend),
+            signal!(divisor, div.divisor),
+            signal!(clk, div.clk),
+            signal!(start, div.start),
+            signal!(div.quotient, quotient),
+            signal!(div.remainder, remainder),
+            signal!(div.busy, busy),
+            signal!(div.done, done)
+        );
+        circuit.add(div);
+        circuit.step().unwrap();
+
+        for cycle in 0..=latency {
+            clk.replace(_1);
+            circuit.step().unwrap();
+            start.replace(_0);
+            clk.replace(_0);
+            circuit.step().unwrap();
+            let finished = cycle == latency;
+            assert_eq!(finished, busy.value() == _0);
+            assert_eq!(finished, done.value() == _1);
+        }
+        (quotient.value().as_u128(), remainder.value().as_u128())
+    }
+
+    #[test]
+    fn quotient_and_remainder() {
+        assert!(Divider::with_width(0).is_none());
+        for &(a, b) in &[
+            (0, 1),
+            (255, 1),
+            (255, 255),
+            (254, 255),
+            (100, 10),
+            (128, 3),
+            (77, 200),
+        ] {
+            assert_eq!((Some(a / b), Some(a % b)), divide(8, a, b), "{} / {}", a, b);
+        }
+        assert_eq!((Some(0), Some(0)), divide(1, 0, 1));
+        assert_eq!((Some(1), Some(0)), divide(1, 1, 1));
+    }
+
+    #[test]
+    fn division_by_zero() {
+        assert_eq!((Some(0xff), Some(42)), divide(8, 42, 0));
+        assert_eq!((Some(1), Some(1)), divide(1, 1, 0));
+    }
+}
@@ -1,8 +1,107 @@
-//! TODO
-// TODO :)
+//! This module consists of arithmetic models that work on [`LogicVector`](crate::LogicVector)s,
+//! e.g. an [`Add`]er or an [`Alu`].
+//!
+//! Some operations are available as a combinational model, which calculates the result
+//! immediately, and as a sequential model, which needs several clock cycles but would use less
+//! hardware, e.g. the [`Multiplier`] and the [`SequentialMultiplier`]. The sequential models share
+//! the same handshake: a high `start` on a rising edge of `clk` samples the operands and sets
+//! `busy`. When the result is available, `busy` is cleared and `done` is set until the next start.
 
 mod add;
+mod alu;
+mod div;
+mod mul;
+mod shift;
 mod twoscomplement;
 
 pub use self::add::Add;
+pub use self::alu::{Alu, AluOp};
+pub use self::div::Divider;
+pub use self::mul::{Multiplier, SequentialMultiplier};
+pub use self::shift::{BarrelShifter, ShiftKind};
 pub use self::twoscomplement::TwosComplement;
+
+use crate::models::rtlib::sequential::Edge;
+use crate::Ieee1164;
+
+/// What a sequential model has to do after its inputs have changed, see [`Sequencer::clock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    /// Nothing happened
+    Nothing,
+    /// The model was reset
+    Reset,
+    /// The operands have to be sampled
+    Load,
+    /// Step number `step` of the calculation has to be done, `last` is `true` for the final one
+    Step { step: usize, last: bool },
+    /// It is unknown what happened, so the state of the model is unknown, too
+    Corrupt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Idle { done: bool },
+    Busy(usize),
+    Unknown,
+}
+
+/// The control unit of a sequential model that needs `steps` clock cycles for a calculation.
+#[derive(Debug)]
+struct Sequencer {
+    state: State,
+    steps: usize,
+    last_clk: Ieee1164,
+}
+
+impl Sequencer {
+    fn new(steps: usize) -> Self {
+        Sequencer {
+            state: State::Idle { done: false },
+            steps,
+            last_clk: Ieee1164::_U,
+        }
+    }
+
+    /// Advances the state with the current values of the control inputs.
+    fn clock(&mut self, clk: Ieee1164, start: Ieee1164, reset: Ieee1164) -> Action {
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        let (state, action) = match (reset.to_x01(), edge, self.state) {
+            (Ieee1164::_1, _, _) => (State::Idle { done: false }, Action::Reset),
+            (Ieee1164::_X, _, _) | (_, Edge::Uncertain, _) => (State::Unknown, Action::Corrupt),
+            (_, Edge::None, state) => (state, Action::Nothing),
+            (_, Edge::Rising, State::Busy(step)) if step + 1 == self.steps => (
+                State::Idle { done: true },
+                Action::Step { step, last: true },
+            ),
+            (_, Edge::Rising, State::Busy(step)) => {
+                (State::Busy(step + 1), Action::Step { step, last: false })
+            }
+            (_, Edge::Rising, state) => match start.to_x01() {
+                Ieee1164::_1 => (State::Busy(0), Action::Load),
+                Ieee1164::_0 => (state, Action::Nothing),
+                _ => (State::Unknown, Action::Corrupt),
+            },
+        };
+        self.state = state;
+        action
+    }
+
+    fn busy(&self) -> Ieee1164 {
+        match self.state {
+            State::Idle { .. } => Ieee1164::_0,
+            State::Busy(_) => Ieee1164::_1,
+            State::Unknown => Ieee1164::_X,
+        }
+    }
+
+    fn done(&self) -> Ieee1164 {
+        match self.state {
+            State::Idle { done: true } => Ieee1164::_1,
+            State::Unknown => Ieee1164::_X,
+            _ => Ieee1164::_0,
+        }
+    }
+}
@@ -0,0 +1,469 @@
+use super::{Action, Sequencer};
+use crate::direction::{Input, Output};
+use crate::models::rtlib::sequential::Storable;
+use crate::models::{checked, drive};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// A combinational multiplier, that outputs the product of [`Multiplier::a`] and
+/// [`Multiplier::b`] on [`Multiplier::p`] without losing any bit, i.e. the product is as wide as
+/// both operands together.
+///
+/// The operands are either unsigned or in two's complement, see [`Multiplier::unsigned`] and
+/// [`Multiplier::signed`]. If an operand contains a metavalue, the product is all
+/// [`Ieee1164::_X`].
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::arithmic::Multiplier;
+///
+/// let mul = Multiplier::signed(8, 4).unwrap();
+/// let a = Port::<_, Output>::new(LogicVector::from_i128(-100, 8).unwrap());
+/// let b = Port::<_, Output>::new(LogicVector::from_i128(7, 4).unwrap());
+/// let p = Port::<_, Input>::new(LogicVector::with_width(12));
+///
+/// let mut circuit = circuit!(signal!(a, mul.a), signal!(b, mul.b), signal!(mul.p, p));
+/// circuit.add(mul);
+///
+/// circuit.step().unwrap();
+/// assert_eq!(Some(-700), p.value().as_i128());
+/// ```
+#[derive(Debug, Component)]
+pub struct Multiplier {
+    /// First operand `Port`
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Second operand `Port`
+    #[port(input)]
+    pub b: Port<LogicVector, Input>,
+    /// Product `Port`
+    #[port(output)]
+    pub p: Port<LogicVector, Output>,
+    a_width: usize,
+    b_width: usize,
+    signed: bool,
+    _private: (),
+}
+
+impl Multiplier {
+    fn new(a_width: usize, b_width: usize, signed: bool) -> Option<Self> {
+        if a_width == 0 || b_width == 0 {
+            return None;
+        }
+        Some(Self {
+            a: Port::new(LogicVector::with_width(a_width)),
+            b: Port::new(LogicVector::with_width(b_width)),
+            p: Port::new(LogicVector::with_width(a_width + b_width)),
+            a_width,
+            b_width,
+            signed,
+            _private: (),
+        })
+    }
+
+    /// Creates a multiplier for unsigned operands that are `a_width` and `b_width` bits wide.
+    ///
+    /// Returns `None` if one of the widths is `0`.
+    pub fn unsigned(a_width: usize, b_width: usize) -> Option<Self> {
+        Self::new(a_width, b_width, false)
+    }
+
+    /// Creates a multiplier for operands in two's complement that are `a_width` and `b_width` bits
+    /// wide.
+    ///
+    /// Returns `None` if one of the widths is `0`.
+    pub fn signed(a_width: usize, b_width: usize) -> Option<Self> {
+        Self::new(a_width, b_width, true)
+    }
+
+    /// Returns `true` if the operands are multiplied as numbers in two's complement.
+    pub fn is_signed(&self) -> bool {
+        self.signed
+    }
+}
+
+impl Updateable for Multiplier {
+    fn update(&mut self) -> bool {
+        let (a, b) = (self.a.value(), self.b.value());
+        let product = if a.width() != self.a_width || b.width() != self.b_width {
+            LogicVector::from_ieee(Ieee1164::_X, self.a_width + self.b_width)
+        } else if self.signed {
+            a.signed_widening_mul(&b)
+        } else {
+            a.widening_mul(&b)
+        };
+        drive(&mut self.p, product)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+/// A sequential shift-and-add multiplier, that needs one clock cycle per bit of [`SequentialMultiplier::b`].
+///
+/// A high [`SequentialMultiplier::start`] on a rising edge of [`SequentialMultiplier::clk`]
+/// samples both operands. `b_width` rising edges later the product is outputted on
+/// [`SequentialMultiplier::p`] and [`SequentialMultiplier::done`] is set. Meanwhile
+/// [`SequentialMultiplier::busy`] is high and `start` is ignored. The product is as wide as both
+/// operands together and keeps its value until the next multiplication is done.
+///
+/// Like the [`Multiplier`], the operands are either unsigned or in two's complement. Uncertain
+/// clock edges or control inputs make the state and the outputs [`Ieee1164::_X`], until the
+/// multiplier is reset or started again.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::arithmic::SequentialMultiplier;
+///
+/// let mul = SequentialMultiplier::unsigned(8, 8).unwrap();
+/// let a = Port::<_, Output>::new(LogicVector::from_int(200, 8).unwrap());
+/// let b = Port::<_, Output>::new(LogicVector::from_int(100, 8).unwrap());
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let start = Port::<_, Output>::new(Ieee1164::_1);
+/// let p = Port::<_, Input>::new(LogicVector::with_width(16));
+/// let done = Port::<_, Input>::default();
+///
+/// let mut circuit = circuit!(
+///     signal!(a, mul.a),
+///     signal!(b, mul.b),
+///     signal!(clk, mul.clk),
+///     signal!(start, mul.start),
+///     signal!(mul.p, p),
+///     signal!(mul.done, done)
+/// );
+/// circuit.add(mul);
+///
+/// circuit.step().unwrap();
+/// let mut cycles = 0;
+/// while done.value() != Ieee1164::_1 {
+///     clk.replace(Ieee1164::_1);
+///     circuit.step().unwrap();
+///     clk.replace(Ieee1164::_0);
+///     circuit.step().unwrap();
+///     cycles += 1;
+/// }
+/// assert_eq!(9, cycles);
+/// assert_eq!(Some(20000), p.value().as_u128());
+/// ```
+#[derive(Debug, Component)]
+pub struct SequentialMultiplier {
+    /// First operand `Port`
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Second operand `Port`
+    #[port(input)]
+    pub b: Port<LogicVector, Input>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high start `Port`, samples the operands on a rising edge
+    #[port(input)]
+    pub start: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Product `Port`
+    #[port(output)]
+    pub p: Port<LogicVector, Output>,
+    /// Output `Port`, high while a multiplication is in progress
+    #[port(output)]
+    pub busy: Port<Ieee1164, Output>,
+    /// Output `Port`, high when the product of the last multiplication is available
+    #[port(output)]
+    pub done: Port<Ieee1164, Output>,
+    sequencer: Sequencer,
+    multiplicand: LogicVector,
+    multiplier: LogicVector,
+    accumulator: LogicVector,
+    signed: bool,
+    _private: (),
+}
+
+impl SequentialMultiplier {
+    fn new(a_width: usize, b_width: usize, signed: bool) -> Option<Self> {
+        if a_width == 0 || b_width == 0 {
+            return None;
+        }
+        Some(Self {
+            a: Port::new(LogicVector::with_width(a_width)),
+            b: Port::new(LogicVector::with_width(b_width)),
+            clk: Port::default(),
+            start: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            p: Port::new(LogicVector::with_width(a_width + b_width)),
+            busy: Port::default(),
+            done: Port::default(),
+            sequencer: Sequencer::new(b_width),
+            multiplicand: LogicVector::with_width(a_width + b_width),
+            multiplier: LogicVector::with_width(b_width),
+            accumulator: LogicVector::with_width(a_width + b_width),
+            signed,
+            _private: (),
+        })
+    }
+
+    /// Creates a multiplier for unsigned operands that are `a_width` and `b_width` bits wide.
+    ///
+    /// Returns `None` if one of the widths is `0`.
+    pub fn unsigned(a_width: usize, b_width: usize) -> Option<Self> {
+        Self::new(a_width, b_width, false)
+    }
+
+    /// Creates a multiplier for operands in two's complement that are `a_width` and `b_width` bits
+    /// wide.
+    ///
+    /// Returns `None` if one of the widths is `0`.
+    pub fn signed(a_width: usize, b_width: usize) -> Option<Self> {
+        Self::new(a_width, b_width, true)
+    }
+
+    /// Returns `true` if the operands are multiplied as numbers in two's complement.
+    pub fn is_signed(&self) -> bool {
+        self.signed
+    }
+
+    /// Returns the number of clock cycles between sampling the operands and the product being
+    /// available.
+    pub fn latency(&self) -> usize {
+        self.multiplier.width()
+    }
+
+    fn load(&mut self) {
+        let width = self.accumulator.width();
+        let b_width = self.multiplier.width();
+        let a = checked(self.a.value(), width - b_width);
+        self.multiplicand = if self.signed {
+            a.sign_extend(width).unwrap()
+        } else {
+            a.zero_extend(width).unwrap()
+        };
+        self.multiplier = checked(self.b.value(), b_width);
+        self.accumulator = LogicVector::from_ieee(Ieee1164::_0, width);
+    }
+
+    /// Adds the partial product of bit `step` of the multiplier. The most significant bit of a
+    /// signed multiplier has a negative weight, so its partial product is subtracted.
+    fn step(&mut self, step: usize) {
+        let partial = self.multiplicand.shift_left(step);
+        let added = if self.signed && step + 1 == self.multiplier.width() {
+            self.accumulator.wrapping_sub(&partial)
+        } else {
+            self.accumulator.wrapping_add(&partial)
+        };
+        self.accumulator = match self.multiplier.get(step).unwrap().to_x01() {
+            Ieee1164::_1 => added,
+            Ieee1164::_0 => self.accumulator.clone(),
+            _ => self.accumulator.merge(&added),
+        };
+    }
+}
+
+impl Updateable for SequentialMultiplier {
+    fn update(&mut self) -> bool {
+        let mut p = None;
+        match self
+            .sequencer
+            .clock(self.clk.value(), self.start.value(), self.reset.value())
+        {
+            Action::Nothing => {}
+            Action::Reset => p = Some(self.accumulator.filled(Ieee1164::_0)),
+            Action::Load => self.load(),
+            Action::Step { step, last } => {
+                self.step(step);
+                if last {
+                    p = Some(self.accumulator.clone());
+                }
+            }
+            Action::Corrupt => p = Some(self.accumulator.filled(Ieee1164::_X)),
+        }
+
+        let p_changed = match p {
+            Some(p) => drive(&mut self.p, p),
+            None => false,
+        };
+        let busy_changed = drive(&mut self.busy, self.sequencer.busy());
+        drive(&mut self.done, self.sequencer.done()) || busy_changed || p_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.reset.id()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    #[test]
+    fn combinational() {
+        assert!(Multiplier::unsigned(0, 4).is_none());
+        let unsigned = Multiplier::unsigned(4, 4).unwrap();
+        let signed = Multiplier::signed(4, 4).unwrap();
+        let mut a = Port::<_, Output>::new(LogicVector::from_int(0b1111, 4).unwrap());
+        let b = Port::<_, Output>::new(LogicVector::from_int(0b0011, 4).unwrap());
+        let pu = Port::<_, Input>::new(LogicVector::with_width(8));
+        let ps = Port::<_, Input>::new(LogicVector::with_width(8));
+
+        let mut circuit = circuit!(
+            signal!(a, unsigned.a, signed.a),
+            signal!(b, unsigned.b, signed.b),
+            signal!(unsigned.p, pu),
+            signal!(signed.p, ps)
+        );
+        circuit.add(unsigned);
+        circuit.add(signed);
+
+        circuit.step().unwrap();
+        assert_eq!(Some(45), pu.value().as_u128());
+        assert_eq!(Some(-3), ps.value().as_i128());
+
+        a.replace("1X11".parse().unwrap());
+        circuit.step().unwrap();
+        assert_eq!("XXXXXXXX", pu.value().to_string());
+        assert_eq!("XXXXXXXX", ps.value().to_string());
+    }
+
+    struct Bench {
+        circuit: Circuit,
+        a: Port<LogicVector, Output>,
+        b: Port<LogicVector, Output>,
+        clk: Port<Ieee1164, Output>,
+        start: Port<Ieee1164, Output>,
+        reset: Port<Ieee1164, Output>,
+        p: Port<LogicVector, Input>,
+        busy: Port<Ieee1164, Input>,
+        done: Port<Ieee1164, Input>,
+        widths: (usize, usize),
+    }
+
+    impl Bench {
+        fn new(a_width: usize, b_width: usize, signed: bool) -> Self {
+            let mul = if signed {
+                SequentialMultiplier::signed(a_width, b_width)
+            } else {
+                SequentialMultiplier::unsigned(a_width, b_width)
+            }
+            .unwrap();
+            assert_eq!(b_width, mul.latency());
+            let a = Port::new(LogicVector::with_width(a_width));
+            let b = Port::new(LogicVector::with_width(b_width));
+            let (clk, start, reset) = (Port::new(_0), Port::new(_0), Port::new(_0));
+            let p = Port::new(LogicVector::with_width(a_width + b_width));
+            let (busy, done) = (Port::default(), Port::default());
+
+            let mut circuit = circuit!(
+                signal!(a, mul.a),
+                signal!(b, mul.b),
+                signal!(clk, mul.clk),
+                signal!(start, mul.start),
+                signal!(reset, mul.reset),
+                signal!(mul.p, p),
+                signal!(mul.busy, busy),
+                signal!(mul.done, done)
+            );
+            circuit.add(mul);
+            circuit.step().unwrap();
+            Bench {
+                circuit,
+                a,
+                b,
+                clk,
+                start,
+                reset,
+                p,
+                busy,
+                done,
+                widths: (a_width, b_width),
+            }
+        }
+
+        fn cycle(&mut self) {
+            self.clk.replace(_1);
+            self.circuit.step().unwrap();
+            self.clk.replace(_0);
+            self.circuit.step().unwrap();
+        }
+
+        fn multiply(&mut self, a: i128, b: i128) {
+            self.a
+                .replace(LogicVector::from_i128(a, self.widths.0).unwrap());
+            self.b
+                .replace(LogicVector::from_i128(b, self.widths.1).unwrap());
+            self.start.replace(_1);
+            self.cycle();
+            self.start.replace(_0);
+            while self.done.value() != _1 {
+                assert_eq!(_1, self.busy.value());
+                self.cycle();
+            }
+        }
+    }
+
+    #[test]
+    fn sequential_signed() {
+        let mut bench = Bench::new(6, 4, true);
+        assert_eq!((_0, _0), (bench.busy.value(), bench.done.value()));
+
+        for &(a, b) in &[(-32, -8), (31, 7), (-17, 5), (13, -3), (0, -8)] {
+            bench.multiply(a, b);
+            assert_eq!(Some(a * b), bench.p.value().as_i128());
+            assert_eq!(_0, bench.busy.value());
+        }
+
+        // the product is kept while idling
+        bench.cycle();
+        assert_eq!(
+            (Some(0), _1),
+            (bench.p.value().as_i128(), bench.done.value())
+        );
+    }
+
+    #[test]
+    fn uncertain_inputs() {
+        let mut bench = Bench::new(4, 4, false);
+        bench.a.replace("1111".parse().unwrap());
+        bench.b.replace("00X1".parse().unwrap());
+        bench.start.replace(_1);
+        bench.cycle();
+        bench.start.replace(_0);
+        for _ in 0..4 {
+            bench.cycle();
+        }
+        // 15 * 1 or 15 * 3
+        assert_eq!("00X011X1", bench.p.value().to_string());
+
+        bench.start.replace(_X);
+        bench.cycle();
+        assert_eq!("XXXXXXXX", bench.p.value().to_string());
+        assert_eq!((_X, _X), (bench.busy.value(), bench.done.value()));
+
+        bench.reset.replace(_1);
+        bench.circuit.step().unwrap();
+        assert_eq!("00000000", bench.p.value().to_string());
+        assert_eq!((_0, _0), (bench.busy.value(), bench.done.value()));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::direction::{Input, Output};
+use crate::models::rtlib::combinational::{candidates, merge_all};
+use crate::models::{drive, select_width};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// The operation of a [`BarrelShifter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShiftKind {
+    /// Shifts towards the most significant bit and fills in [`Ieee1164::_0`]
+    Left,
+    /// Shifts towards the least significant bit and fills in [`Ieee1164::_0`]
+    LogicalRight,
+    /// Shifts towards the least significant bit and fills in the sign bit
+    ArithmeticRight,
+    /// Rotates towards the most significant bit
+    RotateLeft,
+    /// Rotates towards the least significant bit
+    RotateRight,
+}
+
+impl ShiftKind {
+    fn apply(self, value: &LogicVector, amount: usize) -> LogicVector {
+        match self {
+            ShiftKind::Left => value.shift_left(amount),
+            ShiftKind::LogicalRight => value.shift_right(amount),
+            ShiftKind::ArithmeticRight => value.arithmetic_shift_right(amount),
+            ShiftKind::RotateLeft => value.rotate_left(amount),
+            ShiftKind::RotateRight => value.rotate_right(amount),
+        }
+    }
+}
+
+/// A barrel shifter, that shifts or rotates [`BarrelShifter::a`] by the number of bits on
+/// [`BarrelShifter::amount`] in a single step.
+///
+/// The amount port is just wide enough to hold every amount less than the width of `a`, so e.g.
+/// an 8 bit shifter has a 3 bit amount. Shifts by an amount that is not less than the width result
+/// in all bits being shifted out, rotations wrap around.
+///
+/// If the amount is not fully known, every amount it may stand for is taken into account: output
+/// bits that are the same for all of them keep their value, all other bits become
+/// [`Ieee1164::_X`].
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::arithmic::{BarrelShifter, ShiftKind};
+///
+/// let shifter = BarrelShifter::new(8, ShiftKind::RotateLeft).unwrap();
+/// let a = Port::<_, Output>::new(LogicVector::from_int(0b1000_0001, 8).unwrap());
+/// let amount = Port::<_, Output>::new(LogicVector::from_int(3, 3).unwrap());
+/// let z = Port::<_, Input>::new(LogicVector::with_width(8));
+///
+/// let mut circuit = circuit!(signal!(a, shifter.a), signal!(amount, shifter.amount), signal!(shifter.z, z));
+/// circuit.add(shifter);
+///
+/// circuit.step().unwrap();
+/// assert_eq!("00001100", z.value().to_string());
+/// ```
+#[derive(Debug, Component)]
+pub struct BarrelShifter {
+    /// Input `Port`
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Input `Port` with the number of bits to shift
+    #[port(input)]
+    pub amount: Port<LogicVector, Input>,
+    /// Output `Port`
+    #[port(output)]
+    pub z: Port<LogicVector, Output>,
+    kind: ShiftKind,
+    width: usize,
+    amount_width: usize,
+    _private: (),
+}
+
+impl BarrelShifter {
+    /// Creates a barrel shifter for values that are `width` bits wide.
+    ///
+    /// Returns `None` if `width` is `0`.
+    pub fn new(width: usize, kind: ShiftKind) -> Option<Self> {
+        if width == 0 {
+            return None;
+        }
+        let amount_width = select_width(width);
+        Some(Self {
+            a: Port::new(LogicVector::with_width(width)),
+            amount: Port::new(LogicVector::with_width(amount_width)),
+            z: Port::new(LogicVector::with_width(width)),
+            kind,
+            width,
+            amount_width,
+            _private: (),
+        })
+    }
+
+    /// Returns the operation of this shifter.
+    pub fn kind(&self) -> ShiftKind {
+        self.kind
+    }
+
+    /// Returns the width of [`BarrelShifter::amount`].
+    pub fn amount_width(&self) -> usize {
+        self.amount_width
+    }
+
+    fn calc(&self) -> LogicVector {
+        let (a, amount) = (self.a.value(), self.amount.value());
+        if a.width() != self.width || amount.width() != self.amount_width {
+            return LogicVector::from_ieee(Ieee1164::_X, self.width);
+        }
+
+        let (amounts, _) = candidates(&amount, 1 << self.amount_width);
+        merge_all(amounts.into_iter().map(|n| self.kind.apply(&a, n))).unwrap()
+    }
+}
+
+impl Updateable for BarrelShifter {
+    fn update(&mut self) -> bool {
+        let z = self.calc();
+        drive(&mut self.z, z)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    fn lv(s: &str) -> LogicVector {
+        s.parse().unwrap()
+    }
+
+    fn run(kind: ShiftKind, a: &str, amount: &str) -> String {
+        let width = a.len();
+        let shifter = BarrelShifter::new(width, kind).unwrap();
+        assert_eq!(amount.len(), shifter.amount_width());
+        let a = Port::<_, Output>::new(lv(a));
+        let amount = Port::<_, Output>::new(lv(amount));
+        let z = Port::<_, Input>::new(LogicVector::with_width(width));
+
+        let mut circuit = circuit!(
+            signal!(a, shifter.a),
+            signal!(amount, shifter.amount),
+            signal!(shifter.z, z)
+        );
+        circuit.add(shifter);
+        circuit.step().unwrap();
+        z.value().to_string()
+    }
+
+    #[test]
+    fn amount_width() {
+        assert!(BarrelShifter::new(0, ShiftKind::Left).is_none());
+        assert_eq!(
+            1,
+            BarrelShifter::new(1, ShiftKind::Left)
+                .unwrap()
+                .amount_width()
+        );
+        assert_eq!(
+            3,
+            BarrelShifter::new(8, ShiftKind::Left)
+                .unwrap()
+                .amount_width()
+        );
+        assert_eq!(
+            4,
+            BarrelShifter::new(9, ShiftKind::Left)
+                .unwrap()
+                .amount_width()
+        );
+    }
+
+    #[test]
+    fn kinds() {
+        assert_eq!("00110100", run(ShiftKind::Left, "10001101", "010"));
+        assert_eq!("00100011", run(ShiftKind::LogicalRight, "10001101", "010"));
+        assert_eq!(
+            "11100011",
+            run(ShiftKind::ArithmeticRight, "10001101", "010")
+        );
+        assert_eq!("00110110", run(ShiftKind::RotateLeft, "10001101", "010"));
+        assert_eq!("01100011", run(ShiftKind::RotateRight, "10001101", "010"));
+        // amounts beyond the width shift everything out
+        assert_eq!("000", run(ShiftKind::Left, "111", "11"));
+        assert_eq!("111", run(ShiftKind::RotateLeft, "111", "11"));
+    }
+
+    #[test]
+    fn uncertain_amount() {
+        assert_eq!("1X1X", run(ShiftKind::RotateLeft, "1110", "X0"));
+        assert_eq!("1X00", run(ShiftKind::Left, "0011", "1X"));
+        assert_eq!("1010", run(ShiftKind::RotateRight, "0101", "X1"));
+    }
+}
@@ -1,19 +1,40 @@
 use crate::direction::{Input, Output};
-use crate::{LogicVector, Port, Updateable};
+use crate::Component;
+use crate::{LogicVector, Port, PortId, Updateable};
 
 /// Computes the two's complement of the applied value.
-#[derive(Debug)]
+#[derive(Debug, Component)]
 pub struct TwosComplement {
     /// Input `Port`
+    #[port(input)]
     pub a: Port<LogicVector, Input>,
     /// Output `Port`
+    #[port(output)]
     pub y: Port<LogicVector, Output>,
 }
 
+impl TwosComplement {
+    /// Creates this model with an input and output that are `width` bits wide.
+    pub fn with_width(width: usize) -> Self {
+        Self {
+            a: Port::new(LogicVector::with_width(width)),
+            y: Port::new(LogicVector::with_width(width)),
+        }
+    }
+}
+
 impl Updateable for TwosComplement {
     fn update(&mut self) -> bool {
-        let new_value = (!self.a.value()).incr();
+        let new_value = -self.a.value();
         let old_value = self.y.replace(new_value.clone());
         old_value != new_value
     }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
 }
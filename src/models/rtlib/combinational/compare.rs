@@ -0,0 +1,257 @@
+use crate::direction::{Input, Output};
+use crate::models::drive;
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// Compares `a` and `b` bit by bit. The result is [`Ieee1164::_0`] as soon as one pair of bits
+/// definitely differs, otherwise [`Ieee1164::_X`] if any bit is unknown.
+fn equal(a: &LogicVector, b: &LogicVector) -> Ieee1164 {
+    let mut result = Ieee1164::_1;
+    for i in 0..a.width() {
+        let (a, b) = (a.get(i).unwrap().to_x01(), b.get(i).unwrap().to_x01());
+        if a.is_X() || b.is_X() {
+            result = Ieee1164::_X;
+        } else if a != b {
+            return Ieee1164::_0;
+        }
+    }
+    result
+}
+
+/// Compares `a` and `b` as unsigned numbers, starting with the most significant bit, and returns
+/// whether `a` is less than and greater than `b`. The results are [`Ieee1164::_X`] if an unknown
+/// bit is found before the first pair of bits that differs.
+fn order(a: &LogicVector, b: &LogicVector) -> (Ieee1164, Ieee1164) {
+    for i in (0..a.width()).rev() {
+        match (a.get(i).unwrap().to_x01(), b.get(i).unwrap().to_x01()) {
+            (Ieee1164::_0, Ieee1164::_1) => return (Ieee1164::_1, Ieee1164::_0),
+            (Ieee1164::_1, Ieee1164::_0) => return (Ieee1164::_0, Ieee1164::_1),
+            (a, b) if a == b && !a.is_X() => {}
+            _ => return (Ieee1164::_X, Ieee1164::_X),
+        }
+    }
+    (Ieee1164::_0, Ieee1164::_0)
+}
+
+/// A magnitude comparator, that compares the numbers on [`Comparator::a`] and [`Comparator::b`].
+///
+/// The numbers are either unsigned or in two's complement, see [`Comparator::unsigned`] and
+/// [`Comparator::signed`]. Unknown bits only make the outputs unknown if they matter, e.g. `1X` is
+/// greater than `0X`, but it is unknown whether it is equal to `11`.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::combinational::Comparator;
+///
+/// let cmp = Comparator::signed(4);
+/// let a = Port::<_, Output>::new(LogicVector::from_int(0b1111, 4).unwrap());
+/// let b = Port::<_, Output>::new(LogicVector::from_int(0b0001, 4).unwrap());
+/// let lt = Port::<_, Input>::default();
+///
+/// let mut circuit = circuit!(signal!(a, cmp.a), signal!(b, cmp.b), signal!(cmp.lt, lt));
+/// circuit.add(cmp);
+///
+/// circuit.step().unwrap();
+/// // -1 < 1
+/// assert_eq!(Ieee1164::_1, lt.value());
+/// ```
+#[derive(Debug, Component)]
+pub struct Comparator {
+    /// First input `Port`
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Second input `Port`
+    #[port(input)]
+    pub b: Port<LogicVector, Input>,
+    /// Output `Port`, high if `a` equals `b`
+    #[port(output)]
+    pub eq: Port<Ieee1164, Output>,
+    /// Output `Port`, high if `a` is less than `b`
+    #[port(output)]
+    pub lt: Port<Ieee1164, Output>,
+    /// Output `Port`, high if `a` is greater than `b`
+    #[port(output)]
+    pub gt: Port<Ieee1164, Output>,
+    width: usize,
+    signed: bool,
+    _private: (),
+}
+
+impl Comparator {
+    fn with_width(width: usize, signed: bool) -> Self {
+        Self {
+            a: Port::new(LogicVector::with_width(width)),
+            b: Port::new(LogicVector::with_width(width)),
+            eq: Port::default(),
+            lt: Port::default(),
+            gt: Port::default(),
+            width,
+            signed,
+            _private: (),
+        }
+    }
+
+    /// Creates a comparator for unsigned numbers that are `width` bits wide.
+    pub fn unsigned(width: usize) -> Self {
+        Self::with_width(width, false)
+    }
+
+    /// Creates a comparator for numbers in two's complement that are `width` bits wide.
+    pub fn signed(width: usize) -> Self {
+        Self::with_width(width, true)
+    }
+
+    /// Returns `true` if the inputs are compared as numbers in two's complement.
+    pub fn is_signed(&self) -> bool {
+        self.signed
+    }
+
+    fn calc(&self) -> (Ieee1164, Ieee1164, Ieee1164) {
+        let (mut a, mut b) = (self.a.value(), self.b.value());
+        if a.width() != self.width || b.width() != self.width {
+            return (Ieee1164::_X, Ieee1164::_X, Ieee1164::_X);
+        }
+        if self.signed {
+            // flipping the sign bits maps two's complement onto unsigned numbers with the same order
+            let msb = self.width - 1;
+            a.set(msb, !a.get(msb).unwrap());
+            b.set(msb, !b.get(msb).unwrap());
+        }
+        let (lt, gt) = order(&a, &b);
+        (equal(&a, &b), lt, gt)
+    }
+}
+
+impl Updateable for Comparator {
+    fn update(&mut self) -> bool {
+        let (eq, lt, gt) = self.calc();
+        let eq_changed = drive(&mut self.eq, eq);
+        let lt_changed = drive(&mut self.lt, lt);
+        drive(&mut self.gt, gt) || eq_changed || lt_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+/// An equality comparator, that outputs [`Ieee1164::_1`] on [`EqualityComparator::eq`] if both
+/// inputs are equal.
+///
+/// The output is [`Ieee1164::_0`] as soon as one pair of bits differs, even if other bits are
+/// unknown. Weak values are treated like their strong counterparts, e.g. `H` equals `1`.
+#[derive(Debug, Component)]
+pub struct EqualityComparator {
+    /// First input `Port`
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Second input `Port`
+    #[port(input)]
+    pub b: Port<LogicVector, Input>,
+    /// Output `Port`, high if `a` equals `b`
+    #[port(output)]
+    pub eq: Port<Ieee1164, Output>,
+    width: usize,
+    _private: (),
+}
+
+impl EqualityComparator {
+    /// Creates an equality comparator whose inputs are `width` bits wide.
+    pub fn with_width(width: usize) -> Self {
+        Self {
+            a: Port::new(LogicVector::with_width(width)),
+            b: Port::new(LogicVector::with_width(width)),
+            eq: Port::default(),
+            width,
+            _private: (),
+        }
+    }
+}
+
+impl Updateable for EqualityComparator {
+    fn update(&mut self) -> bool {
+        let (a, b) = (self.a.value(), self.b.value());
+        let eq = if a.width() == self.width && b.width() == self.width {
+            equal(&a, &b)
+        } else {
+            Ieee1164::_X
+        };
+        drive(&mut self.eq, eq)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    fn lv(s: &str) -> LogicVector {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(_1, equal(&lv("1H0L"), &lv("1100")));
+        assert_eq!(_0, equal(&lv("1X00"), &lv("0X00")));
+        assert_eq!(_X, equal(&lv("1X00"), &lv("1100")));
+    }
+
+    #[test]
+    fn magnitude() {
+        let unsigned = Comparator::unsigned(4);
+        let signed = Comparator::signed(4);
+        let mut a = Port::<_, Output>::new(lv("1000"));
+        let mut b = Port::<_, Output>::new(lv("0111"));
+        let outputs: Vec<_> = (0..6).map(|_| Port::<_, Input>::default()).collect();
+
+        let mut circuit = circuit!(
+            signal!(a, unsigned.a, signed.a),
+            signal!(b, unsigned.b, signed.b),
+            signal!(unsigned.eq, outputs[0]),
+            signal!(unsigned.lt, outputs[1]),
+            signal!(unsigned.gt, outputs[2]),
+            signal!(signed.eq, outputs[3]),
+            signal!(signed.lt, outputs[4]),
+            signal!(signed.gt, outputs[5])
+        );
+        circuit.add(unsigned);
+        circuit.add(signed);
+        let values = |o: &[Port<Ieee1164, Input>]| o.iter().map(Port::value).collect::<Vec<_>>();
+
+        circuit.step().unwrap();
+        assert_eq!(vec![_0, _0, _1, _0, _1, _0], values(&outputs));
+
+        a.replace(lv("01X1"));
+        b.replace(lv("00XX"));
+        circuit.step().unwrap();
+        assert_eq!(vec![_0, _0, _1, _0, _0, _1], values(&outputs));
+
+        b.replace(lv("0X11"));
+        circuit.step().unwrap();
+        assert_eq!(vec![_X, _X, _X, _X, _X, _X], values(&outputs));
+
+        a.replace(lv("0111"));
+        b.replace(lv("0111"));
+        circuit.step().unwrap();
+        assert_eq!(vec![_1, _0, _0, _1, _0, _0], values(&outputs));
+    }
+}
@@ -0,0 +1,390 @@
+use super::{candidates, merge_all};
+use crate::direction::{Input, Output};
+use crate::models::{drive, select_width};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// A binary decoder, that sets the bit of [`Decoder::z`] whose index is the binary number on
+/// [`Decoder::a`], while all other bits are [`Ieee1164::_0`]. An `n` bit wide input results in a
+/// `2^n` bit wide output.
+///
+/// If [`Decoder::enable`] is driven low, all bits of the output are `0`. The enable input is
+/// active if it is left unconnected.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::combinational::Decoder;
+///
+/// let decoder = Decoder::with_width(2).unwrap();
+/// let a = Port::<_, Output>::new(LogicVector::from_int(2, 2).unwrap());
+/// let mut enable = Port::<_, Output>::new(Ieee1164::_1);
+/// let z = Port::<_, Input>::new(LogicVector::with_width(4));
+///
+/// let mut circuit = circuit!(signal!(a, decoder.a), signal!(enable, decoder.enable), signal!(decoder.z, z));
+/// circuit.add(decoder);
+///
+/// circuit.step().unwrap();
+/// assert_eq!("0100", z.value().to_string());
+///
+/// enable.replace(Ieee1164::_0);
+/// circuit.step().unwrap();
+/// assert_eq!("0000", z.value().to_string());
+/// ```
+#[derive(Debug, Component)]
+pub struct Decoder {
+    /// Input `Port`, holds the index of the bit that is set
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Active-high enable `Port`, active by default
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+    /// Output `Port`, one bit for every value of the input
+    #[port(output)]
+    pub z: Port<LogicVector, Output>,
+    width: usize,
+    _private: (),
+}
+
+impl Decoder {
+    /// The widest input a decoder accepts, which limits the output to `2^16` bits.
+    pub const MAX_WIDTH: usize = 16;
+
+    /// Creates a decoder with an input that is `width` bits wide.
+    ///
+    /// Returns `None` if `width` is `0` or larger than [`Decoder::MAX_WIDTH`].
+    pub fn with_width(width: usize) -> Option<Self> {
+        if width == 0 || width > Self::MAX_WIDTH {
+            return None;
+        }
+        Some(Self {
+            a: Port::new(LogicVector::with_width(width)),
+            enable: Port::new(Ieee1164::_1),
+            z: Port::new(LogicVector::with_width(1 << width)),
+            width,
+            _private: (),
+        })
+    }
+
+    fn calc(&self) -> LogicVector {
+        let count = 1 << self.width;
+        let a = self.a.value();
+        if a.width() != self.width {
+            return LogicVector::from_ieee(Ieee1164::_X, count);
+        }
+
+        let mut z = LogicVector::from_ieee(Ieee1164::_0, count);
+        let (selected, _) = candidates(&a, count);
+        let value = match self.enable.value().to_x01() {
+            Ieee1164::_0 => return z,
+            Ieee1164::_1 if selected.len() == 1 => Ieee1164::_1,
+            _ => Ieee1164::_X,
+        };
+        for i in selected {
+            z.set(i, value);
+        }
+        z
+    }
+}
+
+impl Updateable for Decoder {
+    fn update(&mut self) -> bool {
+        let value = self.calc();
+        drive(&mut self.z, value)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+/// A priority encoder, that outputs the index of the highest bit of [`PriorityEncoder::a`] that is
+/// set on [`PriorityEncoder::z`].
+///
+/// If no bit is set, [`PriorityEncoder::valid`] is [`Ieee1164::_0`] and the output is `0`,
+/// otherwise `valid` is [`Ieee1164::_1`]. Unknown input bits are treated as if they could be set
+/// or not, e.g. the input `0X10` results in the output `X` for the bits in which `1` and `2`
+/// differ.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::combinational::PriorityEncoder;
+///
+/// let encoder = PriorityEncoder::with_width(8).unwrap();
+/// let a = Port::<_, Output>::new(LogicVector::from_int(0b0010_0110, 8).unwrap());
+/// let z = Port::<_, Input>::new(LogicVector::with_width(3));
+/// let valid = Port::<_, Input>::default();
+///
+/// let mut circuit = circuit!(signal!(a, encoder.a), signal!(encoder.z, z), signal!(encoder.valid, valid));
+/// circuit.add(encoder);
+///
+/// circuit.step().unwrap();
+/// assert_eq!(Some(5), z.value().as_u128());
+/// assert_eq!(Ieee1164::_1, valid.value());
+/// ```
+#[derive(Debug, Component)]
+pub struct PriorityEncoder {
+    /// Input `Port`
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Output `Port`, holds the index of the highest bit of the input that is set
+    #[port(output)]
+    pub z: Port<LogicVector, Output>,
+    /// Output `Port`, high if any bit of the input is set
+    #[port(output)]
+    pub valid: Port<Ieee1164, Output>,
+    width: usize,
+    _private: (),
+}
+
+impl PriorityEncoder {
+    /// Creates a priority encoder with an input that is `width` bits wide. The output is as wide as
+    /// needed to hold the index of every input bit.
+    ///
+    /// Returns `None` if `width` is less than `2`.
+    pub fn with_width(width: usize) -> Option<Self> {
+        if width < 2 {
+            return None;
+        }
+        Some(Self {
+            a: Port::new(LogicVector::with_width(width)),
+            z: Port::new(LogicVector::with_width(select_width(width))),
+            valid: Port::default(),
+            width,
+            _private: (),
+        })
+    }
+
+    fn calc(&self) -> (LogicVector, Ieee1164) {
+        let z_width = select_width(self.width);
+        let a = self.a.value();
+        if a.width() != self.width {
+            return (LogicVector::from_ieee(Ieee1164::_X, z_width), Ieee1164::_X);
+        }
+
+        let mut outcomes = vec![];
+        let mut found = false;
+        for i in (0..self.width).rev() {
+            match a.get(i).map(Ieee1164::to_x01) {
+                Some(Ieee1164::_0) => continue,
+                Some(Ieee1164::_1) => found = true,
+                _ => {}
+            }
+            outcomes.push((
+                LogicVector::from_int(i as u128, z_width).unwrap(),
+                Ieee1164::_1,
+            ));
+            if found {
+                break;
+            }
+        }
+        if !found {
+            outcomes.push((LogicVector::from_ieee(Ieee1164::_0, z_width), Ieee1164::_0));
+        }
+
+        let z = merge_all(outcomes.iter().map(|o| o.0.clone()));
+        let valid = merge_all(outcomes.iter().map(|o| o.1));
+        (z.unwrap(), valid.unwrap())
+    }
+}
+
+impl Updateable for PriorityEncoder {
+    fn update(&mut self) -> bool {
+        let (z, valid) = self.calc();
+        let z_changed = drive(&mut self.z, z);
+        drive(&mut self.valid, valid) || z_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+/// The segments that are lit for the hexadecimal digits `0` to `F`, segment `a` is bit `0`.
+const SEGMENTS: [u128; 16] = [
+    0x3f, 0x06, 0x5b, 0x4f, 0x66, 0x6d, 0x7d, 0x07, 0x7f, 0x6f, 0x77, 0x7c, 0x39, 0x5e, 0x79, 0x71,
+];
+
+/// A decoder for a 7-segment display, that shows the hexadecimal digit on the 4 bit wide input
+/// [`SevenSegmentDecoder::a`].
+///
+/// The output [`SevenSegmentDecoder::segments`] is active-high, segment `a` is bit `0` and segment
+/// `g` is bit `6`. The digits `b` and `d` are shown in lower case, all others in upper case.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::combinational::SevenSegmentDecoder;
+///
+/// let decoder = SevenSegmentDecoder::default();
+/// let a = Port::<_, Output>::new(LogicVector::from_int(7, 4).unwrap());
+/// let segments = Port::<_, Input>::new(LogicVector::with_width(7));
+///
+/// let mut circuit = circuit!(signal!(a, decoder.a), signal!(decoder.segments, segments));
+/// circuit.add(decoder);
+///
+/// circuit.step().unwrap();
+/// assert_eq!("0000111", segments.value().to_string());
+/// ```
+#[derive(Debug, Component)]
+#[non_exhaustive]
+pub struct SevenSegmentDecoder {
+    /// Input `Port`, holds the digit that is shown
+    #[port(input)]
+    pub a: Port<LogicVector, Input>,
+    /// Output `Port`, one bit per segment
+    #[port(output)]
+    pub segments: Port<LogicVector, Output>,
+}
+
+impl Default for SevenSegmentDecoder {
+    fn default() -> Self {
+        Self {
+            a: Port::new(LogicVector::with_width(4)),
+            segments: Port::new(LogicVector::with_width(7)),
+        }
+    }
+}
+
+impl SevenSegmentDecoder {
+    fn calc(&self) -> LogicVector {
+        let a = self.a.value();
+        if a.width() != 4 {
+            return LogicVector::from_ieee(Ieee1164::_X, 7);
+        }
+        let (digits, _) = candidates(&a, 16);
+        merge_all(
+            digits
+                .into_iter()
+                .map(|d| LogicVector::from_int(SEGMENTS[d], 7).unwrap()),
+        )
+        .unwrap()
+    }
+}
+
+impl Updateable for SevenSegmentDecoder {
+    fn update(&mut self) -> bool {
+        let value = self.calc();
+        drive(&mut self.segments, value)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    fn lv(s: &str) -> LogicVector {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn decoder_x_propagation() {
+        assert!(Decoder::with_width(0).is_none());
+        assert!(Decoder::with_width(40).is_none());
+        assert!(Decoder::with_width(Decoder::MAX_WIDTH + 1).is_none());
+        let decoder = Decoder::with_width(2).unwrap();
+        let mut a = Port::<_, Output>::new(lv("X1"));
+        let mut enable = Port::<_, Output>::new(_1);
+        let z = Port::<_, Input>::new(LogicVector::with_width(4));
+
+        let mut circuit = circuit!(
+            signal!(a, decoder.a),
+            signal!(enable, decoder.enable),
+            signal!(decoder.z, z)
+        );
+        circuit.add(decoder);
+
+        circuit.step().unwrap();
+        assert_eq!(lv("X0X0"), z.value());
+
+        a.replace(lv("00"));
+        enable.replace(Ieee1164::_W);
+        circuit.step().unwrap();
+        assert_eq!(lv("000X"), z.value());
+
+        enable.replace(Ieee1164::_L);
+        circuit.step().unwrap();
+        assert_eq!(lv("0000"), z.value());
+    }
+
+    #[test]
+    fn priority_encoder() {
+        assert!(PriorityEncoder::with_width(1).is_none());
+        let encoder = PriorityEncoder::with_width(4).unwrap();
+        let mut a = Port::<_, Output>::new(lv("0000"));
+        let z = Port::<_, Input>::new(LogicVector::with_width(2));
+        let valid = Port::<_, Input>::default();
+
+        let mut circuit = circuit!(
+            signal!(a, encoder.a),
+            signal!(encoder.z, z),
+            signal!(encoder.valid, valid)
+        );
+        circuit.add(encoder);
+
+        circuit.step().unwrap();
+        assert_eq!((lv("00"), _0), (z.value(), valid.value()));
+
+        a.replace(lv("0X10"));
+        circuit.step().unwrap();
+        assert_eq!((lv("XX"), _1), (z.value(), valid.value()));
+
+        a.replace(lv("1X10"));
+        circuit.step().unwrap();
+        assert_eq!((lv("11"), _1), (z.value(), valid.value()));
+
+        a.replace(lv("00X0"));
+        circuit.step().unwrap();
+        assert_eq!((lv("0X"), _X), (z.value(), valid.value()));
+    }
+
+    #[test]
+    fn seven_segments() {
+        let decoder = SevenSegmentDecoder::default();
+        let mut a = Port::<_, Output>::new(lv("0000"));
+        let segments = Port::<_, Input>::new(LogicVector::with_width(7));
+
+        let mut circuit = circuit!(signal!(a, decoder.a), signal!(decoder.segments, segments));
+        circuit.add(decoder);
+
+        circuit.step().unwrap();
+        assert_eq!(lv("0111111"), segments.value());
+
+        a.replace(lv("1011"));
+        circuit.step().unwrap();
+        assert_eq!(lv("1111100"), segments.value());
+
+        // 8 and 9 only differ in segment e
+        a.replace(lv("100X"));
+        circuit.step().unwrap();
+        assert_eq!(lv("11X1111"), segments.value());
+    }
+}
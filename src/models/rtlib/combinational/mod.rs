@@ -0,0 +1,68 @@
+//! This module consists of combinational building blocks that work on
+//! [`LogicVector`](crate::LogicVector)s, e.g. multiplexers, decoders, encoders and comparators.
+//!
+//! If a select or address input is not fully known, every value it may stand for is taken into
+//! account: output bits that are the same for all of them keep their value, all other bits become
+//! [`Ieee1164::_X`]. E.g. a [`VectorMux`] whose select input is `0X` outputs the bits in which
+//! its inputs `0` and `1` agree and `X` for the others. If the width of an input does not match the
+//! width of the model, every bit of the outputs is `X`.
+
+mod compare;
+mod decoder;
+mod mux;
+
+pub use self::compare::{Comparator, EqualityComparator};
+pub use self::decoder::{Decoder, PriorityEncoder, SevenSegmentDecoder};
+pub use self::mux::{Demux, VectorMux};
+
+use crate::models::rtlib::sequential::Storable;
+use crate::{Ieee1164, LogicVector};
+
+/// Returns every index in `0..count` that `select` may stand for, because bits of `select` that
+/// are neither high nor low match both values. The second value is `true` if `select` may also
+/// stand for an index outside of `0..count`.
+///
+/// `select` must not be wider than `usize`.
+pub(crate) fn candidates(select: &LogicVector, count: usize) -> (Vec<usize>, bool) {
+    let (mut known, mut value) = (0usize, 0usize);
+    for i in 0..select.width() {
+        match select.get(i).map(Ieee1164::to_x01) {
+            Some(Ieee1164::_1) => {
+                known |= 1 << i;
+                value |= 1 << i;
+            }
+            Some(Ieee1164::_0) => known |= 1 << i,
+            _ => {}
+        }
+    }
+    let all = usize::MAX >> (usize::BITS as usize - select.width());
+    let highest = value | (all & !known);
+    let matching = (0..count.min(highest + 1))
+        .filter(|i| i & known == value)
+        .collect();
+    (matching, highest >= count)
+}
+
+/// Merges all `values`, see [`Storable::merge`], or returns `None` if there are no values.
+pub(crate) fn merge_all<T: Storable>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().fold(None, |merged, v| match merged {
+        None => Some(v),
+        Some(m) => Some(m.merge(&v)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_of_uncertain_selects() {
+        let lv = |s: &str| s.parse::<LogicVector>().unwrap();
+        assert_eq!((vec![2], false), candidates(&lv("10"), 4));
+        assert_eq!((vec![1, 3], false), candidates(&lv("X1"), 4));
+        assert_eq!((vec![0, 2], false), candidates(&lv("Z0"), 3));
+        assert_eq!((vec![0], true), candidates(&lv("Z0"), 2));
+        assert_eq!((vec![], true), candidates(&lv("11"), 3));
+        assert_eq!((vec![0, 1, 2, 3], false), candidates(&lv("UW"), 4));
+    }
+}
@@ -0,0 +1,299 @@
+use super::{candidates, merge_all};
+use crate::component::PortInfo;
+use crate::direction::{Input, Output};
+use crate::models::rtlib::sequential::Storable;
+use crate::models::{checked, drive, select_width};
+use crate::{Component, Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// A multiplexer that selects one of its [`LogicVector`] inputs [`VectorMux::d`] by the binary
+/// number on [`VectorMux::select`] and outputs it on [`VectorMux::z`].
+///
+/// The select input is as wide as needed to address all inputs. If it addresses an input that
+/// does not exist, the output is [`Ieee1164::_X`]. The inputs are listed as `d0`, `d1`, ... by
+/// [`Component::ports`].
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::combinational::VectorMux;
+///
+/// let mux = VectorMux::new(3, 4).unwrap();
+/// let d0 = Port::<_, Output>::new(LogicVector::from_int(0b0011, 4).unwrap());
+/// let d1 = Port::<_, Output>::new(LogicVector::from_int(0b0101, 4).unwrap());
+/// let d2 = Port::<_, Output>::new(LogicVector::from_int(0b1111, 4).unwrap());
+/// let mut select = Port::<_, Output>::new("01".parse().unwrap());
+/// let z = Port::<_, Input>::new(LogicVector::with_width(4));
+///
+/// let mut circuit = circuit!(
+///     signal!(d0, mux.d[0]),
+///     signal!(d1, mux.d[1]),
+///     signal!(d2, mux.d[2]),
+///     signal!(select, mux.select),
+///     signal!(mux.z, z)
+/// );
+/// circuit.add(mux);
+///
+/// circuit.step().unwrap();
+/// assert_eq!(Some(0b0101), z.value().as_u128());
+///
+/// select.replace("0X".parse().unwrap());
+/// circuit.step().unwrap();
+/// assert_eq!("0XX1", z.value().to_string());
+/// ```
+#[derive(Debug)]
+pub struct VectorMux {
+    /// Data input `Port`s
+    pub d: Vec<Port<LogicVector, Input>>,
+    /// Select input `Port`, determines which data input is outputted
+    pub select: Port<LogicVector, Input>,
+    /// Output `Port`
+    pub z: Port<LogicVector, Output>,
+    width: usize,
+    _private: (),
+}
+
+impl VectorMux {
+    /// Creates a multiplexer with `inputs` data inputs that are `width` bits wide.
+    ///
+    /// Returns `None` if there are less than two inputs or `width` is `0`.
+    pub fn new(inputs: usize, width: usize) -> Option<Self> {
+        if inputs < 2 || width == 0 {
+            return None;
+        }
+        Some(Self {
+            d: (0..inputs)
+                .map(|_| Port::new(LogicVector::with_width(width)))
+                .collect(),
+            select: Port::new(LogicVector::with_width(select_width(inputs))),
+            z: Port::new(LogicVector::with_width(width)),
+            width,
+            _private: (),
+        })
+    }
+
+    fn calc(&self) -> LogicVector {
+        let unknown = LogicVector::from_ieee(Ieee1164::_X, self.width);
+        let select = self.select.value();
+        if select.width() != select_width(self.d.len()) {
+            return unknown;
+        }
+        match candidates(&select, self.d.len()) {
+            (_, true) => unknown,
+            (inputs, false) => merge_all(
+                inputs
+                    .into_iter()
+                    .map(|i| checked(self.d[i].value(), self.width)),
+            )
+            .unwrap_or(unknown),
+        }
+    }
+}
+
+impl Component for VectorMux {
+    fn ports(&self) -> Vec<PortInfo> {
+        let mut ports: Vec<_> = self
+            .d
+            .iter()
+            .enumerate()
+            .map(|(i, d)| PortInfo::new(&format!("d{}", i), d))
+            .collect();
+        ports.push(PortInfo::new("select", &self.select));
+        ports.push(PortInfo::new("z", &self.z));
+        ports
+    }
+}
+
+impl Updateable for VectorMux {
+    fn update(&mut self) -> bool {
+        let value = self.calc();
+        drive(&mut self.z, value)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        let mut ids: Vec<_> = self.d.iter().map(Port::id).collect();
+        ids.push(self.select.id());
+        ids
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        vec![self.z.id()]
+    }
+}
+
+/// A demultiplexer that outputs [`Demux::a`] on the output [`Demux::z`] that is selected by the
+/// binary number on [`Demux::select`]. All other outputs are [`Ieee1164::_0`].
+///
+/// The select input is as wide as needed to address all outputs. If it addresses an output that
+/// does not exist, all outputs are `0`. The outputs are listed as `z0`, `z1`, ... by
+/// [`Component::ports`].
+#[derive(Debug)]
+pub struct Demux {
+    /// Data input `Port`
+    pub a: Port<LogicVector, Input>,
+    /// Select input `Port`, determines which output is driven with the data input
+    pub select: Port<LogicVector, Input>,
+    /// Output `Port`s
+    pub z: Vec<Port<LogicVector, Output>>,
+    width: usize,
+    _private: (),
+}
+
+impl Demux {
+    /// Creates a demultiplexer with `outputs` outputs that are `width` bits wide.
+    ///
+    /// Returns `None` if there are less than two outputs or `width` is `0`.
+    pub fn new(outputs: usize, width: usize) -> Option<Self> {
+        if outputs < 2 || width == 0 {
+            return None;
+        }
+        Some(Self {
+            a: Port::new(LogicVector::with_width(width)),
+            select: Port::new(LogicVector::with_width(select_width(outputs))),
+            z: (0..outputs)
+                .map(|_| Port::new(LogicVector::with_width(width)))
+                .collect(),
+            width,
+            _private: (),
+        })
+    }
+
+    fn calc(&self) -> Vec<LogicVector> {
+        let zero = LogicVector::from_ieee(Ieee1164::_0, self.width);
+        let a = checked(self.a.value(), self.width);
+        let select = self.select.value();
+        let (selected, maybe_none) = if select.width() == select_width(self.z.len()) {
+            candidates(&select, self.z.len())
+        } else {
+            ((0..self.z.len()).collect(), true)
+        };
+
+        let mut values = vec![zero.clone(); self.z.len()];
+        if let [i] = selected[..] {
+            if !maybe_none {
+                values[i] = a;
+                return values;
+            }
+        }
+        for i in selected {
+            values[i] = zero.merge(&a);
+        }
+        values
+    }
+}
+
+impl Component for Demux {
+    fn ports(&self) -> Vec<PortInfo> {
+        let mut ports = vec![
+            PortInfo::new("a", &self.a),
+            PortInfo::new("select", &self.select),
+        ];
+        ports.extend(
+            self.z
+                .iter()
+                .enumerate()
+                .map(|(i, z)| PortInfo::new(&format!("z{}", i), z)),
+        );
+        ports
+    }
+}
+
+impl Updateable for Demux {
+    fn update(&mut self) -> bool {
+        let values = self.calc();
+        self.z
+            .iter_mut()
+            .zip(values)
+            .fold(false, |changed, (z, value)| drive(z, value) || changed)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        vec![self.a.id(), self.select.id()]
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.z.iter().map(Port::id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    fn lv(s: &str) -> LogicVector {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn invalid_sizes() {
+        assert!(VectorMux::new(1, 8).is_none());
+        assert!(VectorMux::new(2, 0).is_none());
+        assert!(Demux::new(1, 8).is_none());
+        assert_eq!(
+            3,
+            VectorMux::new(5, 1).unwrap().ports()[5].port_type().width()
+        );
+    }
+
+    #[test]
+    fn mux_out_of_range_and_data_x() {
+        let mux = VectorMux::new(3, 2).unwrap();
+        let d0 = Port::<_, Output>::new(lv("1X"));
+        let d1 = Port::<_, Output>::new(lv("10"));
+        let d2 = Port::<_, Output>::new(lv("00"));
+        let mut select = Port::<_, Output>::new(lv("00"));
+        let z = Port::<_, Input>::new(LogicVector::with_width(2));
+
+        let mut circuit = circuit!(
+            signal!(d0, mux.d[0]),
+            signal!(d1, mux.d[1]),
+            signal!(d2, mux.d[2]),
+            signal!(select, mux.select),
+            signal!(mux.z, z)
+        );
+        circuit.add(mux);
+
+        circuit.step().unwrap();
+        assert_eq!(lv("1X"), z.value());
+
+        select.replace(lv("X0"));
+        circuit.step().unwrap();
+        assert_eq!(lv("XX"), z.value());
+
+        select.replace(lv("0X"));
+        circuit.step().unwrap();
+        assert_eq!(lv("1X"), z.value());
+
+        select.replace(lv("11"));
+        circuit.step().unwrap();
+        assert_eq!(lv("XX"), z.value());
+    }
+
+    #[test]
+    fn demux() {
+        let demux = Demux::new(4, 2).unwrap();
+        let a = Port::<_, Output>::new(lv("10"));
+        let mut select = Port::<_, Output>::new(lv("10"));
+        let z: Vec<_> = (0..4)
+            .map(|_| Port::<_, Input>::new(LogicVector::with_width(2)))
+            .collect();
+
+        let mut circuit = circuit!(signal!(a, demux.a), signal!(select, demux.select));
+        for (z, out) in z.iter().zip(&demux.z) {
+            circuit.add(signal!(*out, *z));
+        }
+        circuit.add(demux);
+        let values = |z: &[Port<LogicVector, Input>]| {
+            z.iter().map(|z| z.value().to_string()).collect::<Vec<_>>()
+        };
+
+        circuit.step().unwrap();
+        assert_eq!(vec!["00", "00", "10", "00"], values(&z));
+
+        select.replace(lv("X1"));
+        circuit.step().unwrap();
+        assert_eq!(vec!["00", "X0", "00", "X0"], values(&z));
+    }
+}
@@ -1,17 +1,19 @@
 use crate::direction::Output;
+use crate::Component;
 use crate::{LogicVector, Port};
 
 /// This struct can be used as a user-defined input, e.g. in a graphical environment.
-#[derive(Debug)]
+#[derive(Debug, Component)]
 pub struct VectorInput {
     /// The output port
+    #[port(output)]
     pub port: Port<LogicVector, Output>,
     _private: (),
 }
 
 impl VectorInput {
     /// Create this struct with a defines width for the inner [`LogicVector`]
-    pub fn with_width(width: u8) -> Self {
+    pub fn with_width(width: usize) -> Self {
         Self {
             port: Port::new(LogicVector::with_width(width)),
             _private: (),
@@ -0,0 +1,695 @@
+use super::{new_memory, MAX_ADDR_WIDTH};
+use crate::direction::{Input, Output};
+use crate::models::rtlib::sequential::Edge;
+use crate::models::{checked, drive};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// Converts a binary number into gray code, where consecutive numbers differ in a single bit.
+fn gray(binary: usize) -> usize {
+    binary ^ (binary >> 1)
+}
+
+fn flag(value: bool) -> Ieee1164 {
+    if value {
+        Ieee1164::_1
+    } else {
+        Ieee1164::_0
+    }
+}
+
+/// A synchronous first-in, first-out memory, where both sides share one clock.
+///
+/// On a rising edge of [`SyncFifo::clk`] the word on [`SyncFifo::data_in`] is appended if
+/// [`SyncFifo::write_enable`] is high and the oldest word is removed if [`SyncFifo::read_enable`]
+/// is high. The oldest word is always outputted on [`SyncFifo::data_out`] (first-word
+/// fall-through), so it can be used before it is removed. A write to a full FIFO and a read from
+/// an empty FIFO are ignored, both are decided on the fill level before the clock edge, i.e. a full
+/// FIFO ignores a write even if a word is read at the same time.
+///
+/// [`SyncFifo::full`], [`SyncFifo::empty`] and [`SyncFifo::almost_full`] signal the fill level,
+/// which is also outputted on [`SyncFifo::level`]. `almost_full` is high as soon as the level
+/// reaches the threshold given to [`SyncFifo::new`].
+///
+/// If it is unknown whether a word is written or read, e.g. because of an uncertain clock edge or
+/// an enable that is [`Ieee1164::_X`], the fill level becomes unknown and all outputs become `X`,
+/// until the FIFO is reset. Writes and reads that would be ignored anyway do not matter.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::memory::SyncFifo;
+///
+/// let fifo = SyncFifo::new(4, 8, 3).unwrap();
+/// let mut data_in = Port::<_, Output>::new(LogicVector::from_int(1, 8).unwrap());
+/// let write_enable = Port::<_, Output>::new(Ieee1164::_1);
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let mut reset = Port::<_, Output>::new(Ieee1164::_1);
+/// let data_out = Port::<_, Input>::new(LogicVector::with_width(8));
+/// let almost_full = Port::<_, Input>::default();
+///
+/// let mut circuit = circuit!(
+///     signal!(data_in, fifo.data_in),
+///     signal!(write_enable, fifo.write_enable),
+///     signal!(clk, fifo.clk),
+///     signal!(reset, fifo.reset),
+///     signal!(fifo.data_out, data_out),
+///     signal!(fifo.almost_full, almost_full)
+/// );
+/// circuit.add(fifo);
+///
+/// circuit.step().unwrap();
+/// reset.replace(Ieee1164::_0);
+/// for i in 1..=3 {
+///     data_in.replace(LogicVector::from_int(i, 8).unwrap());
+///     clk.replace(Ieee1164::_1);
+///     circuit.step().unwrap();
+///     clk.replace(Ieee1164::_0);
+///     circuit.step().unwrap();
+/// }
+/// assert_eq!(Some(1), data_out.value().as_u128());
+/// assert_eq!(Ieee1164::_1, almost_full.value());
+/// ```
+#[derive(Debug, Component)]
+pub struct SyncFifo {
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, empties the FIFO, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Active-high write enable `Port`, inactive by default
+    #[port(input)]
+    pub write_enable: Port<Ieee1164, Input>,
+    /// Data port which contains the word that is written.
+    #[port(input)]
+    pub data_in: Port<LogicVector, Input>,
+    /// Active-high read enable `Port`, inactive by default
+    #[port(input)]
+    pub read_enable: Port<Ieee1164, Input>,
+    /// Data port which contains the oldest word, or [`Ieee1164::_X`] if the FIFO is empty.
+    #[port(output)]
+    pub data_out: Port<LogicVector, Output>,
+    /// Output `Port`, high if no more words can be written
+    #[port(output)]
+    pub full: Port<Ieee1164, Output>,
+    /// Output `Port`, high if there is no word to read
+    #[port(output)]
+    pub empty: Port<Ieee1164, Output>,
+    /// Output `Port`, high if the fill level reached the almost full threshold
+    #[port(output)]
+    pub almost_full: Port<Ieee1164, Output>,
+    /// Output `Port` with the number of stored words
+    #[port(output)]
+    pub level: Port<LogicVector, Output>,
+    memory: Vec<LogicVector>,
+    head: usize,
+    count: Option<usize>,
+    almost_full_level: usize,
+    level_width: usize,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl SyncFifo {
+    /// Creates a `SyncFifo` that stores up to `depth` words of `data_width` bits. `almost_full` is
+    /// the fill level at which [`SyncFifo::almost_full`] gets high.
+    ///
+    /// Returns `None` if `depth` or `data_width` is `0`, if `depth` is larger than
+    /// `2^`[`MAX_ADDR_WIDTH`] or if `almost_full` is not in `1..=depth`.
+    pub fn new(depth: usize, data_width: usize, almost_full: usize) -> Option<Self> {
+        if depth == 0 || depth > 1 << MAX_ADDR_WIDTH {
+            return None;
+        }
+        if data_width == 0 || almost_full == 0 || almost_full > depth {
+            return None;
+        }
+        let level_width = (usize::BITS - depth.leading_zeros()) as usize;
+        Some(Self {
+            clk: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            write_enable: Port::new(Ieee1164::_0),
+            data_in: Port::new(LogicVector::with_width(data_width)),
+            read_enable: Port::new(Ieee1164::_0),
+            data_out: Port::new(LogicVector::with_width(data_width)),
+            full: Port::default(),
+            empty: Port::default(),
+            almost_full: Port::default(),
+            level: Port::new(LogicVector::with_width(level_width)),
+            memory: vec![LogicVector::with_width(data_width); depth],
+            head: 0,
+            count: None,
+            almost_full_level: almost_full,
+            level_width,
+            last_clk: Ieee1164::_U,
+            _private: (),
+        })
+    }
+
+    /// Returns the maximum number of words this FIFO can store.
+    pub fn depth(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// Returns the fill level at which [`SyncFifo::almost_full`] gets high.
+    pub fn almost_full_level(&self) -> usize {
+        self.almost_full_level
+    }
+
+    /// Returns the new fill level after a clock edge, or `None` if it is unknown.
+    fn clock(&mut self, edge: Edge, count: usize) -> Option<usize> {
+        let depth = self.depth();
+        let write = if count < depth {
+            self.write_enable.value().to_x01()
+        } else {
+            Ieee1164::_0
+        };
+        let read = if count > 0 {
+            self.read_enable.value().to_x01()
+        } else {
+            Ieee1164::_0
+        };
+        if write == Ieee1164::_0 && read == Ieee1164::_0 {
+            return Some(count);
+        }
+        if edge == Edge::Uncertain || write.is_X() || read.is_X() {
+            return None;
+        }
+
+        let mut count = count;
+        if write == Ieee1164::_1 {
+            let width = self.memory[0].width();
+            self.memory[(self.head + count) % depth] = checked(self.data_in.value(), width);
+            count += 1;
+        }
+        if read == Ieee1164::_1 {
+            self.head = (self.head + 1) % depth;
+            count -= 1;
+        }
+        Some(count)
+    }
+}
+
+impl Updateable for SyncFifo {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        self.count = match (self.reset.value().to_x01(), edge, self.count) {
+            (Ieee1164::_1, _, _) => {
+                self.head = 0;
+                Some(0)
+            }
+            (Ieee1164::_X, _, _) => None,
+            (_, Edge::None, count) | (_, _, count @ None) => count,
+            (_, edge, Some(count)) => self.clock(edge, count),
+        };
+
+        let width = self.memory[0].width();
+        let (data, full, empty, almost_full, level) = match self.count {
+            Some(count) => (
+                if count > 0 {
+                    self.memory[self.head].clone()
+                } else {
+                    LogicVector::from_ieee(Ieee1164::_X, width)
+                },
+                flag(count == self.depth()),
+                flag(count == 0),
+                flag(count >= self.almost_full_level),
+                LogicVector::from_int(count as u128, self.level_width).unwrap(),
+            ),
+            None => (
+                LogicVector::from_ieee(Ieee1164::_X, width),
+                Ieee1164::_X,
+                Ieee1164::_X,
+                Ieee1164::_X,
+                LogicVector::from_ieee(Ieee1164::_X, self.level_width),
+            ),
+        };
+        let data_changed = drive(&mut self.data_out, data);
+        let full_changed = drive(&mut self.full, full);
+        let empty_changed = drive(&mut self.empty, empty);
+        let almost_full_changed = drive(&mut self.almost_full, almost_full);
+        drive(&mut self.level, level)
+            || data_changed
+            || full_changed
+            || empty_changed
+            || almost_full_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.reset.id()]
+    }
+}
+
+/// A first-in, first-out memory with separate clocks for writing and reading, which transfers
+/// words between two clock domains.
+///
+/// The FIFO stores up to `2^addr_width` words. Each side has its own pointer, which is passed to
+/// the other clock domain in gray code through a synchronizer of two registers. Because only one
+/// bit of a gray code pointer changes at a time, the synchronized pointer is never garbled, but
+/// it lags behind: [`AsyncFifo::empty`] gets low two rising edges of [`AsyncFifo::read_clk`] after
+/// a word has been written, and [`AsyncFifo::full`] gets low two rising edges of
+/// [`AsyncFifo::write_clk`] after a word has been read. The flags are therefore pessimistic, but
+/// never wrong.
+///
+/// Otherwise the FIFO behaves like the [`SyncFifo`]: on a rising edge of `write_clk` the word on
+/// [`AsyncFifo::data_in`] is appended if [`AsyncFifo::write_enable`] is high and the FIFO is not
+/// full, on a rising edge of `read_clk` the oldest word is removed if [`AsyncFifo::read_enable`] is
+/// high and the FIFO is not empty. The oldest word is always outputted on
+/// [`AsyncFifo::data_out`].
+///
+/// Uncertain clock edges corrupt the synchronizers, so they make the state unknown and all outputs
+/// [`Ieee1164::_X`] until the FIFO is reset. The same happens if an enable is `X` while a write or
+/// read is possible.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::memory::AsyncFifo;
+///
+/// let fifo = AsyncFifo::new(2, 8).unwrap();
+/// let data_in = Port::<_, Output>::new(LogicVector::from_int(42, 8).unwrap());
+/// let mut write_enable = Port::<_, Output>::new(Ieee1164::_1);
+/// let mut write_clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let mut read_clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let mut reset = Port::<_, Output>::new(Ieee1164::_1);
+/// let data_out = Port::<_, Input>::new(LogicVector::with_width(8));
+/// let empty = Port::<_, Input>::default();
+///
+/// let mut circuit = circuit!(
+///     signal!(data_in, fifo.data_in),
+///     signal!(write_enable, fifo.write_enable),
+///     signal!(write_clk, fifo.write_clk),
+///     signal!(read_clk, fifo.read_clk),
+///     signal!(reset, fifo.reset),
+///     signal!(fifo.data_out, data_out),
+///     signal!(fifo.empty, empty)
+/// );
+/// circuit.add(fifo);
+///
+/// circuit.step().unwrap();
+/// reset.replace(Ieee1164::_0);
+/// write_clk.replace(Ieee1164::_1);
+/// circuit.step().unwrap();
+/// write_enable.replace(Ieee1164::_0);
+///
+/// // the write pointer needs two read clock cycles to pass the synchronizer
+/// for _ in 0..2 {
+///     assert_eq!(Ieee1164::_1, empty.value());
+///     read_clk.replace(Ieee1164::_1);
+///     circuit.step().unwrap();
+///     read_clk.replace(Ieee1164::_0);
+///     circuit.step().unwrap();
+/// }
+/// assert_eq!(Ieee1164::_0, empty.value());
+/// assert_eq!(Some(42), data_out.value().as_u128());
+/// ```
+#[derive(Debug, Component)]
+pub struct AsyncFifo {
+    /// Active-high asynchronous reset `Port` of both clock domains, empties the FIFO, inactive by
+    /// default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Clock input `Port` of the write side
+    #[port(input)]
+    pub write_clk: Port<Ieee1164, Input>,
+    /// Active-high write enable `Port`, inactive by default
+    #[port(input)]
+    pub write_enable: Port<Ieee1164, Input>,
+    /// Data port which contains the word that is written.
+    #[port(input)]
+    pub data_in: Port<LogicVector, Input>,
+    /// Output `Port` of the write side, high if no more words can be written
+    #[port(output)]
+    pub full: Port<Ieee1164, Output>,
+    /// Clock input `Port` of the read side
+    #[port(input)]
+    pub read_clk: Port<Ieee1164, Input>,
+    /// Active-high read enable `Port`, inactive by default
+    #[port(input)]
+    pub read_enable: Port<Ieee1164, Input>,
+    /// Data port which contains the oldest word, or [`Ieee1164::_X`] if the FIFO is empty.
+    #[port(output)]
+    pub data_out: Port<LogicVector, Output>,
+    /// Output `Port` of the read side, high if there is no word to read
+    #[port(output)]
+    pub empty: Port<Ieee1164, Output>,
+    memory: Vec<LogicVector>,
+    addr_width: usize,
+    pointers: Option<Pointers>,
+    last_write_clk: Ieee1164,
+    last_read_clk: Ieee1164,
+    _private: (),
+}
+
+/// The pointers of an [`AsyncFifo`]. Both pointers have one bit more than the address, so a full
+/// FIFO can be told apart from an empty one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Pointers {
+    write: usize,
+    read: usize,
+    /// The gray coded read pointer, synchronized to the write clock
+    read_sync: [usize; 2],
+    /// The gray coded write pointer, synchronized to the read clock
+    write_sync: [usize; 2],
+}
+
+impl AsyncFifo {
+    /// Creates an `AsyncFifo` that stores up to `2^addr_width` words of `data_width` bits.
+    ///
+    /// Returns `None` if one of the widths is `0` or `addr_width` is larger than
+    /// [`MAX_ADDR_WIDTH`].
+    pub fn new(addr_width: usize, data_width: usize) -> Option<Self> {
+        let memory = new_memory(addr_width, data_width)?;
+        Some(Self {
+            reset: Port::new(Ieee1164::_0),
+            write_clk: Port::default(),
+            write_enable: Port::new(Ieee1164::_0),
+            data_in: Port::new(LogicVector::with_width(data_width)),
+            full: Port::default(),
+            read_clk: Port::default(),
+            read_enable: Port::new(Ieee1164::_0),
+            data_out: Port::new(LogicVector::with_width(data_width)),
+            empty: Port::default(),
+            memory,
+            addr_width,
+            pointers: None,
+            last_write_clk: Ieee1164::_U,
+            last_read_clk: Ieee1164::_U,
+            _private: (),
+        })
+    }
+
+    /// Returns the maximum number of words this FIFO can store.
+    pub fn depth(&self) -> usize {
+        self.memory.len()
+    }
+
+    fn is_full(&self, p: &Pointers) -> bool {
+        // a full FIFO differs from an empty one in the two most significant bits of the gray code
+        gray(p.write) == p.read_sync[1] ^ (0b11 << (self.addr_width - 1))
+    }
+
+    fn is_empty(&self, p: &Pointers) -> bool {
+        gray(p.read) == p.write_sync[1]
+    }
+
+    /// Calculates the pointers after the clock edges `write_edge` and `read_edge`, or returns
+    /// `None` if they are unknown afterwards. Both sides see the state before the edges.
+    fn clock(&mut self, write_edge: Edge, read_edge: Edge, p: Pointers) -> Option<Pointers> {
+        let wrap = 2 << self.addr_width;
+        let mut next = p;
+
+        match write_edge {
+            Edge::None => {}
+            Edge::Uncertain => return None,
+            Edge::Rising => {
+                next.read_sync = [gray(p.read), p.read_sync[0]];
+                let write = if self.is_full(&p) {
+                    Ieee1164::_0
+                } else {
+                    self.write_enable.value().to_x01()
+                };
+                match write {
+                    Ieee1164::_1 => {
+                        let (index, width) = (p.write % self.depth(), self.memory[0].width());
+                        self.memory[index] = checked(self.data_in.value(), width);
+                        next.write = (p.write + 1) % wrap;
+                    }
+                    Ieee1164::_0 => {}
+                    _ => return None,
+                }
+            }
+        }
+
+        match read_edge {
+            Edge::None => {}
+            Edge::Uncertain => return None,
+            Edge::Rising => {
+                next.write_sync = [gray(p.write), p.write_sync[0]];
+                let read = if self.is_empty(&p) {
+                    Ieee1164::_0
+                } else {
+                    self.read_enable.value().to_x01()
+                };
+                match read {
+                    Ieee1164::_1 => next.read = (p.read + 1) % wrap,
+                    Ieee1164::_0 => {}
+                    _ => return None,
+                }
+            }
+        }
+        Some(next)
+    }
+}
+
+impl Updateable for AsyncFifo {
+    fn update(&mut self) -> bool {
+        let (write_clk, read_clk) = (self.write_clk.value(), self.read_clk.value());
+        let write_edge = Edge::detect(self.last_write_clk, write_clk);
+        let read_edge = Edge::detect(self.last_read_clk, read_clk);
+        self.last_write_clk = write_clk;
+        self.last_read_clk = read_clk;
+
+        self.pointers = match (self.reset.value().to_x01(), self.pointers) {
+            (Ieee1164::_1, _) => Some(Pointers::default()),
+            (Ieee1164::_X, _) | (_, None) => None,
+            (_, Some(p)) => self.clock(write_edge, read_edge, p),
+        };
+
+        let width = self.memory[0].width();
+        let (data, full, empty) = match self.pointers {
+            Some(p) => {
+                let empty = self.is_empty(&p);
+                let data = if empty {
+                    LogicVector::from_ieee(Ieee1164::_X, width)
+                } else {
+                    self.memory[p.read % self.depth()].clone()
+                };
+                (data, flag(self.is_full(&p)), flag(empty))
+            }
+            None => (
+                LogicVector::from_ieee(Ieee1164::_X, width),
+                Ieee1164::_X,
+                Ieee1164::_X,
+            ),
+        };
+        let data_changed = drive(&mut self.data_out, data);
+        let full_changed = drive(&mut self.full, full);
+        drive(&mut self.empty, empty) || data_changed || full_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.reset.id(), self.write_clk.id(), self.read_clk.id()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    fn lv(value: u128) -> LogicVector {
+        LogicVector::from_int(value, 8).unwrap()
+    }
+
+    fn cycle(circuit: &mut Circuit, clk: &mut Port<Ieee1164, Output>) {
+        clk.replace(_1);
+        circuit.step().unwrap();
+        clk.replace(_0);
+        circuit.step().unwrap();
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert!(SyncFifo::new(0, 8, 1).is_none());
+        assert!(SyncFifo::new(4, 0, 1).is_none());
+        assert!(SyncFifo::new(4, 8, 0).is_none());
+        assert!(SyncFifo::new(4, 8, 5).is_none());
+        assert!(AsyncFifo::new(0, 8).is_none());
+        assert!(AsyncFifo::new(40, 8).is_none());
+        assert!(SyncFifo::new(usize::MAX, 8, 1).is_none());
+        assert_eq!(8, AsyncFifo::new(3, 8).unwrap().depth());
+    }
+
+    #[test]
+    fn sync_fifo() {
+        let fifo = SyncFifo::new(3, 8, 2).unwrap();
+        let mut clk = Port::<_, Output>::new(_0);
+        let mut reset = Port::<_, Output>::new(_1);
+        let mut write_enable = Port::<_, Output>::new(_1);
+        let mut data_in = Port::<_, Output>::new(lv(0));
+        let mut read_enable = Port::<_, Output>::new(_0);
+        let data_out = Port::<_, Input>::new(LogicVector::with_width(8));
+        let flags: Vec<_> = (0..3).map(|_| Port::<_, Input>::default()).collect();
+        let level = Port::<_, Input>::new(LogicVector::with_width(2));
+
+        let mut circuit = circuit!(
+            signal!(clk, fifo.clk),
+            signal!(reset, fifo.reset),
+            signal!(write_enable, fifo.write_enable),
+            signal!(data_in, fifo.data_in),
+            signal!(read_enable, fifo.read_enable),
+            signal!(fifo.data_out, data_out),
+            signal!(fifo.full, flags[0]),
+            signal!(fifo.empty, flags[1]),
+            signal!(fifo.almost_full, flags[2]),
+            signal!(fifo.level, level)
+        );
+        circuit.add(fifo);
+        // returns `(level, [full, empty, almost_full])`
+        let state = |flags: &[Port<Ieee1164, Input>], level: &Port<LogicVector, Input>| {
+            (
+                level.value().as_u128(),
+                [flags[0].value(), flags[1].value(), flags[2].value()],
+            )
+        };
+
+        circuit.step().unwrap();
+        assert_eq!((Some(0), [_0, _1, _0]), state(&flags, &level));
+        assert_eq!(LogicVector::from_ieee(_X, 8), data_out.value());
+        reset.replace(_0);
+
+        for i in 1..=4 {
+            data_in.replace(lv(i));
+            cycle(&mut circuit, &mut clk);
+        }
+        assert_eq!((Some(3), [_1, _0, _1]), state(&flags, &level));
+        assert_eq!(Some(1), data_out.value().as_u128());
+
+        // a full FIFO ignores the write, even if a word is read
+        read_enable.replace(_1);
+        cycle(&mut circuit, &mut clk);
+        assert_eq!((Some(2), [_0, _0, _1]), state(&flags, &level));
+        assert_eq!(Some(2), data_out.value().as_u128());
+
+        // reading and writing at the same time keeps the level
+        data_in.replace(lv(5));
+        cycle(&mut circuit, &mut clk);
+        assert_eq!((Some(2), [_0, _0, _1]), state(&flags, &level));
+
+        write_enable.replace(_0);
+        let mut words = vec![];
+        for _ in 0..3 {
+            words.push(data_out.value().as_u128());
+            cycle(&mut circuit, &mut clk);
+        }
+        assert_eq!(vec![Some(3), Some(5), None], words);
+        assert_eq!((Some(0), [_0, _1, _0]), state(&flags, &level));
+
+        // reading an empty FIFO does not matter, even if it is unknown whether to read
+        read_enable.replace(_X);
+        cycle(&mut circuit, &mut clk);
+        assert_eq!((Some(0), [_0, _1, _0]), state(&flags, &level));
+
+        write_enable.replace(_X);
+        cycle(&mut circuit, &mut clk);
+        assert_eq!((None, [_X, _X, _X]), state(&flags, &level));
+        assert_eq!("XX", level.value().to_string());
+
+        reset.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!((Some(0), [_0, _1, _0]), state(&flags, &level));
+    }
+
+    #[test]
+    fn async_fifo() {
+        let fifo = AsyncFifo::new(2, 8).unwrap();
+        let mut write_clk = Port::<_, Output>::new(_0);
+        let mut read_clk = Port::<_, Output>::new(_0);
+        let mut reset = Port::<_, Output>::new(_1);
+        let mut write_enable = Port::<_, Output>::new(_1);
+        let mut data_in = Port::<_, Output>::new(lv(0));
+        let mut read_enable = Port::<_, Output>::new(_0);
+        let data_out = Port::<_, Input>::new(LogicVector::with_width(8));
+        let full = Port::<_, Input>::default();
+        let empty = Port::<_, Input>::default();
+
+        let mut circuit = circuit!(
+            signal!(write_clk, fifo.write_clk),
+            signal!(read_clk, fifo.read_clk),
+            signal!(reset, fifo.reset),
+            signal!(write_enable, fifo.write_enable),
+            signal!(data_in, fifo.data_in),
+            signal!(read_enable, fifo.read_enable),
+            signal!(fifo.data_out, data_out),
+            signal!(fifo.full, full),
+            signal!(fifo.empty, empty)
+        );
+        circuit.add(fifo);
+        circuit.step().unwrap();
+        assert_eq!((_0, _1), (full.value(), empty.value()));
+        reset.replace(_0);
+
+        // the read clock is idle, so the written words are not visible yet
+        for i in 1..=5 {
+            data_in.replace(lv(i));
+            cycle(&mut circuit, &mut write_clk);
+        }
+        assert_eq!((_1, _1), (full.value(), empty.value()));
+
+        cycle(&mut circuit, &mut read_clk);
+        assert_eq!(_1, empty.value());
+        cycle(&mut circuit, &mut read_clk);
+        assert_eq!((_0, Some(1)), (empty.value(), data_out.value().as_u128()));
+
+        // both clocks rise at the same time, the write side still sees the FIFO as full
+        read_enable.replace(_1);
+        write_clk.replace(_1);
+        read_clk.replace(_1);
+        circuit.step().unwrap();
+        write_clk.replace(_0);
+        read_clk.replace(_0);
+        circuit.step().unwrap();
+        assert_eq!((_1, Some(2)), (full.value(), data_out.value().as_u128()));
+        read_enable.replace(_0);
+
+        // the freed location is visible to the write side after two of its clock edges
+        write_enable.replace(_0);
+        cycle(&mut circuit, &mut write_clk);
+        assert_eq!(_1, full.value());
+        cycle(&mut circuit, &mut write_clk);
+        assert_eq!(_0, full.value());
+
+        read_enable.replace(_1);
+        let mut words = vec![];
+        while empty.value() == _0 {
+            words.push(data_out.value().as_u128().unwrap());
+            cycle(&mut circuit, &mut read_clk);
+        }
+        assert_eq!(vec![2, 3, 4], words);
+
+        read_clk.replace(_X);
+        circuit.step().unwrap();
+        assert_eq!((_X, _X), (full.value(), empty.value()));
+        reset.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!((_0, _1), (full.value(), empty.value()));
+    }
+}
@@ -0,0 +1,586 @@
+//! Loaders and savers for memory images, e.g. Intel HEX files of a firmware.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use super::{Ram, RegisterFile, Rom, SyncRam};
+use crate::{Ieee1164, LogicVector};
+
+/// The file formats a memory image can be loaded from or saved to.
+///
+/// The byte oriented formats ([`ImageFormat::IntelHex`], [`ImageFormat::SRecord`] and
+/// [`ImageFormat::Binary`]) address bytes. Every word of a memory occupies its width divided by 8
+/// (rounded up) consecutive bytes in little endian order, so byte address `n` of an 8-bit memory
+/// is word `n`, but it is the upper half of word `n / 2` of a 16-bit memory, if `n` is odd.
+///
+/// The text formats of Verilog's `$readmemh` and `$readmemb` address whole words and can also
+/// contain metavalues, like `x` or `z`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Intel HEX records, e.g. `:0300300002337A1E`, with extended segment and linear addresses
+    IntelHex,
+    /// Motorola S-records, e.g. `S1130000285F245F2212226A000424290008237C2A`
+    SRecord,
+    /// The raw bytes, starting at address `0`
+    Binary,
+    /// Hexadecimal words, as read by `$readmemh` of Verilog
+    ReadMemH,
+    /// Binary words, as read by `$readmemb` of Verilog
+    ReadMemB,
+}
+
+/// Errors that can occur when loading or saving a memory image.
+#[derive(Debug)]
+pub enum ImageError {
+    /// Reading or writing the image failed.
+    Io(io::Error),
+    /// The record or word in the given line (starting at `1`) is malformed.
+    Malformed(usize),
+    /// The checksum of the record in the given line (starting at `1`) is wrong.
+    Checksum(usize),
+    /// The given address lies beyond the end of the memory. It is a byte address for the byte
+    /// oriented formats and a word address otherwise.
+    OutOfRange(u64),
+    /// The byte at the given address contains bits beyond the width of the memory word it belongs
+    /// to.
+    TooWide(u64),
+    /// The word at the given address contains a metavalue and can therefore not be saved as bytes.
+    Metavalue(usize),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageError::Io(e) => write!(f, "{}", e),
+            ImageError::Malformed(line) => write!(f, "malformed record in line {}", line),
+            ImageError::Checksum(line) => write!(f, "wrong checksum in line {}", line),
+            ImageError::OutOfRange(address) => write!(f, "address {:#x} is out of range", address),
+            ImageError::TooWide(address) => {
+                write!(f, "byte at {:#x} does not fit into the word", address)
+            }
+            ImageError::Metavalue(address) => {
+                write!(f, "word at {:#x} contains a metavalue", address)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImageError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImageError {
+    fn from(e: io::Error) -> Self {
+        ImageError::Io(e)
+    }
+}
+
+/// A model that stores its content as a list of equally wide [`LogicVector`]s, e.g. a
+/// [`Rom`] or a [`Ram`]. Its content can be loaded from and saved to memory images.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::models::rtlib::memory::{ImageFormat, Memory, Rom1kx8};
+///
+/// let hex = ":0400100001020304E2\n:00000001FF\n";
+/// let mut rom = Rom1kx8::default();
+/// rom.load_image(ImageFormat::IntelHex, hex.as_bytes()).unwrap();
+/// assert_eq!(Some(3), rom.memory[0x12].as_u128());
+///
+/// let mut readmemh = vec![];
+/// rom.save_image(ImageFormat::ReadMemH, &mut readmemh).unwrap();
+/// assert!(String::from_utf8(readmemh).unwrap().starts_with("00\n"));
+/// ```
+pub trait Memory {
+    /// Returns all words of this memory, the word at address `0` first.
+    fn words(&self) -> &[LogicVector];
+
+    /// Returns all words of this memory mutable. The width of the words must not be changed.
+    fn words_mut(&mut self) -> &mut [LogicVector];
+
+    /// Loads the image in `format` from `reader`. Only the words that are contained in the image
+    /// are changed, all other words keep their value.
+    ///
+    /// If an error occurs, the words that have been loaded so far keep their new value.
+    fn load_image<R: Read>(
+        &mut self,
+        format: ImageFormat,
+        mut reader: R,
+    ) -> Result<(), ImageError> {
+        let memory = self.words_mut();
+        match format {
+            ImageFormat::IntelHex => load_intel_hex(memory, &read_text(&mut reader)?),
+            ImageFormat::SRecord => load_srecord(memory, &read_text(&mut reader)?),
+            ImageFormat::Binary => {
+                let mut bytes = vec![];
+                reader.read_to_end(&mut bytes)?;
+                bytes
+                    .into_iter()
+                    .enumerate()
+                    .try_for_each(|(address, byte)| put_byte(memory, address as u64, byte))
+            }
+            ImageFormat::ReadMemH => load_readmem(memory, &read_text(&mut reader)?, 'h'),
+            ImageFormat::ReadMemB => load_readmem(memory, &read_text(&mut reader)?, 'b'),
+        }
+    }
+
+    /// Saves the whole content of this memory in `format` to `writer`.
+    ///
+    /// The byte oriented formats can not represent metavalues, so every word has to consist of
+    /// [`Ieee1164::_0`] and [`Ieee1164::_1`] (or their weak counterparts) only.
+    fn save_image<W: Write>(&self, format: ImageFormat, mut writer: W) -> Result<(), ImageError> {
+        let memory = self.words();
+        match format {
+            ImageFormat::IntelHex => save_intel_hex(&to_bytes(memory)?, &mut writer),
+            ImageFormat::SRecord => save_srecord(&to_bytes(memory)?, &mut writer),
+            ImageFormat::Binary => Ok(writer.write_all(&to_bytes(memory)?)?),
+            ImageFormat::ReadMemH => memory
+                .iter()
+                .try_for_each(|w| Ok(writeln!(writer, "{:x}", w)?)),
+            ImageFormat::ReadMemB => memory
+                .iter()
+                .try_for_each(|w| Ok(writeln!(writer, "{:b}", w)?)),
+        }
+    }
+}
+
+impl<const DEPTH: usize, const WIDTH: usize> Memory for Rom<DEPTH, WIDTH> {
+    fn words(&self) -> &[LogicVector] {
+        &self.memory
+    }
+
+    fn words_mut(&mut self) -> &mut [LogicVector] {
+        &mut self.memory
+    }
+}
+
+impl Memory for Ram {
+    fn words(&self) -> &[LogicVector] {
+        &self.memory
+    }
+
+    fn words_mut(&mut self) -> &mut [LogicVector] {
+        &mut self.memory
+    }
+}
+
+impl Memory for SyncRam {
+    fn words(&self) -> &[LogicVector] {
+        &self.memory
+    }
+
+    fn words_mut(&mut self) -> &mut [LogicVector] {
+        &mut self.memory
+    }
+}
+
+impl Memory for RegisterFile {
+    fn words(&self) -> &[LogicVector] {
+        &self.memory
+    }
+
+    fn words_mut(&mut self) -> &mut [LogicVector] {
+        &mut self.memory
+    }
+}
+
+fn read_text<R: Read>(reader: &mut R) -> Result<String, ImageError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+fn bytes_per_word(memory: &[LogicVector]) -> usize {
+    memory.first().map_or(1, |w| w.width().div_ceil(8))
+}
+
+/// Writes `byte` to the byte oriented `address`.
+fn put_byte(memory: &mut [LogicVector], address: u64, byte: u8) -> Result<(), ImageError> {
+    let bpw = bytes_per_word(memory);
+    let (index, offset) = usize::try_from(address)
+        .ok()
+        .map(|a| (a / bpw, a % bpw * 8))
+        .filter(|&(index, _)| index < memory.len())
+        .ok_or(ImageError::OutOfRange(address))?;
+    let word = &mut memory[index];
+    for i in 0..8 {
+        let bit = byte >> i & 1 == 1;
+        if offset + i < word.width() {
+            word.set(offset + i, if bit { Ieee1164::_1 } else { Ieee1164::_0 });
+        } else if bit {
+            return Err(ImageError::TooWide(address));
+        }
+    }
+    Ok(())
+}
+
+fn to_bytes(memory: &[LogicVector]) -> Result<Vec<u8>, ImageError> {
+    let mut bytes = Vec::with_capacity(memory.len() * bytes_per_word(memory));
+    for (address, word) in memory.iter().enumerate() {
+        bytes.extend(word.to_le_bytes().ok_or(ImageError::Metavalue(address))?);
+    }
+    Ok(bytes)
+}
+
+/// Decodes the hexadecimal digits of a record into bytes.
+fn decode_hex(digits: &str, line: usize) -> Result<Vec<u8>, ImageError> {
+    if !digits.len().is_multiple_of(2) || !digits.is_ascii() {
+        return Err(ImageError::Malformed(line));
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| ImageError::Malformed(line)))
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+/// Returns the big endian number in `bytes`.
+fn big_endian(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |acc, b| acc << 8 | u64::from(*b))
+}
+
+fn load_intel_hex(memory: &mut [LogicVector], text: &str) -> Result<(), ImageError> {
+    let mut base = 0;
+    for (line, record) in text.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+        if record.is_empty() {
+            continue;
+        }
+        let bytes = decode_hex(
+            record
+                .strip_prefix(':')
+                .ok_or(ImageError::Malformed(line))?,
+            line,
+        )?;
+        if bytes.len() < 5 || bytes.len() != usize::from(bytes[0]) + 5 {
+            return Err(ImageError::Malformed(line));
+        }
+        if bytes.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)) != 0 {
+            return Err(ImageError::Checksum(line));
+        }
+        let address = big_endian(&bytes[1..3]);
+        let data = &bytes[4..bytes.len() - 1];
+        match (bytes[3], data.len()) {
+            (0x00, _) => {
+                for (i, byte) in data.iter().enumerate() {
+                    put_byte(memory, base + address + i as u64, *byte)?;
+                }
+            }
+            (0x01, 0) => return Ok(()),
+            (0x02, 2) => base = big_endian(data) << 4,
+            (0x04, 2) => base = big_endian(data) << 16,
+            (0x03, 4) | (0x05, 4) => {}
+            _ => return Err(ImageError::Malformed(line)),
+        }
+    }
+    Ok(())
+}
+
+fn intel_hex_record<W: Write>(
+    writer: &mut W,
+    kind: u8,
+    address: u16,
+    data: &[u8],
+) -> Result<(), ImageError> {
+    let mut bytes = vec![data.len() as u8];
+    bytes.extend(&address.to_be_bytes());
+    bytes.push(kind);
+    bytes.extend(data);
+    let checksum = bytes.iter().fold(0u8, |acc, b| acc.wrapping_sub(*b));
+    writeln!(writer, ":{}{:02X}", encode_hex(&bytes), checksum)?;
+    Ok(())
+}
+
+fn save_intel_hex<W: Write>(bytes: &[u8], writer: &mut W) -> Result<(), ImageError> {
+    let mut upper = 0;
+    for (i, chunk) in bytes.chunks(16).enumerate() {
+        let address = i * 16;
+        if address >> 16 != upper {
+            upper = address >> 16;
+            if upper > 0xFFFF {
+                return Err(ImageError::OutOfRange(address as u64));
+            }
+            intel_hex_record(writer, 0x04, 0, &(upper as u16).to_be_bytes())?;
+        }
+        intel_hex_record(writer, 0x00, address as u16, chunk)?;
+    }
+    intel_hex_record(writer, 0x01, 0, &[])
+}
+
+fn load_srecord(memory: &mut [LogicVector], text: &str) -> Result<(), ImageError> {
+    for (line, record) in text.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+        if record.is_empty() {
+            continue;
+        }
+        let kind = record
+            .strip_prefix('S')
+            .and_then(|r| r.chars().next())
+            .ok_or(ImageError::Malformed(line))?;
+        let address_len = match kind {
+            '0' | '1' | '5' | '9' => 2,
+            '2' | '6' | '8' => 3,
+            '3' | '7' => 4,
+            _ => return Err(ImageError::Malformed(line)),
+        };
+        let bytes = decode_hex(&record[2..], line)?;
+        if bytes.len() < address_len + 2 || bytes.len() != usize::from(bytes[0]) + 1 {
+            return Err(ImageError::Malformed(line));
+        }
+        if bytes.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)) != 0xFF {
+            return Err(ImageError::Checksum(line));
+        }
+        let address = big_endian(&bytes[1..=address_len]);
+        match kind {
+            '1' | '2' | '3' => {
+                for (i, byte) in bytes[address_len + 1..bytes.len() - 1].iter().enumerate() {
+                    put_byte(memory, address + i as u64, *byte)?;
+                }
+            }
+            '7' | '8' | '9' => return Ok(()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn srecord<W: Write>(
+    writer: &mut W,
+    kind: u8,
+    address: &[u8],
+    data: &[u8],
+) -> Result<(), ImageError> {
+    let mut bytes = vec![(address.len() + data.len() + 1) as u8];
+    bytes.extend(address);
+    bytes.extend(data);
+    let checksum = !bytes.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+    writeln!(writer, "S{}{}{:02X}", kind, encode_hex(&bytes), checksum)?;
+    Ok(())
+}
+
+fn save_srecord<W: Write>(bytes: &[u8], writer: &mut W) -> Result<(), ImageError> {
+    let (kind, address_len) = match bytes.len() {
+        0..=0x1_0000 => (1, 2),
+        0x1_0001..=0x100_0000 => (2, 3),
+        0x100_0001..=0x1_0000_0000 => (3, 4),
+        len => return Err(ImageError::OutOfRange(len as u64 - 1)),
+    };
+    srecord(writer, 0, &[0, 0], &[])?;
+    for (i, chunk) in bytes.chunks(16).enumerate() {
+        let address = ((i * 16) as u32).to_be_bytes();
+        srecord(writer, kind, &address[4 - address_len..], chunk)?;
+    }
+    srecord(writer, 10 - kind, &vec![0; address_len], &[])
+}
+
+/// Splits the text of a `$readmem` file into its tokens together with their line numbers,
+/// skipping all comments.
+fn readmem_tokens(text: &str) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut in_comment = false;
+    for (line, mut rest) in text.lines().enumerate().map(|(i, l)| (i + 1, l)) {
+        loop {
+            if in_comment {
+                match rest.find("*/") {
+                    Some(end) => {
+                        rest = &rest[end + 2..];
+                        in_comment = false;
+                    }
+                    None => break,
+                }
+            }
+            let end = rest
+                .find("//")
+                .unwrap_or(rest.len())
+                .min(rest.find("/*").unwrap_or(rest.len()));
+            tokens.extend(rest[..end].split_whitespace().map(|t| (line, t)));
+            if rest[end..].starts_with("/*") {
+                rest = &rest[end + 2..];
+                in_comment = true;
+            } else {
+                break;
+            }
+        }
+    }
+    tokens
+}
+
+fn load_readmem(memory: &mut [LogicVector], text: &str, base: char) -> Result<(), ImageError> {
+    let mut address = 0u64;
+    for (line, token) in readmem_tokens(text) {
+        if let Some(digits) = token.strip_prefix('@') {
+            address = u64::from_str_radix(digits, 16).map_err(|_| ImageError::Malformed(line))?;
+            continue;
+        }
+        let word = usize::try_from(address)
+            .ok()
+            .and_then(|a| memory.get_mut(a))
+            .ok_or(ImageError::OutOfRange(address))?;
+        if token.contains('\'') {
+            return Err(ImageError::Malformed(line));
+        }
+        *word = format!("{}'{}{}", word.width(), base, token)
+            .parse()
+            .map_err(|_| ImageError::Malformed(line))?;
+        address += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::rtlib::memory::Rom1kx8;
+
+    fn load(memory: &mut impl Memory, format: ImageFormat, image: &str) -> Result<(), ImageError> {
+        memory.load_image(format, image.as_bytes())
+    }
+
+    fn save(memory: &impl Memory, format: ImageFormat) -> String {
+        let mut image = vec![];
+        memory.save_image(format, &mut image).unwrap();
+        String::from_utf8(image).unwrap()
+    }
+
+    #[test]
+    fn intel_hex() {
+        let image = ":10010000214601360121470136007EFE09D2190140\n\
+                     :100110002146017E17C20001FF5F16002148011928\n\
+                     :02000004000AF0\n\
+                     :0100000042BD\n\
+                     :00000001FF\n";
+        let mut ram = Ram::new(20, 8).unwrap();
+        load(&mut ram, ImageFormat::IntelHex, image).unwrap();
+        assert_eq!(Some(0x21), ram.memory[0x100].as_u128());
+        assert_eq!(Some(0x19), ram.memory[0x11F].as_u128());
+        assert_eq!(Some(0x42), ram.memory[0xA_0000].as_u128());
+        assert_eq!(LogicVector::with_width(8), ram.memory[0xFF]);
+
+        let mut rom = Rom1kx8::default();
+        assert!(matches!(
+            load(&mut rom, ImageFormat::IntelHex, image),
+            Err(ImageError::OutOfRange(0xA_0000))
+        ));
+        assert!(matches!(
+            load(&mut rom, ImageFormat::IntelHex, ":0100000042BE"),
+            Err(ImageError::Checksum(1))
+        ));
+        assert!(matches!(
+            load(&mut rom, ImageFormat::IntelHex, "\n0100000042BD"),
+            Err(ImageError::Malformed(2))
+        ));
+        assert!(matches!(
+            load(&mut rom, ImageFormat::IntelHex, ":0200000042BC"),
+            Err(ImageError::Malformed(1))
+        ));
+
+        rom.memory[0x3F0] = LogicVector::from_int(0x99, 8).unwrap();
+        let saved = save(&rom, ImageFormat::IntelHex);
+        assert!(saved.ends_with(":1003F0009900000000000000000000000000000064\n:00000001FF\n"));
+        let mut copy = Rom1kx8::try_from_iter(std::iter::repeat(0xFF)).unwrap();
+        load(&mut copy, ImageFormat::IntelHex, &saved).unwrap();
+        assert_eq!(rom.memory, copy.memory);
+    }
+
+    #[test]
+    fn srecord() {
+        let image = "S00F000068656C6C6F202020202000003C\n\
+                     S11F00007C0802A6900100049421FFF07C6C1B787C8C23783C6000003863000026\n\
+                     S5030001FB\n\
+                     S9030000FC\n";
+        let mut rom = Rom::<64, 16>::default();
+        load(&mut rom, ImageFormat::SRecord, image).unwrap();
+        assert_eq!(Some(0x087C), rom.memory[0].as_u128());
+        assert_eq!(Some(0x6338), rom.memory[12].as_u128());
+        assert!(matches!(
+            load(&mut rom, ImageFormat::SRecord, "S9030000FD"),
+            Err(ImageError::Checksum(1))
+        ));
+        assert!(matches!(
+            load(&mut rom, ImageFormat::SRecord, "S4030000FC"),
+            Err(ImageError::Malformed(1))
+        ));
+
+        let saved = save(&rom, ImageFormat::SRecord);
+        assert!(saved.starts_with("S0030000FC\nS11300007C0802A6900100049421FFF07C6C1B780C\n"));
+        assert!(saved.ends_with("S9030000FC\n"));
+        let mut copy = Rom::<64, 16>::default();
+        load(&mut copy, ImageFormat::SRecord, &saved).unwrap();
+        assert_eq!(rom.memory, copy.memory);
+    }
+
+    #[test]
+    fn binary() {
+        let mut rom = Rom::<4, 12>::default();
+        rom.load_image(ImageFormat::Binary, &[0x34, 0x02, 0xFF, 0x0F][..])
+            .unwrap();
+        assert_eq!(Some(0x234), rom.memory[0].as_u128());
+        assert_eq!(Some(0xFFF), rom.memory[1].as_u128());
+
+        let mut saved = vec![];
+        rom.save_image(ImageFormat::Binary, &mut saved).unwrap();
+        assert_eq!(vec![0x34, 0x02, 0xFF, 0x0F, 0, 0, 0, 0], saved);
+
+        assert!(matches!(
+            rom.load_image(ImageFormat::Binary, &[0, 0x10][..]),
+            Err(ImageError::TooWide(1))
+        ));
+        assert!(matches!(
+            rom.load_image(ImageFormat::Binary, &[0; 9][..]),
+            Err(ImageError::OutOfRange(8))
+        ));
+
+        let ram = Ram::new(1, 8).unwrap();
+        assert!(matches!(
+            ram.save_image(ImageFormat::Binary, vec![]),
+            Err(ImageError::Metavalue(0))
+        ));
+    }
+
+    #[test]
+    fn readmem() {
+        let image = "// header\n\
+                     dead be_ef /* a\n\
+                     comment */ 1x2z\n\
+                     @a xxxx // done\n";
+        let mut ram = SyncRam::new(4, 16).unwrap();
+        load(&mut ram, ImageFormat::ReadMemH, image).unwrap();
+        assert_eq!(Some(0xdead), ram.memory[0].as_u128());
+        assert_eq!(Some(0xbeef), ram.memory[1].as_u128());
+        assert_eq!("0001XXXX0010ZZZZ", ram.memory[2].to_string());
+        assert_eq!(LogicVector::with_width(16), ram.memory[3]);
+        assert_eq!(LogicVector::from_ieee(Ieee1164::_X, 16), ram.memory[10]);
+
+        assert!(matches!(
+            load(&mut ram, ImageFormat::ReadMemH, "\n\n1ffff"),
+            Err(ImageError::Malformed(3))
+        ));
+        assert!(matches!(
+            load(&mut ram, ImageFormat::ReadMemH, "@g"),
+            Err(ImageError::Malformed(1))
+        ));
+        assert!(matches!(
+            load(&mut ram, ImageFormat::ReadMemH, "@f 0 0"),
+            Err(ImageError::OutOfRange(16))
+        ));
+
+        let mut copy = SyncRam::new(4, 16).unwrap();
+        load(
+            &mut copy,
+            ImageFormat::ReadMemB,
+            &save(&ram, ImageFormat::ReadMemB),
+        )
+        .unwrap();
+        let expected: Vec<_> = ram.memory.iter().map(LogicVector::to_x01z).collect();
+        assert_eq!(expected, copy.memory);
+        assert!(save(&ram, ImageFormat::ReadMemH).starts_with("dead\nbeef\n1x2z\nxxxx\n"));
+    }
+}
@@ -1,5 +1,89 @@
-//! This module consists of memory related models, e.g. RAM or ROM.
+//! This module consists of memory related models, e.g. RAM, ROM, FIFOs or register files.
 
+mod fifo;
+mod image;
+mod ram;
+mod regfile;
 mod rom;
 
-pub use self::rom::Rom1kx8;
+pub use self::fifo::{AsyncFifo, SyncFifo};
+pub use self::image::{ImageError, ImageFormat, Memory};
+pub use self::ram::{Ram, SyncRam};
+pub use self::regfile::{ReadDuringWrite, ReadPort, RegisterFile, WritePort};
+pub use self::rom::{Rom, Rom1kx8, RomError};
+
+use std::convert::TryFrom;
+
+use crate::models::checked;
+use crate::models::rtlib::sequential::Storable;
+use crate::{Ieee1164, LogicVector};
+
+/// Describes whether an access to the memory takes place, depending on active-low control lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    No,
+    Yes,
+    Maybe,
+}
+
+impl Access {
+    fn of(active_low: &[Ieee1164]) -> Access {
+        if active_low.iter().any(|v| v.is_1H()) {
+            Access::No
+        } else if active_low.iter().all(|v| v.is_0L()) {
+            Access::Yes
+        } else {
+            Access::Maybe
+        }
+    }
+
+    fn uncertain(self) -> Access {
+        match self {
+            Access::Yes => Access::Maybe,
+            a => a,
+        }
+    }
+}
+
+/// The widest address the memory models accept, which limits a memory to `2^24` words.
+pub const MAX_ADDR_WIDTH: usize = 24;
+
+/// Creates the memory of a RAM, or returns `None` if one of the widths is invalid.
+fn new_memory(addr_width: usize, data_width: usize) -> Option<Vec<LogicVector>> {
+    if addr_width == 0 || data_width == 0 || addr_width > MAX_ADDR_WIDTH {
+        return None;
+    }
+    Some(vec![LogicVector::with_width(data_width); 1 << addr_width])
+}
+
+fn index(memory: &[LogicVector], addr: &LogicVector) -> Option<usize> {
+    addr.as_u128()
+        .and_then(|a| usize::try_from(a).ok())
+        .filter(|&a| a < memory.len())
+}
+
+fn read(memory: &[LogicVector], addr: &LogicVector) -> LogicVector {
+    match index(memory, addr) {
+        Some(a) => memory[a].clone(),
+        None => LogicVector::from_ieee(Ieee1164::_X, memory[0].width()),
+    }
+}
+
+/// Writes `data` to `addr`. If it is unknown whether the write takes place, the addressed location
+/// is corrupted, i.e. all bits that differ from `data` become [`Ieee1164::_X`]. If the address is
+/// unknown, every location is corrupted.
+fn write(memory: &mut [LogicVector], addr: &LogicVector, data: &LogicVector, access: Access) {
+    if access == Access::No {
+        return;
+    }
+    let data = checked(data.clone(), memory[0].width());
+    match (index(memory, addr), access) {
+        (Some(a), Access::Yes) => memory[a] = data,
+        (Some(a), _) => memory[a] = memory[a].merge(&data),
+        (None, _) => {
+            for m in memory.iter_mut() {
+                *m = m.merge(&data);
+            }
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use super::{new_memory, read, write, Access};
+use crate::direction::{Input, Output};
+use crate::models::drive;
+use crate::models::rtlib::sequential::{Edge, Storable};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// This struct represents an asynchronous random-access-memory, like a classic SRAM.
+///
+/// The RAM has `2^addr_width` locations of `data_width` bits, which are [`Ieee1164::_U`] after
+/// creation. All control lines are active-low. While [`Ram::n_chip_select`] and
+/// [`Ram::n_write_enable`] are low, [`Ram::data_in`] is written to the location addressed by
+/// [`Ram::addr`]. While [`Ram::n_chip_select`] and [`Ram::n_output_enable`] are low and no write
+/// takes place, the addressed location is outputted on [`Ram::data_out`], otherwise `data_out` is
+/// [`Ieee1164::_Z`] (high-impedance).
+///
+/// Because `data_out` is high-impedance during a write, `data_in` and `data_out` can be connected
+/// to the same [`Signal`](crate::Signal) to get a bidirectional data bus.
+///
+/// If it is unknown whether a write takes place, e.g. because `n_write_enable` is
+/// [`Ieee1164::_X`], the addressed location is corrupted. A write to an unknown address corrupts
+/// the whole memory.
+///
+/// # Examples
+///
+/// ```rust
+/// use logical::{signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::memory::Ram;
+///
+/// let ram = Ram::new(4, 8).unwrap();
+/// let mut addr = Port::<_, Output>::new(LogicVector::from_int(3, 4).unwrap());
+/// let data_in = Port::<_, Output>::new(LogicVector::from_int(0xA5, 8).unwrap());
+/// let data_out = Port::<_, Input>::new(LogicVector::with_width(8));
+/// let n_chip_select = Port::<_, Output>::new(Ieee1164::_0);
+/// let mut n_write_enable = Port::<_, Output>::new(Ieee1164::_0);
+/// let n_output_enable = Port::<_, Output>::new(Ieee1164::_0);
+///
+/// let mut circuit = Circuit::default();
+/// circuit.add(signal!(addr, ram.addr));
+/// circuit.add(signal!(data_in, ram.data_in));
+/// circuit.add(signal!(ram.data_out, data_out));
+/// circuit.add(signal!(n_chip_select, ram.n_chip_select));
+/// circuit.add(signal!(n_write_enable, ram.n_write_enable));
+/// circuit.add(signal!(n_output_enable, ram.n_output_enable));
+/// let ram = circuit.add(ram);
+///
+/// circuit.step().unwrap();
+/// n_write_enable.replace(Ieee1164::_1);
+/// circuit.step().unwrap();
+/// assert_eq!(Some(0xA5), data_out.value().as_u128());
+/// assert_eq!(Some(0xA5), circuit[ram].memory[3].as_u128());
+///
+/// addr.replace(LogicVector::from_int(4, 4).unwrap());
+/// circuit.step().unwrap();
+/// assert_eq!(LogicVector::with_width(8), data_out.value());
+/// ```
+#[derive(Debug, Component)]
+#[non_exhaustive]
+pub struct Ram {
+    /// The memory that holds the values stored inside this `Ram`, one entry per address.
+    pub memory: Vec<LogicVector>,
+    /// Determines the position inside the `Ram` where the data is read from or written to.
+    #[port(input)]
+    pub addr: Port<LogicVector, Input>,
+    /// Data port which contains the data that is written.
+    #[port(input)]
+    pub data_in: Port<LogicVector, Input>,
+    /// Data port which contains the data addressed by the `addr` port.
+    #[port(output)]
+    pub data_out: Port<LogicVector, Output>,
+    /// Active-low chip-select pin. If pulled high, nothing is read or written.
+    #[port(input)]
+    pub n_chip_select: Port<Ieee1164, Input>,
+    /// Active-low write enable pin. If pulled low, `data_in` is written.
+    #[port(input)]
+    pub n_write_enable: Port<Ieee1164, Input>,
+    /// Active-low output enable pin. If pulled high, the output will be [`Ieee1164::_Z`].
+    #[port(input)]
+    pub n_output_enable: Port<Ieee1164, Input>,
+}
+
+impl Ram {
+    /// Creates a `Ram` with `2^addr_width` locations of `data_width` bits each.
+    ///
+    /// Returns `None` if one of the widths is `0` or `addr_width` is larger than
+    /// [`MAX_ADDR_WIDTH`](super::MAX_ADDR_WIDTH).
+    pub fn new(addr_width: usize, data_width: usize) -> Option<Self> {
+        Some(Self {
+            memory: new_memory(addr_width, data_width)?,
+            addr: Port::new(LogicVector::with_width(addr_width)),
+            data_in: Port::new(LogicVector::with_width(data_width)),
+            data_out: Port::new(LogicVector::with_width(data_width)),
+            n_chip_select: Port::default(),
+            n_write_enable: Port::default(),
+            n_output_enable: Port::default(),
+        })
+    }
+}
+
+impl Updateable for Ram {
+    fn update(&mut self) -> bool {
+        let addr = self.addr.value();
+        let ncs = self.n_chip_select.value();
+        let nwe = self.n_write_enable.value();
+        let noe = self.n_output_enable.value();
+
+        write(
+            &mut self.memory,
+            &addr,
+            &self.data_in.value(),
+            Access::of(&[ncs, nwe]),
+        );
+
+        let width = self.memory[0].width();
+        let data = match (Access::of(&[ncs, noe]), Access::of(&[nwe])) {
+            (Access::No, _) | (_, Access::Yes) => LogicVector::from_ieee(Ieee1164::_Z, width),
+            (Access::Yes, Access::No) => read(&self.memory, &addr),
+            _ => LogicVector::from_ieee(Ieee1164::_X, width),
+        };
+        drive(&mut self.data_out, data)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+/// This struct represents a synchronous random-access-memory, like the block RAM of an FPGA.
+///
+/// The RAM has `2^addr_width` locations of `data_width` bits, which are [`Ieee1164::_U`] after
+/// creation. All control lines are active-low. On a rising edge of [`SyncRam::clk`] while
+/// [`SyncRam::n_chip_select`] is low, the addressed location is read into the output register
+/// and, if [`SyncRam::n_write_enable`] is low, [`SyncRam::data_in`] is written afterwards
+/// (read-first). The output register is outputted on [`SyncRam::data_out`] while
+/// [`SyncRam::n_output_enable`] is low, otherwise `data_out` is [`Ieee1164::_Z`].
+///
+/// Uncertain clock edges or control lines corrupt the output register and the addressed
+/// location, like for [`Ram`]. See the
+/// [sequential module](crate::models::rtlib::sequential) for how clock edges are detected.
+///
+/// # Examples
+///
+/// ```rust
+/// use logical::{signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::memory::SyncRam;
+///
+/// let ram = SyncRam::new(4, 8).unwrap();
+/// let addr = Port::<_, Output>::new(LogicVector::from_int(3, 4).unwrap());
+/// let data_in = Port::<_, Output>::new(LogicVector::from_int(0xA5, 8).unwrap());
+/// let data_out = Port::<_, Input>::new(LogicVector::with_width(8));
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let control = Port::<_, Output>::new(Ieee1164::_0);
+///
+/// let mut circuit = Circuit::default();
+/// circuit.add(signal!(addr, ram.addr));
+/// circuit.add(signal!(data_in, ram.data_in));
+/// circuit.add(signal!(ram.data_out, data_out));
+/// circuit.add(signal!(clk, ram.clk));
+/// circuit.add(signal!(control, ram.n_chip_select, ram.n_write_enable, ram.n_output_enable));
+/// circuit.add(ram);
+///
+/// circuit.step().unwrap();
+/// clk.replace(Ieee1164::_1);
+/// circuit.step().unwrap();
+/// // the old value has been read, before the new one was written
+/// assert_eq!(LogicVector::with_width(8), data_out.value());
+///
+/// clk.replace(Ieee1164::_0);
+/// circuit.step().unwrap();
+/// clk.replace(Ieee1164::_1);
+/// circuit.step().unwrap();
+/// assert_eq!(Some(0xA5), data_out.value().as_u128());
+/// ```
+#[derive(Debug, Component)]
+pub struct SyncRam {
+    /// The memory that holds the values stored inside this `SyncRam`, one entry per address.
+    pub memory: Vec<LogicVector>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Determines the position inside the `SyncRam` where the data is read from or written to.
+    #[port(input)]
+    pub addr: Port<LogicVector, Input>,
+    /// Data port which contains the data that is written.
+    #[port(input)]
+    pub data_in: Port<LogicVector, Input>,
+    /// Data port which contains the output register.
+    #[port(output)]
+    pub data_out: Port<LogicVector, Output>,
+    /// Active-low chip-select pin. If pulled high, nothing is read or written.
+    #[port(input)]
+    pub n_chip_select: Port<Ieee1164, Input>,
+    /// Active-low write enable pin. If pulled low, `data_in` is written.
+    #[port(input)]
+    pub n_write_enable: Port<Ieee1164, Input>,
+    /// Active-low output enable pin. If pulled high, the output will be [`Ieee1164::_Z`].
+    #[port(input)]
+    pub n_output_enable: Port<Ieee1164, Input>,
+    output: LogicVector,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl SyncRam {
+    /// Creates a `SyncRam` with `2^addr_width` locations of `data_width` bits each.
+    ///
+    /// Returns `None` if one of the widths is `0` or `addr_width` is larger than
+    /// [`MAX_ADDR_WIDTH`](super::MAX_ADDR_WIDTH).
+    pub fn new(addr_width: usize, data_width: usize) -> Option<Self> {
+        Some(Self {
+            memory: new_memory(addr_width, data_width)?,
+            clk: Port::default(),
+            addr: Port::new(LogicVector::with_width(addr_width)),
+            data_in: Port::new(LogicVector::with_width(data_width)),
+            data_out: Port::new(LogicVector::with_width(data_width)),
+            n_chip_select: Port::default(),
+            n_write_enable: Port::default(),
+            n_output_enable: Port::default(),
+            output: LogicVector::with_width(data_width),
+            last_clk: Ieee1164::_U,
+            _private: (),
+        })
+    }
+}
+
+impl Updateable for SyncRam {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        if edge != Edge::None {
+            let addr = self.addr.value();
+            let ncs = self.n_chip_select.value();
+            let nwe = self.n_write_enable.value();
+            let (read_access, write_access) = match edge {
+                Edge::Rising => (Access::of(&[ncs]), Access::of(&[ncs, nwe])),
+                _ => (
+                    Access::of(&[ncs]).uncertain(),
+                    Access::of(&[ncs, nwe]).uncertain(),
+                ),
+            };
+
+            let data = read(&self.memory, &addr);
+            match read_access {
+                Access::Yes => self.output = data,
+                Access::Maybe => self.output = self.output.merge(&data),
+                Access::No => {}
+            }
+            write(&mut self.memory, &addr, &self.data_in.value(), write_access);
+        }
+
+        let width = self.output.width();
+        let data = match Access::of(&[self.n_output_enable.value()]) {
+            Access::Yes => self.output.clone(),
+            Access::No => LogicVector::from_ieee(Ieee1164::_Z, width),
+            Access::Maybe => LogicVector::from_ieee(Ieee1164::_X, width),
+        };
+        drive(&mut self.data_out, data)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.n_output_enable.id()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::rtlib::memory::MAX_ADDR_WIDTH;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    fn lv(value: u128) -> LogicVector {
+        LogicVector::from_int(value, 8).unwrap()
+    }
+
+    #[test]
+    fn invalid_widths() {
+        assert!(Ram::new(0, 8).is_none());
+        assert!(Ram::new(8, 0).is_none());
+        assert!(SyncRam::new(usize::BITS as usize, 8).is_none());
+        assert!(Ram::new(40, 8).is_none());
+        assert!(Ram::new(MAX_ADDR_WIDTH + 1, 8).is_none());
+        assert_eq!(1024, Ram::new(10, 8).unwrap().memory.len());
+    }
+
+    #[test]
+    fn bidirectional_bus() {
+        let ram = Ram::new(2, 8).unwrap();
+        let mut addr = Port::<_, Output>::new(LogicVector::from_int(1, 2).unwrap());
+        let mut cpu = Port::<_, Output>::new(lv(0x42));
+        let bus = Port::<_, Input>::new(LogicVector::with_width(8));
+        let n_chip_select = Port::<_, Output>::new(_0);
+        let mut n_write_enable = Port::<_, Output>::new(_0);
+        let n_output_enable = Port::<_, Output>::new(_0);
+
+        let mut circuit = circuit!(
+            signal!(addr, ram.addr),
+            signal!(cpu, ram.data_out, ram.data_in, bus),
+            signal!(n_chip_select, ram.n_chip_select),
+            signal!(n_write_enable, ram.n_write_enable),
+            signal!(n_output_enable, ram.n_output_enable)
+        );
+        let ram = circuit.add(ram);
+
+        circuit.step().unwrap();
+        assert_eq!(Some(0x42), bus.value().as_u128());
+
+        cpu.replace(LogicVector::from_ieee(Ieee1164::_Z, 8));
+        n_write_enable.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!(Some(0x42), bus.value().as_u128());
+
+        addr.replace(LogicVector::from_int(2, 2).unwrap());
+        circuit.step().unwrap();
+        assert_eq!(LogicVector::with_width(8), bus.value());
+        assert_eq!(Some(0x42), circuit[ram].memory[1].as_u128());
+    }
+
+    #[test]
+    fn x_writes_corrupt() {
+        let mut ram = Ram::new(2, 8).unwrap();
+        for (i, m) in ram.memory.iter_mut().enumerate() {
+            *m = lv(i as u128);
+        }
+        let mut addr = Port::<_, Output>::new(LogicVector::from_int(1, 2).unwrap());
+        let data = Port::<_, Output>::new(lv(3));
+        let n_chip_select = Port::<_, Output>::new(_0);
+        let mut n_write_enable = Port::<_, Output>::new(_X);
+
+        let mut circuit = circuit!(
+            signal!(addr, ram.addr),
+            signal!(data, ram.data_in),
+            signal!(n_chip_select, ram.n_chip_select),
+            signal!(n_write_enable, ram.n_write_enable)
+        );
+        let ram = circuit.add(ram);
+
+        circuit.step().unwrap();
+        assert_eq!("000000X1", circuit[ram].memory[1].to_string());
+        assert_eq!(Some(0), circuit[ram].memory[0].as_u128());
+
+        n_write_enable.replace(_0);
+        addr.replace("0X".parse().unwrap());
+        circuit.step().unwrap();
+        assert_eq!("000000XX", circuit[ram].memory[0].to_string());
+        assert_eq!("000000X1", circuit[ram].memory[1].to_string());
+        assert_eq!("0000001X", circuit[ram].memory[2].to_string());
+        assert_eq!(Some(3), circuit[ram].memory[3].as_u128());
+    }
+
+    #[test]
+    fn sync_write_and_read() {
+        let ram = SyncRam::new(3, 8).unwrap();
+        let mut addr = Port::<_, Output>::new(LogicVector::from_int(5, 3).unwrap());
+        let mut data = Port::<_, Output>::new(lv(0x11));
+        let out = Port::<_, Input>::new(LogicVector::with_width(8));
+        let mut clk = Port::<_, Output>::new(_0);
+        let n_chip_select = Port::<_, Output>::new(_0);
+        let mut n_write_enable = Port::<_, Output>::new(_0);
+        let mut n_output_enable = Port::<_, Output>::new(_1);
+
+        let mut circuit = circuit!(
+            signal!(addr, ram.addr),
+            signal!(data, ram.data_in),
+            signal!(ram.data_out, out),
+            signal!(clk, ram.clk),
+            signal!(n_chip_select, ram.n_chip_select),
+            signal!(n_write_enable, ram.n_write_enable),
+            signal!(n_output_enable, ram.n_output_enable)
+        );
+        let ram = circuit.add(ram);
+        let mut clock = |circuit: &mut Circuit| {
+            for &level in &[_1, _0] {
+                clk.replace(level);
+                circuit.step().unwrap();
+            }
+        };
+
+        circuit.step().unwrap();
+        clock(&mut circuit);
+        assert_eq!(LogicVector::from_ieee(Ieee1164::_Z, 8), out.value());
+        assert_eq!(Some(0x11), circuit[ram].memory[5].as_u128());
+
+        n_write_enable.replace(_1);
+        data.replace(lv(0x22));
+        n_output_enable.replace(_0);
+        clock(&mut circuit);
+        assert_eq!(Some(0x11), out.value().as_u128());
+        assert_eq!(Some(0x11), circuit[ram].memory[5].as_u128());
+
+        addr.replace(LogicVector::from_int(6, 3).unwrap());
+        n_write_enable.replace(_0);
+        circuit[ram].memory[6] = lv(0x20);
+        clk.replace(_X);
+        circuit.step().unwrap();
+        assert_eq!("00XX000X", out.value().to_string());
+        assert_eq!("001000X0", circuit[ram].memory[6].to_string());
+    }
+}
@@ -0,0 +1,376 @@
+use super::{new_memory, read, write, Access};
+use crate::component::{Component, PortInfo};
+use crate::direction::{Input, Output};
+use crate::models::rtlib::sequential::{Edge, Storable};
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// What a read port of a [`RegisterFile`] outputs, if it reads a location that is written at the
+/// same clock edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadDuringWrite {
+    /// The value before the write is read (read-first)
+    OldData,
+    /// The value that is written is read (write-first)
+    NewData,
+    /// The read value is undefined, i.e. all bits that are changed by the write are
+    /// [`Ieee1164::_X`]
+    DontCare,
+}
+
+/// A read port of a [`RegisterFile`].
+#[derive(Debug, Component)]
+#[non_exhaustive]
+pub struct ReadPort {
+    /// Determines the location that is read.
+    #[port(input)]
+    pub addr: Port<LogicVector, Input>,
+    /// Data port which contains the word that was read at the last clock edge.
+    #[port(output)]
+    pub data: Port<LogicVector, Output>,
+}
+
+/// A write port of a [`RegisterFile`].
+#[derive(Debug, Component)]
+#[non_exhaustive]
+pub struct WritePort {
+    /// Determines the location that is written.
+    #[port(input)]
+    pub addr: Port<LogicVector, Input>,
+    /// Data port which contains the word that is written.
+    #[port(input)]
+    pub data: Port<LogicVector, Input>,
+    /// Active-high write enable `Port`, inactive by default
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+}
+
+/// A register file with several read and write ports, that all share one clock, like the
+/// registers of a processor.
+///
+/// The register file has `2^addr_width` locations of `data_width` bits, which are
+/// [`Ieee1164::_U`] after creation. On a rising edge of [`RegisterFile::clk`] every
+/// [`WritePort`] whose `enable` is high writes its `data` to its `addr`. If several write ports
+/// write the same location, the one with the highest index wins. At the same edge every
+/// [`ReadPort`] stores the location addressed by its `addr` in its output register. What is read
+/// from a location that is written at the same time is determined by the [`ReadDuringWrite`]
+/// behavior.
+///
+/// Uncertain clock edges or enables corrupt the written locations and the output registers, like
+/// for the [`SyncRam`](super::SyncRam).
+///
+/// The ports are listed as children of the [`Component`], e.g. `read0` and `write0`.
+///
+/// # Examples
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::memory::{ReadDuringWrite, RegisterFile};
+///
+/// let regs = RegisterFile::new(3, 8, 2, 1, ReadDuringWrite::NewData).unwrap();
+/// let addr = Port::<_, Output>::new(LogicVector::from_int(5, 3).unwrap());
+/// let other_addr = Port::<_, Output>::new(LogicVector::from_int(6, 3).unwrap());
+/// let data = Port::<_, Output>::new(LogicVector::from_int(0x42, 8).unwrap());
+/// let enable = Port::<_, Output>::new(Ieee1164::_1);
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let a = Port::<_, Input>::new(LogicVector::with_width(8));
+/// let b = Port::<_, Input>::new(LogicVector::with_width(8));
+///
+/// let mut circuit = circuit!(
+///     signal!(addr, regs.write[0].addr, regs.read[0].addr),
+///     signal!(other_addr, regs.read[1].addr),
+///     signal!(data, regs.write[0].data),
+///     signal!(enable, regs.write[0].enable),
+///     signal!(clk, regs.clk),
+///     signal!(regs.read[0].data, a),
+///     signal!(regs.read[1].data, b)
+/// );
+/// circuit.add(regs);
+///
+/// circuit.step().unwrap();
+/// clk.replace(Ieee1164::_1);
+/// circuit.step().unwrap();
+/// assert_eq!(Some(0x42), a.value().as_u128());
+/// assert_eq!(LogicVector::with_width(8), b.value());
+/// ```
+#[derive(Debug)]
+pub struct RegisterFile {
+    /// The memory that holds the values stored inside this `RegisterFile`, one entry per address.
+    pub memory: Vec<LogicVector>,
+    /// Clock input `Port`
+    pub clk: Port<Ieee1164, Input>,
+    /// The read ports
+    pub read: Vec<ReadPort>,
+    /// The write ports
+    pub write: Vec<WritePort>,
+    read_during_write: ReadDuringWrite,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl RegisterFile {
+    /// Creates a `RegisterFile` with `2^addr_width` locations of `data_width` bits each, that has
+    /// `read_ports` read and `write_ports` write ports.
+    ///
+    /// Returns `None` if one of the widths or the number of read or write ports is `0`, or if
+    /// `addr_width` is larger than [`MAX_ADDR_WIDTH`](super::MAX_ADDR_WIDTH).
+    pub fn new(
+        addr_width: usize,
+        data_width: usize,
+        read_ports: usize,
+        write_ports: usize,
+        read_during_write: ReadDuringWrite,
+    ) -> Option<Self> {
+        if read_ports == 0 || write_ports == 0 {
+            return None;
+        }
+        let memory = new_memory(addr_width, data_width)?;
+        let read = (0..read_ports)
+            .map(|_| ReadPort {
+                addr: Port::new(LogicVector::with_width(addr_width)),
+                data: Port::new(LogicVector::with_width(data_width)),
+            })
+            .collect();
+        let write = (0..write_ports)
+            .map(|_| WritePort {
+                addr: Port::new(LogicVector::with_width(addr_width)),
+                data: Port::new(LogicVector::with_width(data_width)),
+                enable: Port::new(Ieee1164::_0),
+            })
+            .collect();
+        Some(Self {
+            memory,
+            clk: Port::default(),
+            read,
+            write,
+            read_during_write,
+            last_clk: Ieee1164::_U,
+            _private: (),
+        })
+    }
+
+    /// Returns what is read from a location that is written at the same time.
+    pub fn read_during_write(&self) -> ReadDuringWrite {
+        self.read_during_write
+    }
+}
+
+impl Component for RegisterFile {
+    fn ports(&self) -> Vec<PortInfo> {
+        vec![PortInfo::new("clk", &self.clk)]
+    }
+
+    fn children(&self) -> Vec<(String, &dyn Component)> {
+        let read = self
+            .read
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (format!("read{}", i), r as &dyn Component));
+        let write = self
+            .write
+            .iter()
+            .enumerate()
+            .map(|(i, w)| (format!("write{}", i), w as &dyn Component));
+        read.chain(write).collect()
+    }
+}
+
+impl Updateable for RegisterFile {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+        if edge == Edge::None {
+            return false;
+        }
+
+        let old: Vec<_> = self
+            .read
+            .iter()
+            .map(|r| read(&self.memory, &r.addr.value()))
+            .collect();
+        for w in &self.write {
+            let access = Access::of(&[!w.enable.value()]);
+            let access = if edge == Edge::Rising {
+                access
+            } else {
+                access.uncertain()
+            };
+            write(&mut self.memory, &w.addr.value(), &w.data.value(), access);
+        }
+
+        let mut changed = false;
+        for (r, old) in self.read.iter_mut().zip(old) {
+            let new = read(&self.memory, &r.addr.value());
+            let value = match self.read_during_write {
+                ReadDuringWrite::OldData => old,
+                ReadDuringWrite::NewData => new,
+                ReadDuringWrite::DontCare => old.merge(&new),
+            };
+            changed |= r.data.with_value_mut(|data| {
+                let value = if edge == Edge::Rising {
+                    value
+                } else {
+                    data.merge(&value)
+                };
+                let port_changed = *data != value;
+                *data = value;
+                port_changed
+            });
+        }
+        changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        vec![self.clk.id()]
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.read.iter().map(|r| r.data.id()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    fn lv(value: u128, width: usize) -> LogicVector {
+        LogicVector::from_int(value, width).unwrap()
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert!(RegisterFile::new(2, 8, 0, 1, ReadDuringWrite::OldData).is_none());
+        assert!(RegisterFile::new(2, 8, 1, 0, ReadDuringWrite::OldData).is_none());
+        assert!(RegisterFile::new(0, 8, 1, 1, ReadDuringWrite::OldData).is_none());
+    }
+
+    #[test]
+    fn ports_are_children() {
+        let regs = RegisterFile::new(2, 8, 2, 1, ReadDuringWrite::OldData).unwrap();
+        let mut names = vec![];
+        regs.visit_ports(&mut |path, port| {
+            let mut name = path.to_vec();
+            name.push(port.name().to_string());
+            names.push(name.join("."));
+        });
+        let expected = vec![
+            "clk",
+            "read0.addr",
+            "read0.data",
+            "read1.addr",
+            "read1.data",
+            "write0.addr",
+            "write0.data",
+            "write0.enable",
+        ];
+        assert_eq!(expected, names);
+    }
+
+    /// Writes `0x0f` to location `1`, which holds `0x33`, while all modes read it
+    fn read_during_write(enable: Ieee1164, clk_edge: Ieee1164) -> Vec<String> {
+        let modes = [
+            ReadDuringWrite::OldData,
+            ReadDuringWrite::NewData,
+            ReadDuringWrite::DontCare,
+        ];
+        let mut regs: Vec<_> = modes
+            .iter()
+            .map(|&mode| {
+                let mut regs = RegisterFile::new(2, 8, 1, 2, mode).unwrap();
+                regs.memory[1] = lv(0x33, 8);
+                regs
+            })
+            .collect();
+        let addr = Port::<_, Output>::new(lv(1, 2));
+        let data = Port::<_, Output>::new(lv(0x0f, 8));
+        let other_addr = Port::<_, Output>::new(lv(2, 2));
+        let enable = Port::<_, Output>::new(enable);
+        let mut clk = Port::<_, Output>::new(_0);
+        let outputs: Vec<_> = modes
+            .iter()
+            .map(|_| Port::<_, Input>::new(LogicVector::with_width(8)))
+            .collect();
+
+        let mut circuit = Circuit::default();
+        for (r, output) in regs.iter().zip(&outputs) {
+            // the second write port is not enabled, so it does not matter
+            circuit.add(signal!(addr, r.write[0].addr, r.read[0].addr));
+            circuit.add(signal!(other_addr, r.write[1].addr));
+            circuit.add(signal!(data, r.write[0].data, r.write[1].data));
+            circuit.add(signal!(enable, r.write[0].enable));
+            circuit.add(signal!(clk, r.clk));
+            circuit.add(signal!(r.read[0].data, *output));
+        }
+        for r in regs.drain(..) {
+            circuit.add(r);
+        }
+
+        circuit.step().unwrap();
+        clk.replace(clk_edge);
+        circuit.step().unwrap();
+        outputs.iter().map(|o| o.value().to_string()).collect()
+    }
+
+    #[test]
+    fn read_during_write_modes() {
+        assert_eq!(
+            vec!["00110011", "00001111", "00XXXX11"],
+            read_during_write(_1, _1)
+        );
+        assert_eq!(
+            vec!["00110011", "00110011", "00110011"],
+            read_during_write(_0, _1)
+        );
+        let merged = "00XXXX11";
+        assert_eq!(vec!["00110011", merged, merged], read_during_write(_X, _1));
+        // an uncertain edge merges with the uninitialized output register
+        assert_eq!(vec!["XXXXXXXX"; 3], read_during_write(_0, _X));
+    }
+
+    #[test]
+    fn several_write_ports() {
+        let regs = RegisterFile::new(2, 4, 1, 2, ReadDuringWrite::OldData).unwrap();
+        let addr = Port::<_, Output>::new(lv(3, 2));
+        let data0 = Port::<_, Output>::new(lv(1, 4));
+        let data1 = Port::<_, Output>::new(lv(2, 4));
+        let mut enable1 = Port::<_, Output>::new(_1);
+        let enable0 = Port::<_, Output>::new(_1);
+        let mut clk = Port::<_, Output>::new(_0);
+        let q = Port::<_, Input>::new(LogicVector::with_width(4));
+
+        let mut circuit = circuit!(
+            signal!(
+                addr,
+                regs.write[0].addr,
+                regs.write[1].addr,
+                regs.read[0].addr
+            ),
+            signal!(data0, regs.write[0].data),
+            signal!(data1, regs.write[1].data),
+            signal!(enable0, regs.write[0].enable),
+            signal!(enable1, regs.write[1].enable),
+            signal!(clk, regs.clk),
+            signal!(regs.read[0].data, q)
+        );
+        let regs = circuit.add(regs);
+
+        circuit.step().unwrap();
+        clk.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!(Some(2), circuit[regs].memory[3].as_u128());
+
+        enable1.replace(_0);
+        clk.replace(_0);
+        circuit.step().unwrap();
+        clk.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!(Some(1), circuit[regs].memory[3].as_u128());
+        assert_eq!(Some(2), q.value().as_u128());
+    }
+}
@@ -1,96 +1,171 @@
+use std::convert::TryFrom;
 use std::fmt;
-use std::iter::FromIterator;
 
 use crate::direction::{Input, Output};
-use crate::{Ieee1164, LogicVector, Port, Updateable};
+use crate::models::select_width;
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
 
-/// This struct represents a Read-only-memory with a size of 1kB (1024 bytes).
+/// This struct represents a Read-only-memory with `DEPTH` words of `WIDTH` bits each.
 ///
-/// This rom consists of a 10-bit address line, a 8-bit data line, a chip-select and an
-/// output-enable line which can be used to control the data output to be [`Ieee1164::_Z`]
-/// (high-impedance) instead of outputting a value.
+/// This rom consists of an address line, which is just wide enough to address all words, a
+/// `WIDTH`-bit data line, a chip-select and an output-enable line which can be used to control the
+/// data output to be [`Ieee1164::_Z`] (high-impedance) instead of outputting a value. Addresses
+/// beyond `DEPTH` output [`Ieee1164::_X`].
 ///
 /// Althought it's a `Rom`, you can modify the values inside programmatically, but not with
 /// [`Signals`](crate::Signal).
 ///
+/// Neither `DEPTH` nor `WIDTH` may be `0`, which is checked at compile time.
+///
 /// # Examples
 ///
-/// The easiest way to create a `Rom`, is using the [`FromIterator`] trait.
+/// The easiest way to create a `Rom`, is [`Rom::try_from_iter`].
 /// ```rust
-/// use logical::models::rtlib::memory::Rom1kx8;
+/// use logical::models::rtlib::memory::{Rom, RomError};
 ///
-/// let rom: Rom1kx8 = (0..=255).cycle().collect();
+/// let rom = Rom::<300, 12>::try_from_iter((0..4096).step_by(3)).unwrap();
+/// assert_eq!(9, rom.addr_width());
+/// assert_eq!(Some(897), rom.memory[299].as_u128());
+///
+/// assert_eq!(Err(RomError::WrongDepth(100)), Rom::<300, 12>::try_from_iter(0..100).map(|_| ()));
 /// ```
 ///
-/// The `FromIterator` implementation takes exactly 1024 bytes out of the stream and panics if there
-/// are less bytes available.
-pub struct Rom1kx8 {
-    /// The memory that holds the values stored inside this Rom.
-    pub memory: [u8; 1024],
+/// The contents can also be given as [`LogicVector`]s, which may contain any [`Ieee1164`] value.
+/// ```rust
+/// use std::convert::TryFrom;
+/// use logical::LogicVector;
+/// use logical::models::rtlib::memory::Rom;
+///
+/// let words = vec!["10XZ".parse::<LogicVector>().unwrap(), "0110".parse().unwrap()];
+/// let rom = Rom::<2, 4>::try_from(words).unwrap();
+/// ```
+#[derive(Component)]
+#[non_exhaustive]
+pub struct Rom<const DEPTH: usize, const WIDTH: usize> {
+    /// The memory that holds the values stored inside this Rom, one `WIDTH`-bit word per address.
+    pub memory: Vec<LogicVector>,
     /// Determines the position inside the `Rom` where the data to read from.
+    #[port(input)]
     pub addr: Port<LogicVector, Input>,
     /// Data port which contains the data addressed by the `addr` port.
+    #[port(output)]
     pub data: Port<LogicVector, Output>,
     /// Active-low chip-select pin. If pulled high, the output will be [`Ieee1164::_Z`].
+    #[port(input)]
     pub n_chip_select: Port<Ieee1164, Input>,
     /// Active-low output enable pin. If pulled high, the output will be [`Ieee1164::_Z`].
+    #[port(input)]
     pub n_output_enable: Port<Ieee1164, Input>,
-    _private: (),
 }
 
-impl FromIterator<u8> for Rom1kx8 {
-    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
-        let mut mem = [0; 1024];
-        let mut bytes_read = 0;
-        for (m, v) in mem.iter_mut().zip(iter.into_iter()).take(1024) {
-            bytes_read += 1;
-            *m = v;
+/// A Read-only-memory with a size of 1kB (1024 bytes), i.e. with a 10-bit address line and a 8-bit
+/// data line.
+///
+/// ```rust
+/// use logical::models::rtlib::memory::Rom1kx8;
+///
+/// let rom = Rom1kx8::try_from_iter((0..=255).cycle()).unwrap();
+/// assert_eq!(10, rom.addr_width());
+/// ```
+pub type Rom1kx8 = Rom<1024, 8>;
+
+/// Errors that can occur when creating a [`Rom`] from its contents.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum RomError {
+    /// The number of given words does not match the depth of the `Rom`. Contains the number of
+    /// words that were given.
+    WrongDepth(usize),
+    /// The word at the given index does not fit into the width of the `Rom`.
+    WrongWidth(usize),
+}
+
+impl fmt::Display for RomError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RomError::WrongDepth(depth) => write!(f, "wrong number of words: {}", depth),
+            RomError::WrongWidth(index) => write!(f, "word {} does not fit into the rom", index),
         }
-        assert_eq!(1024, bytes_read);
+    }
+}
+
+impl std::error::Error for RomError {}
 
+impl<const DEPTH: usize, const WIDTH: usize> Rom<DEPTH, WIDTH> {
+    const VALID: () = assert!(DEPTH > 0 && WIDTH > 0, "a rom needs at least one bit");
+
+    fn with_memory(memory: Vec<LogicVector>) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
         Self {
-            memory: mem,
-            addr: Port::new(LogicVector::with_width(10)),
-            data: Port::new(LogicVector::with_width(8)),
+            memory,
+            addr: Port::new(LogicVector::with_width(select_width(DEPTH))),
+            data: Port::new(LogicVector::with_width(WIDTH)),
             n_chip_select: Port::default(),
             n_output_enable: Port::default(),
-            _private: (),
         }
     }
+
+    /// Creates a `Rom` from exactly `DEPTH` integers, the remaining items of `iter` are ignored.
+    ///
+    /// Returns an error if `iter` is too short or a value does not fit into `WIDTH` bits.
+    pub fn try_from_iter<I: IntoIterator<Item = u128>>(iter: I) -> Result<Self, RomError> {
+        let mut memory = Vec::with_capacity(DEPTH);
+        for (i, v) in iter.into_iter().take(DEPTH).enumerate() {
+            memory.push(LogicVector::from_int(v, WIDTH).ok_or(RomError::WrongWidth(i))?);
+        }
+        if memory.len() != DEPTH {
+            return Err(RomError::WrongDepth(memory.len()));
+        }
+        Ok(Self::with_memory(memory))
+    }
+
+    /// Returns the width of the [`Rom::addr`] port.
+    pub fn addr_width(&self) -> usize {
+        select_width(DEPTH)
+    }
 }
 
-impl Default for Rom1kx8 {
-    fn default() -> Self {
-        Self {
-            memory: [0; 1024],
-            addr: Port::new(LogicVector::with_width(10)),
-            data: Port::new(LogicVector::with_width(8)),
-            n_chip_select: Port::default(),
-            n_output_enable: Port::default(),
-            _private: (),
+impl<const DEPTH: usize, const WIDTH: usize> TryFrom<Vec<LogicVector>> for Rom<DEPTH, WIDTH> {
+    type Error = RomError;
+
+    fn try_from(memory: Vec<LogicVector>) -> Result<Self, Self::Error> {
+        if memory.len() != DEPTH {
+            return Err(RomError::WrongDepth(memory.len()));
         }
+        if let Some(i) = memory.iter().position(|m| m.width() != WIDTH) {
+            return Err(RomError::WrongWidth(i));
+        }
+        Ok(Self::with_memory(memory))
+    }
+}
+
+impl<const DEPTH: usize, const WIDTH: usize> Default for Rom<DEPTH, WIDTH> {
+    fn default() -> Self {
+        Self::with_memory(vec![LogicVector::from_ieee(Ieee1164::_0, WIDTH); DEPTH])
     }
 }
 
-impl fmt::Debug for Rom1kx8 {
+impl<const DEPTH: usize, const WIDTH: usize> fmt::Debug for Rom<DEPTH, WIDTH> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Rom1kx8 {{ addr: {:?}, data: {:?}, n_chip_select {:?}, n_output_enable {:?} }}",
-            self.addr, self.data, self.n_chip_select, self.n_output_enable
+            "Rom<{}, {}> {{ addr: {:?}, data: {:?}, n_chip_select {:?}, n_output_enable {:?} }}",
+            DEPTH, WIDTH, self.addr, self.data, self.n_chip_select, self.n_output_enable
         )
     }
 }
 
-impl Updateable for Rom1kx8 {
+impl<const DEPTH: usize, const WIDTH: usize> Updateable for Rom<DEPTH, WIDTH> {
     fn update(&mut self) -> bool {
         let ncs = self.n_chip_select.value();
         let noe = self.n_output_enable.value();
-        let data = if let Some(addr) = self.addr.value().as_u128() {
-            Some(u128::from(self.memory[addr as usize]))
-        } else {
-            None
-        };
+        let data = self
+            .addr
+            .value()
+            .as_u128()
+            .filter(|&addr| addr < DEPTH as u128)
+            .map(|addr| self.memory[addr as usize].clone());
 
         self.data.with_value_mut(|f| {
             let old_value = f.clone();
@@ -99,13 +174,21 @@ impl Updateable for Rom1kx8 {
             } else if ncs.is_1H() || noe.is_1H() {
                 f.set_all_to(Ieee1164::_Z);
             } else if let Some(data) = data {
-                f.replace_with_int(data).unwrap();
+                *f = data;
             } else {
                 f.set_all_to(Ieee1164::_X);
             };
-            old_value == *f
+            old_value != *f
         })
     }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
 }
 
 #[cfg(test)]
@@ -117,13 +200,13 @@ mod tests {
     fn default_all_zero() {
         let rom = Rom1kx8::default();
         for mem in rom.memory.iter() {
-            assert_eq!(0, *mem);
+            assert_eq!(Some(0), mem.as_u128());
         }
     }
 
     #[test]
     fn read_out_all_data() {
-        let mut rom: Rom1kx8 = (0..=255).cycle().collect();
+        let mut rom = Rom1kx8::try_from_iter((0..=255).cycle()).unwrap();
         let mut addr = Port::<LogicVector, Output>::new(LogicVector::from_ieee(Ieee1164::_0, 10));
         let data = Port::<LogicVector, Input>::new(LogicVector::with_width(8));
         let noe = Port::<Ieee1164, Output>::new(Ieee1164::_0);
@@ -160,7 +243,7 @@ mod tests {
     fn output() {
         let mut rom = Rom1kx8::default();
         for (i, m) in rom.memory.iter_mut().enumerate() {
-            *m = i as u8;
+            *m = LogicVector::from_int(i as u128 & 0xFF, 8).unwrap();
         }
         let mut addr = Port::<LogicVector, Output>::new(LogicVector::from_ieee(Ieee1164::_0, 10));
         let data = Port::<LogicVector, Input>::new(LogicVector::with_width(8));
@@ -193,4 +276,69 @@ mod tests {
             assert_eq!(data.value(), i & 0xFF);
         }
     }
+
+    #[test]
+    fn short_or_wrong_contents() {
+        assert_eq!(
+            Err(RomError::WrongDepth(1023)),
+            Rom1kx8::try_from_iter((0..1023).map(|i| i % 256)).map(|_| ())
+        );
+        assert_eq!(
+            Err(RomError::WrongWidth(256)),
+            Rom1kx8::try_from_iter(0..).map(|_| ())
+        );
+        assert_eq!(
+            Err(RomError::WrongDepth(256)),
+            Rom1kx8::try_from_iter(0..256).map(|_| ())
+        );
+
+        let words = vec![LogicVector::with_width(4), LogicVector::with_width(5)];
+        assert_eq!(
+            Err(RomError::WrongWidth(1)),
+            Rom::<2, 4>::try_from(words).map(|_| ())
+        );
+        assert_eq!(
+            Err(RomError::WrongDepth(0)),
+            Rom::<2, 4>::try_from(vec![]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn any_depth_and_width() {
+        let words = [
+            LogicVector::from_ieee(Ieee1164::_1, 200),
+            "Z".repeat(200).parse().unwrap(),
+        ];
+        let mut rom =
+            Rom::<3, 200>::try_from(vec![words[0].clone(), words[1].clone(), words[0].clone()])
+                .unwrap();
+        assert_eq!(2, rom.addr_width());
+        assert_eq!(1, Rom::<1, 1>::default().addr_width());
+
+        let mut addr = Port::<LogicVector, Output>::new(LogicVector::from_int(1, 2).unwrap());
+        let data = Port::<LogicVector, Input>::new(LogicVector::with_width(200));
+        let enable = Port::<Ieee1164, Output>::new(Ieee1164::_0);
+        let mut sig_enable = Signal::default();
+        sig_enable.connect(&enable).unwrap();
+        sig_enable.connect(&rom.n_output_enable).unwrap();
+        sig_enable.connect(&rom.n_chip_select).unwrap();
+        let mut sig_addr = Signal::default();
+        sig_addr.connect(&addr).unwrap();
+        sig_addr.connect(&rom.addr).unwrap();
+        let mut sig_data = Signal::default();
+        sig_data.connect(&rom.data).unwrap();
+        sig_data.connect(&data).unwrap();
+
+        sig_enable.update();
+        sig_addr.update();
+        rom.update();
+        sig_data.update();
+        assert_eq!(words[1], data.value());
+
+        addr.replace(LogicVector::from_int(3, 2).unwrap());
+        sig_addr.update();
+        rom.update();
+        sig_data.update();
+        assert_eq!(LogicVector::from_ieee(Ieee1164::_X, 200), data.value());
+    }
 }
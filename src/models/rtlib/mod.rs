@@ -2,5 +2,7 @@
 //! TODO
 
 pub mod arithmic;
+pub mod combinational;
 pub mod inputs;
 pub mod memory;
+pub mod sequential;
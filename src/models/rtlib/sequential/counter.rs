@@ -0,0 +1,366 @@
+use super::{advance, select, shift_in, shift_out, Edge, Storable};
+use crate::direction::{Input, Output};
+use crate::models::{checked, drive};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// A synchronous binary up/down counter with parallel load, clock enable and terminal count, like
+/// the classic 74x163 and 74x191 counters.
+///
+/// On a rising edge of [`Counter::clk`] the counter stores [`Counter::d`] if [`Counter::load`] is
+/// high, regardless of [`Counter::enable`]. Otherwise it counts up if [`Counter::up`] is high and
+/// down if it is low, as long as `enable` is high. The counter wraps around in both directions.
+///
+/// [`Counter::tc`] is high if the counter holds its last value in the current direction, i.e. all
+/// ones when counting up and all zeros when counting down, so the next count wraps around. It can
+/// be used to cascade counters.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::sequential::Counter;
+///
+/// let counter = Counter::with_width(4);
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let mut reset = Port::<_, Output>::new(Ieee1164::_1);
+/// let q = Port::<_, Input>::new(LogicVector::with_width(4));
+/// let tc = Port::<_, Input>::default();
+///
+/// let mut circuit = circuit!(
+///     signal!(clk, counter.clk),
+///     signal!(reset, counter.reset),
+///     signal!(counter.q, q),
+///     signal!(counter.tc, tc)
+/// );
+/// circuit.add(counter);
+///
+/// circuit.step().unwrap();
+/// reset.replace(Ieee1164::_0);
+/// for _ in 0..15 {
+///     clk.replace(Ieee1164::_1);
+///     circuit.step().unwrap();
+///     clk.replace(Ieee1164::_0);
+///     circuit.step().unwrap();
+/// }
+/// assert_eq!(Some(15), q.value().as_u128());
+/// assert_eq!(Ieee1164::_1, tc.value());
+/// ```
+#[derive(Debug, Component)]
+pub struct Counter {
+    /// Data input `Port`, which is stored if `load` is high
+    #[port(input)]
+    pub d: Port<LogicVector, Input>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Active-high synchronous load `Port`, inactive by default
+    #[port(input)]
+    pub load: Port<Ieee1164, Input>,
+    /// Active-high count enable `Port`, active by default
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+    /// Direction `Port`, counts up if high and down if low, high by default
+    #[port(input)]
+    pub up: Port<Ieee1164, Input>,
+    /// Output `Port`, holds the current count
+    #[port(output)]
+    pub q: Port<LogicVector, Output>,
+    /// Terminal count output `Port`
+    #[port(output)]
+    pub tc: Port<Ieee1164, Output>,
+    state: LogicVector,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl Counter {
+    /// Creates a counter whose data input and output are `width` bits wide.
+    pub fn with_width(width: usize) -> Self {
+        Self {
+            d: Port::new(LogicVector::with_width(width)),
+            clk: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            load: Port::new(Ieee1164::_0),
+            enable: Port::new(Ieee1164::_1),
+            up: Port::new(Ieee1164::_1),
+            q: Port::new(LogicVector::with_width(width)),
+            tc: Port::default(),
+            state: LogicVector::with_width(width),
+            last_clk: Ieee1164::_U,
+            _private: (),
+        }
+    }
+}
+
+/// Returns whether `q` is the last value before wrapping around in the direction `up`.
+fn terminal_count(q: &LogicVector, up: Ieee1164) -> Ieee1164 {
+    let bits = || (0..q.width()).map(|i| q.get(i).unwrap());
+    let all_ones = bits().fold(Ieee1164::_1, |tc, b| tc & b);
+    let all_zeros = bits().fold(Ieee1164::_1, |tc, b| tc & !b);
+    select(up, &all_zeros, &all_ones).to_x01()
+}
+
+impl Updateable for Counter {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        let q = &self.state;
+        let d = checked(self.d.value(), q.width());
+        let (load, up) = (self.load.value(), self.up.value());
+        let one = LogicVector::from_int(1, q.width()).unwrap();
+        let counted = select(up, &q.wrapping_sub(&one), &q.wrapping_add(&one));
+        let next = select(load, &select(self.enable.value(), q, &counted), &d);
+        // `load` overrides `enable`, so the enable is already taken into account
+        self.state = advance(
+            edge,
+            q,
+            &next,
+            Ieee1164::_1,
+            self.reset.value(),
+            &q.filled(Ieee1164::_0),
+        );
+
+        let tc = terminal_count(&self.state, up);
+        let q_changed = drive(&mut self.q, self.state.clone());
+        drive(&mut self.tc, tc) || q_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        // the terminal count follows the direction without waiting for the clock
+        vec![self.clk.id(), self.reset.id(), self.up.id()]
+    }
+}
+
+macro_rules! create_shift_counter {
+    ($name:ident, $init:expr, $next:expr, $doc:tt) => {
+        #[derive(Debug, Component)]
+        #[doc = $doc]
+        pub struct $name {
+            /// Clock input `Port`
+            #[port(input)]
+            pub clk: Port<Ieee1164, Input>,
+            /// Active-high asynchronous reset `Port`, inactive by default
+            #[port(input)]
+            pub reset: Port<Ieee1164, Input>,
+            /// Active-high clock enable `Port`, active by default
+            #[port(input)]
+            pub enable: Port<Ieee1164, Input>,
+            /// Output `Port`, holds the current state
+            #[port(output)]
+            pub q: Port<LogicVector, Output>,
+            state: LogicVector,
+            last_clk: Ieee1164,
+            _private: (),
+        }
+
+        impl $name {
+            /// Creates this counter with `width` bits, which is also the number of states of a
+            /// ring counter and half the number of states of a Johnson counter.
+            pub fn with_width(width: usize) -> Self {
+                Self {
+                    clk: Port::default(),
+                    reset: Port::new(Ieee1164::_0),
+                    enable: Port::new(Ieee1164::_1),
+                    q: Port::new(LogicVector::with_width(width)),
+                    state: LogicVector::with_width(width),
+                    last_clk: Ieee1164::_U,
+                    _private: (),
+                }
+            }
+        }
+
+        impl Updateable for $name {
+            fn update(&mut self) -> bool {
+                let clk = self.clk.value();
+                let edge = Edge::detect(self.last_clk, clk);
+                self.last_clk = clk;
+
+                let init: fn(usize) -> LogicVector = $init;
+                let next: fn(&LogicVector) -> LogicVector = $next;
+                let q = &self.state;
+                self.state = advance(
+                    edge,
+                    q,
+                    &next(q),
+                    self.enable.value(),
+                    self.reset.value(),
+                    &init(q.width()),
+                );
+                drive(&mut self.q, self.state.clone())
+            }
+
+            fn sensitivity(&self) -> Vec<PortId> {
+                self.input_ids()
+            }
+
+            fn drives(&self) -> Vec<PortId> {
+                self.output_ids()
+            }
+
+            fn combinational_inputs(&self) -> Vec<PortId> {
+                vec![self.clk.id(), self.reset.id()]
+            }
+        }
+    };
+}
+
+create_shift_counter!(
+    RingCounter,
+    |width| LogicVector::from_int(1, width).unwrap(),
+    |q| q.rotate_left(1),
+    "A ring counter, that rotates a single high bit through its [`LogicVector`] towards the most \
+     significant bit on every rising edge of its clock. The reset sets the least significant bit, \
+     e.g. a 4 bit ring counter runs through `0001`, `0010`, `0100` and `1000`."
+);
+
+create_shift_counter!(
+    JohnsonCounter,
+    |width| LogicVector::from_ieee(Ieee1164::_0, width),
+    |q| shift_in(q, !shift_out(q)),
+    "A Johnson (or twisted ring) counter, that shifts its [`LogicVector`] towards the most \
+     significant bit on every rising edge of its clock and feeds the inverted most significant \
+     bit back. The reset clears all bits, e.g. a 3 bit Johnson counter runs through `000`, `001`, \
+     `011`, `111`, `110` and `100`."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    fn cycle(circuit: &mut Circuit, clk: &mut Port<Ieee1164, Output>) {
+        clk.replace(_1);
+        circuit.step().unwrap();
+        clk.replace(_0);
+        circuit.step().unwrap();
+    }
+
+    #[test]
+    fn count_load_and_direction() {
+        let counter = Counter::with_width(3);
+        let mut d = Port::<_, Output>::new(LogicVector::from_int(6, 3).unwrap());
+        let mut clk = Port::<_, Output>::new(_0);
+        let mut load = Port::<_, Output>::new(_1);
+        let mut enable = Port::<_, Output>::new(_0);
+        let mut up = Port::<_, Output>::new(_1);
+        let q = Port::<_, Input>::new(LogicVector::with_width(3));
+        let tc = Port::<_, Input>::default();
+
+        let mut circuit = circuit!(
+            signal!(d, counter.d),
+            signal!(clk, counter.clk),
+            signal!(load, counter.load),
+            signal!(enable, counter.enable),
+            signal!(up, counter.up),
+            signal!(counter.q, q),
+            signal!(counter.tc, tc)
+        );
+        circuit.add(counter);
+        circuit.step().unwrap();
+        assert_eq!(_X, tc.value());
+
+        // loading does not need the enable
+        cycle(&mut circuit, &mut clk);
+        assert_eq!((Some(6), _0), (q.value().as_u128(), tc.value()));
+
+        load.replace(_0);
+        cycle(&mut circuit, &mut clk);
+        assert_eq!(Some(6), q.value().as_u128());
+
+        enable.replace(_1);
+        cycle(&mut circuit, &mut clk);
+        assert_eq!((Some(7), _1), (q.value().as_u128(), tc.value()));
+        cycle(&mut circuit, &mut clk);
+        assert_eq!((Some(0), _0), (q.value().as_u128(), tc.value()));
+
+        // the terminal count follows the direction immediately
+        up.replace(_0);
+        circuit.step().unwrap();
+        assert_eq!(_1, tc.value());
+        cycle(&mut circuit, &mut clk);
+        assert_eq!((Some(7), _0), (q.value().as_u128(), tc.value()));
+
+        // either 6 or 0
+        up.replace(_X);
+        cycle(&mut circuit, &mut clk);
+        assert_eq!(("XX0".to_string(), _X), (q.value().to_string(), tc.value()));
+
+        d.replace(LogicVector::from_int(0, 3).unwrap());
+        load.replace(_1);
+        cycle(&mut circuit, &mut clk);
+        assert_eq!((Some(0), _X), (q.value().as_u128(), tc.value()));
+    }
+
+    #[test]
+    fn ring_and_johnson() {
+        let ring = RingCounter::with_width(3);
+        let johnson = JohnsonCounter::with_width(3);
+        let mut clk = Port::<_, Output>::new(_0);
+        let mut reset = Port::<_, Output>::new(_1);
+        let mut enable = Port::<_, Output>::new(_1);
+        let ring_q = Port::<_, Input>::new(LogicVector::with_width(3));
+        let johnson_q = Port::<_, Input>::new(LogicVector::with_width(3));
+
+        let mut circuit = circuit!(
+            signal!(clk, ring.clk, johnson.clk),
+            signal!(reset, ring.reset, johnson.reset),
+            signal!(enable, ring.enable, johnson.enable),
+            signal!(ring.q, ring_q),
+            signal!(johnson.q, johnson_q)
+        );
+        circuit.add(ring);
+        circuit.add(johnson);
+        circuit.step().unwrap();
+        reset.replace(_0);
+
+        let mut states = vec![];
+        for _ in 0..6 {
+            states.push((ring_q.value().to_string(), johnson_q.value().to_string()));
+            cycle(&mut circuit, &mut clk);
+        }
+        let expected = [
+            ("001", "000"),
+            ("010", "001"),
+            ("100", "011"),
+            ("001", "111"),
+            ("010", "110"),
+            ("100", "100"),
+        ];
+        assert_eq!(
+            expected
+                .iter()
+                .map(|(r, j)| (r.to_string(), j.to_string()))
+                .collect::<Vec<_>>(),
+            states
+        );
+
+        enable.replace(_0);
+        cycle(&mut circuit, &mut clk);
+        assert_eq!(
+            ("001", "000"),
+            (
+                &*ring_q.value().to_string(),
+                &*johnson_q.value().to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use super::{advance, reset, select, Edge};
+use crate::direction::{Input, Output};
+use crate::models::drive;
+use crate::Component;
+use crate::{Ieee1164, Port, PortId, Updateable};
+
+/// A rising edge triggered D flip-flop with an asynchronous reset, a synchronous reset and a clock
+/// enable.
+///
+/// On a rising edge of [`DFlipFlop::clk`] the value of [`DFlipFlop::d`] is stored and outputted on
+/// [`DFlipFlop::q`], if [`DFlipFlop::enable`] is high. A high [`DFlipFlop::sync_reset`] stores
+/// [`Ieee1164::_0`] instead. A high [`DFlipFlop::reset`] sets the output to `0` immediately,
+/// regardless of the clock.
+///
+/// If one of the control inputs is neither high nor low, the output becomes [`Ieee1164::_X`],
+/// unless it would be the same for both possible values of that input. See the
+/// [module documentation](crate::models::rtlib::sequential) for how the clock edges are detected.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::sequential::DFlipFlop;
+///
+/// let mut d = Port::<_, Output>::new(Ieee1164::_1);
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let q = Port::<_, Input>::default();
+/// let dff = DFlipFlop::default();
+///
+/// let mut circuit = circuit!(signal!(d, dff.d), signal!(clk, dff.clk), signal!(dff.q, q));
+/// circuit.add(dff);
+///
+/// circuit.step().unwrap();
+/// assert_eq!(Ieee1164::_U, q.value());
+///
+/// clk.replace(Ieee1164::_1);
+/// circuit.step().unwrap();
+/// assert_eq!(Ieee1164::_1, q.value());
+///
+/// d.replace(Ieee1164::_0);
+/// clk.replace(Ieee1164::_0);
+/// circuit.step().unwrap();
+/// assert_eq!(Ieee1164::_1, q.value());
+/// ```
+#[derive(Debug, Component)]
+pub struct DFlipFlop {
+    /// Data input `Port`
+    #[port(input)]
+    pub d: Port<Ieee1164, Input>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Active-high synchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub sync_reset: Port<Ieee1164, Input>,
+    /// Active-high clock enable `Port`, active by default
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+    /// Output `Port`, holds the stored value
+    #[port(output)]
+    pub q: Port<Ieee1164, Output>,
+    state: Ieee1164,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl Default for DFlipFlop {
+    fn default() -> Self {
+        Self {
+            d: Port::default(),
+            clk: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            sync_reset: Port::new(Ieee1164::_0),
+            enable: Port::new(Ieee1164::_1),
+            q: Port::default(),
+            state: Ieee1164::_U,
+            last_clk: Ieee1164::_U,
+            _private: (),
+        }
+    }
+}
+
+impl Updateable for DFlipFlop {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        let (q, d) = (&self.state, self.d.value());
+        let next = reset(self.sync_reset.value(), &select(self.enable.value(), q, &d));
+        // the synchronous reset overrides `enable`, so the enable is already taken into account
+        self.state = advance(
+            edge,
+            q,
+            &next,
+            Ieee1164::_1,
+            self.reset.value(),
+            &Ieee1164::_0,
+        );
+        drive(&mut self.q, self.state)
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.reset.id()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _H: Ieee1164 = Ieee1164::_H;
+    const _U: Ieee1164 = Ieee1164::_U;
+    const _X: Ieee1164 = Ieee1164::_X;
+    const _Z: Ieee1164 = Ieee1164::_Z;
+
+    struct Bench {
+        circuit: Circuit,
+        d: Port<Ieee1164, Output>,
+        clk: Port<Ieee1164, Output>,
+        reset: Port<Ieee1164, Output>,
+        sync_reset: Port<Ieee1164, Output>,
+        enable: Port<Ieee1164, Output>,
+        q: Port<Ieee1164, Input>,
+    }
+
+    impl Bench {
+        fn new() -> Self {
+            let dff = DFlipFlop::default();
+            let d = Port::new(_1);
+            let clk = Port::new(_0);
+            let reset = Port::new(_0);
+            let sync_reset = Port::new(_0);
+            let enable = Port::new(_1);
+            let q = Port::default();
+            let mut circuit = circuit!(
+                signal!(d, dff.d),
+                signal!(clk, dff.clk),
+                signal!(reset, dff.reset),
+                signal!(sync_reset, dff.sync_reset),
+                signal!(enable, dff.enable),
+                signal!(dff.q, q)
+            );
+            circuit.add(dff);
+            circuit.step().unwrap();
+            Self {
+                circuit,
+                d,
+                clk,
+                reset,
+                sync_reset,
+                enable,
+                q,
+            }
+        }
+
+        fn clock(&mut self, value: Ieee1164) -> Ieee1164 {
+            self.clk.replace(value);
+            self.circuit.step().unwrap();
+            self.q.value()
+        }
+    }
+
+    #[test]
+    fn rising_edge_stores() {
+        let mut bench = Bench::new();
+        assert_eq!(_U, bench.q.value());
+        assert_eq!(_1, bench.clock(_1));
+
+        bench.d.replace(_0);
+        assert_eq!(_1, bench.clock(_H));
+        assert_eq!(_1, bench.clock(_0));
+        assert_eq!(_0, bench.clock(_H));
+    }
+
+    #[test]
+    fn x_on_clock() {
+        let mut bench = Bench::new();
+        bench.clock(_1);
+        bench.clock(_0);
+
+        // the stored value does not change, so an uncertain edge does not matter
+        assert_eq!(_1, bench.clock(_X));
+        assert_eq!(_1, bench.clock(_1));
+
+        bench.d.replace(_0);
+        bench.clock(_0);
+        assert_eq!(_X, bench.clock(_Z));
+        assert_eq!(_X, bench.clock(_1));
+
+        // falling edges never store
+        bench.d.replace(_1);
+        assert_eq!(_X, bench.clock(_X));
+        assert_eq!(_X, bench.clock(_0));
+        assert_eq!(_1, bench.clock(_1));
+    }
+
+    #[test]
+    fn resets_and_enable() {
+        let mut bench = Bench::new();
+        bench.enable.replace(_0);
+        bench.reset.replace(_1);
+        assert_eq!(_0, bench.clock(_0));
+        assert_eq!(_0, bench.clock(_1));
+
+        bench.reset.replace(_0);
+        bench.clock(_0);
+        assert_eq!(_0, bench.clock(_1));
+
+        bench.enable.replace(_X);
+        bench.clock(_0);
+        assert_eq!(_X, bench.clock(_1));
+
+        bench.enable.replace(_1);
+        bench.clock(_0);
+        assert_eq!(_1, bench.clock(_1));
+
+        bench.sync_reset.replace(_1);
+        assert_eq!(_1, bench.clock(_0));
+        assert_eq!(_0, bench.clock(_1));
+
+        bench.sync_reset.replace(_0);
+        bench.clock(_0);
+        assert_eq!(_1, bench.clock(_1));
+
+        bench.reset.replace(_X);
+        assert_eq!(_X, bench.clock(_0));
+        bench.d.replace(_0);
+        assert_eq!(_0, bench.clock(_1));
+    }
+}
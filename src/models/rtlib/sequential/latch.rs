@@ -0,0 +1,145 @@
+use super::{reset, select};
+use crate::direction::{Input, Output};
+use crate::Component;
+use crate::{Ieee1164, Port, PortId, Updateable};
+
+/// A level-sensitive D latch with an asynchronous reset.
+///
+/// As long as [`Latch::enable`] is high the latch is transparent, i.e. [`Latch::d`] is outputted
+/// on [`Latch::q`]. If `enable` is driven low, the last value is held. If `enable` is neither high
+/// nor low, the output becomes [`Ieee1164::_X`], unless `d` and `q` have the same value. A high
+/// [`Latch::reset`] sets the output to [`Ieee1164::_0`].
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::sequential::Latch;
+///
+/// let mut d = Port::<_, Output>::new(Ieee1164::_1);
+/// let mut enable = Port::<_, Output>::new(Ieee1164::_1);
+/// let q = Port::<_, Input>::default();
+/// let latch = Latch::default();
+///
+/// let mut circuit = circuit!(signal!(d, latch.d), signal!(enable, latch.enable), signal!(latch.q, q));
+/// circuit.add(latch);
+///
+/// circuit.step().unwrap();
+/// assert_eq!(Ieee1164::_1, q.value());
+///
+/// enable.replace(Ieee1164::_0);
+/// d.replace(Ieee1164::_0);
+/// circuit.step().unwrap();
+/// assert_eq!(Ieee1164::_1, q.value());
+/// ```
+#[derive(Debug, Component)]
+#[non_exhaustive]
+pub struct Latch {
+    /// Data input `Port`
+    #[port(input)]
+    pub d: Port<Ieee1164, Input>,
+    /// Active-high enable `Port`, the latch is transparent while this is high
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Output `Port`, holds the stored value
+    #[port(output)]
+    pub q: Port<Ieee1164, Output>,
+}
+
+impl Default for Latch {
+    fn default() -> Self {
+        Self {
+            d: Port::default(),
+            enable: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            q: Port::default(),
+        }
+    }
+}
+
+impl Updateable for Latch {
+    fn update(&mut self) -> bool {
+        let d = self.d.value();
+        let enable = self.enable.value();
+        let async_reset = self.reset.value();
+
+        self.q.with_value_mut(|q| {
+            let next = reset(async_reset, &select(enable, q, &d));
+            let changed = *q != next;
+            *q = next;
+            changed
+        })
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _L: Ieee1164 = Ieee1164::_L;
+    const _U: Ieee1164 = Ieee1164::_U;
+    const _W: Ieee1164 = Ieee1164::_W;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    #[test]
+    fn transparent_and_hold() {
+        let latch = Latch::default();
+        let mut d = Port::<_, Output>::new(_0);
+        let mut enable = Port::<_, Output>::new(_0);
+        let mut reset = Port::<_, Output>::new(_0);
+        let q = Port::<_, Input>::default();
+        let mut circuit = circuit!(
+            signal!(d, latch.d),
+            signal!(enable, latch.enable),
+            signal!(reset, latch.reset),
+            signal!(latch.q, q)
+        );
+        circuit.add(latch);
+
+        circuit.step().unwrap();
+        assert_eq!(_U, q.value());
+
+        enable.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!(_0, q.value());
+
+        d.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!(_1, q.value());
+
+        enable.replace(_L);
+        d.replace(_0);
+        circuit.step().unwrap();
+        assert_eq!(_1, q.value());
+
+        enable.replace(_W);
+        circuit.step().unwrap();
+        assert_eq!(_X, q.value());
+
+        d.replace(_1);
+        enable.replace(_1);
+        circuit.step().unwrap();
+        enable.replace(_X);
+        circuit.step().unwrap();
+        assert_eq!(_1, q.value());
+
+        reset.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!(_0, q.value());
+    }
+}
@@ -0,0 +1,260 @@
+use super::{advance, shift_in, shift_out, Edge};
+use crate::direction::{Input, Output};
+use crate::models::{checked, drive};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// The structure of an [`Lfsr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LfsrKind {
+    /// The tapped bits are XORed together and the result is shifted in (external feedback)
+    Fibonacci,
+    /// The bit that is shifted out is XORed into the tapped bits (internal feedback)
+    Galois,
+}
+
+/// A linear-feedback shift register, that generates a pseudo random bit sequence.
+///
+/// The feedback is described by the exponents of its polynomial, e.g. the taps `[16, 14, 13, 11]`
+/// stand for `x^16 + x^14 + x^13 + x^11 + 1`. If the polynomial is primitive, the register runs
+/// through all `2^width - 1` values except zero, no matter whether it is built as a
+/// [`LfsrKind::Fibonacci`] or a [`LfsrKind::Galois`] register. Both kinds shift towards the most
+/// significant bit, which is outputted on [`Lfsr::serial_out`].
+///
+/// An all-zero state never changes, that is why the reset loads [`Lfsr::seed`] instead of
+/// clearing the register. The seed is `1` if left unconnected.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::sequential::{Lfsr, LfsrKind};
+///
+/// let lfsr = Lfsr::new(4, LfsrKind::Galois, &[4, 3]).unwrap();
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let mut reset = Port::<_, Output>::new(Ieee1164::_1);
+/// let q = Port::<_, Input>::new(LogicVector::with_width(4));
+///
+/// let mut circuit = circuit!(signal!(clk, lfsr.clk), signal!(reset, lfsr.reset), signal!(lfsr.q, q));
+/// circuit.add(lfsr);
+///
+/// circuit.step().unwrap();
+/// reset.replace(Ieee1164::_0);
+/// let mut period = 0;
+/// loop {
+///     clk.replace(Ieee1164::_1);
+///     circuit.step().unwrap();
+///     clk.replace(Ieee1164::_0);
+///     circuit.step().unwrap();
+///     period += 1;
+///     if q.value().as_u128() == Some(1) {
+///         break;
+///     }
+/// }
+/// assert_eq!(15, period);
+/// ```
+#[derive(Debug, Component)]
+pub struct Lfsr {
+    /// Seed input `Port`, which is loaded while `reset` is active
+    #[port(input)]
+    pub seed: Port<LogicVector, Input>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Active-high clock enable `Port`, active by default
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+    /// Serial output `Port`, the most significant bit of `q`
+    #[port(output)]
+    pub serial_out: Port<Ieee1164, Output>,
+    /// Output `Port`, holds the current state
+    #[port(output)]
+    pub q: Port<LogicVector, Output>,
+    state: LogicVector,
+    kind: LfsrKind,
+    taps: Vec<usize>,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl Lfsr {
+    /// Creates a `width` bit register of the given `kind` with the feedback polynomial `taps`.
+    ///
+    /// Returns `None` if `width` is `0`, if a tap is `0` or greater than `width`, or if `width`
+    /// itself is not one of the taps.
+    pub fn new(width: usize, kind: LfsrKind, taps: &[usize]) -> Option<Self> {
+        if width == 0 || !taps.contains(&width) || taps.iter().any(|&t| t == 0 || t > width) {
+            return None;
+        }
+        let mut taps = taps.to_vec();
+        taps.sort_unstable_by(|a, b| b.cmp(a));
+        taps.dedup();
+        Some(Self {
+            seed: Port::new(LogicVector::from_int(1, width).unwrap()),
+            clk: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            enable: Port::new(Ieee1164::_1),
+            serial_out: Port::default(),
+            q: Port::new(LogicVector::with_width(width)),
+            state: LogicVector::with_width(width),
+            kind,
+            taps,
+            last_clk: Ieee1164::_U,
+            _private: (),
+        })
+    }
+
+    /// Returns the structure of this register.
+    pub fn kind(&self) -> LfsrKind {
+        self.kind
+    }
+
+    /// Returns the exponents of the feedback polynomial, the highest first.
+    pub fn taps(&self) -> &[usize] {
+        &self.taps
+    }
+
+    fn next(&self) -> LogicVector {
+        let q = &self.state;
+        match self.kind {
+            LfsrKind::Fibonacci => {
+                let feedback = self.taps.iter().map(|&t| q.get(t - 1).unwrap());
+                shift_in(q, feedback.fold(Ieee1164::_0, |f, b| f ^ b))
+            }
+            LfsrKind::Galois => {
+                let out = shift_out(q);
+                let mut next = shift_in(q, out);
+                for &t in self.taps.iter().filter(|&&t| t < q.width()) {
+                    next.set(t, next.get(t).unwrap() ^ out);
+                }
+                next
+            }
+        }
+    }
+}
+
+impl Updateable for Lfsr {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        let seed = checked(self.seed.value(), self.state.width());
+        let next = self.next();
+        self.state = advance(
+            edge,
+            &self.state,
+            &next,
+            self.enable.value(),
+            self.reset.value(),
+            &seed,
+        );
+
+        let out_changed = drive(&mut self.serial_out, shift_out(&self.state));
+        drive(&mut self.q, self.state.clone()) || out_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.reset.id()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+
+    #[test]
+    fn invalid_taps() {
+        assert!(Lfsr::new(0, LfsrKind::Fibonacci, &[0]).is_none());
+        assert!(Lfsr::new(4, LfsrKind::Fibonacci, &[3]).is_none());
+        assert!(Lfsr::new(4, LfsrKind::Galois, &[4, 5]).is_none());
+        assert!(Lfsr::new(4, LfsrKind::Galois, &[4, 0]).is_none());
+        assert_eq!(
+            &[4, 3, 1],
+            Lfsr::new(4, LfsrKind::Galois, &[1, 4, 3, 4])
+                .unwrap()
+                .taps()
+        );
+    }
+
+    /// Returns the states after reset until the seed repeats, at most `limit` of them
+    fn sequence(
+        kind: LfsrKind,
+        width: usize,
+        taps: &[usize],
+        seed: u128,
+        limit: usize,
+    ) -> Vec<u128> {
+        let lfsr = Lfsr::new(width, kind, taps).unwrap();
+        let seed = Port::<_, Output>::new(LogicVector::from_int(seed, width).unwrap());
+        let mut clk = Port::<_, Output>::new(_0);
+        let mut reset = Port::<_, Output>::new(_1);
+        let q = Port::<_, Input>::new(LogicVector::with_width(width));
+
+        let mut circuit = circuit!(
+            signal!(seed, lfsr.seed),
+            signal!(clk, lfsr.clk),
+            signal!(reset, lfsr.reset),
+            signal!(lfsr.q, q)
+        );
+        circuit.add(lfsr);
+        circuit.step().unwrap();
+        reset.replace(_0);
+        circuit.step().unwrap();
+
+        let first = q.value().as_u128().unwrap();
+        let mut states = vec![first];
+        while states.len() < limit {
+            clk.replace(_1);
+            circuit.step().unwrap();
+            clk.replace(_0);
+            circuit.step().unwrap();
+            match q.value().as_u128().unwrap() {
+                s if s == first => break,
+                s => states.push(s),
+            }
+        }
+        states
+    }
+
+    #[test]
+    fn maximal_length() {
+        for &kind in &[LfsrKind::Fibonacci, LfsrKind::Galois] {
+            let mut states = sequence(kind, 4, &[4, 3], 0b1001, 20);
+            states.sort_unstable();
+            assert_eq!((1..16).collect::<Vec<_>>(), states);
+
+            assert_eq!(255, sequence(kind, 8, &[8, 6, 5, 4], 1, 300).len());
+        }
+    }
+
+    #[test]
+    fn fibonacci_and_galois_differ() {
+        assert_eq!(
+            vec![1, 2, 4, 9, 3],
+            sequence(LfsrKind::Fibonacci, 4, &[4, 3], 1, 5)
+        );
+        assert_eq!(
+            vec![1, 2, 4, 8, 9],
+            sequence(LfsrKind::Galois, 4, &[4, 3], 1, 5)
+        );
+        // a zero seed locks the register
+        assert_eq!(vec![0], sequence(LfsrKind::Galois, 4, &[4, 3], 0, 5));
+    }
+}
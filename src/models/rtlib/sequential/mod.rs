@@ -0,0 +1,152 @@
+//! This module consists of sequential models, that store a value, e.g. flip-flops, latches,
+//! registers, counters, shift registers and linear-feedback shift registers.
+//!
+//! All clocked models in here trigger on the rising edge of their clock. An edge from
+//! [`Ieee1164::_0`] to [`Ieee1164::_1`] (or their weak counterparts) stores the input, while an
+//! uncertain edge, e.g. from `0` to [`Ieee1164::_X`] or from `X` to `1`, makes the stored value
+//! `X` unless the new value is the same as the old one. A clock coming from [`Ieee1164::_U`] never
+//! triggers, so an uninitialized clock does not produce an edge.
+//!
+//! Ports that are not connected to a [`Signal`](crate::Signal) keep their initial value, which
+//! means the reset ports are inactive (`0`) and the enable ports are active (`1`) if left
+//! unconnected.
+//!
+//! Models with more state than a single register, e.g. a [`Counter`] or an [`Lfsr`], output their
+//! whole state on a `q` port, so it is included when the model is dumped, see
+//! [`Vcd::serialize_ports`](crate::dump::Vcd::serialize_ports).
+
+mod counter;
+mod dff;
+mod latch;
+mod lfsr;
+mod register;
+mod shift;
+
+pub use self::counter::{Counter, JohnsonCounter, RingCounter};
+pub use self::dff::DFlipFlop;
+pub use self::latch::Latch;
+pub use self::lfsr::{Lfsr, LfsrKind};
+pub use self::register::Register;
+pub use self::shift::{PisoShiftRegister, SipoShiftRegister, SisoShiftRegister};
+
+use crate::{Ieee1164, LogicVector};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Edge {
+    None,
+    Rising,
+    Uncertain,
+}
+
+impl Edge {
+    pub(crate) fn detect(previous: Ieee1164, current: Ieee1164) -> Edge {
+        if previous.is_U() {
+            return Edge::None;
+        }
+        let (previous, current) = (previous.to_x01(), current.to_x01());
+        if previous == current || previous == Ieee1164::_1 || current == Ieee1164::_0 {
+            Edge::None
+        } else if previous == Ieee1164::_0 && current == Ieee1164::_1 {
+            Edge::Rising
+        } else {
+            Edge::Uncertain
+        }
+    }
+}
+
+/// Values that can be stored by a sequential model.
+pub(crate) trait Storable: Clone + PartialEq {
+    /// Returns a value with the same shape as `self`, but all bits set to `value`.
+    fn filled(&self, value: Ieee1164) -> Self;
+
+    /// Returns the value that is stored, if it is unknown whether `self` or `other` is stored.
+    /// Bits that agree keep their value, all other bits become [`Ieee1164::_X`].
+    fn merge(&self, other: &Self) -> Self;
+}
+
+impl Storable for Ieee1164 {
+    fn filled(&self, value: Ieee1164) -> Self {
+        value
+    }
+
+    fn merge(&self, other: &Self) -> Self {
+        if self == other {
+            *self
+        } else if self.to_x01() == other.to_x01() {
+            self.to_x01()
+        } else {
+            Ieee1164::_X
+        }
+    }
+}
+
+impl Storable for LogicVector {
+    fn filled(&self, value: Ieee1164) -> Self {
+        LogicVector::from_ieee(value, self.width())
+    }
+
+    fn merge(&self, other: &Self) -> Self {
+        if self.width() != other.width() {
+            return self.filled(Ieee1164::_X);
+        }
+        let mut merged = self.clone();
+        for i in 0..self.width() {
+            merged.set(i, self.get(i).unwrap().merge(&other.get(i).unwrap()));
+        }
+        merged
+    }
+}
+
+/// Chooses `one` if `select` is high, `zero` if it is low and merges both otherwise.
+fn select<T: Storable>(select: Ieee1164, zero: &T, one: &T) -> T {
+    match select.to_x01() {
+        Ieee1164::_1 => one.clone(),
+        Ieee1164::_0 => zero.clone(),
+        _ => zero.merge(one),
+    }
+}
+
+/// Applies an active-high reset to `value`.
+fn reset<T: Storable>(reset: Ieee1164, value: &T) -> T {
+    select(reset, value, &value.filled(Ieee1164::_0))
+}
+
+/// Calculates the next value of a rising edge triggered storage element with the current value
+/// `q` and the input `d`.
+fn next_state<T: Storable>(edge: Edge, q: &T, d: &T, enable: Ieee1164, sync_reset: Ieee1164) -> T {
+    let sampled = reset(sync_reset, &select(enable, q, d));
+    match edge {
+        Edge::None => q.clone(),
+        Edge::Rising => sampled,
+        Edge::Uncertain => q.merge(&sampled),
+    }
+}
+
+/// Calculates the next value of a rising edge triggered model with the current value `q`, like
+/// [`next_state`]. An active asynchronous reset sets the value to `init` instead.
+fn advance<T: Storable>(
+    edge: Edge,
+    q: &T,
+    next: &T,
+    enable: Ieee1164,
+    async_reset: Ieee1164,
+    init: &T,
+) -> T {
+    select(
+        async_reset,
+        &next_state(edge, q, next, enable, Ieee1164::_0),
+        init,
+    )
+}
+
+/// Shifts `q` one bit towards the most significant bit and shifts `bit` in.
+fn shift_in(q: &LogicVector, bit: Ieee1164) -> LogicVector {
+    let mut shifted = q.shift_left(1);
+    shifted.set(0, bit);
+    shifted
+}
+
+/// Returns the bit that is shifted out of `q` next, which is the most significant one.
+fn shift_out(q: &LogicVector) -> Ieee1164 {
+    q.get(q.width() - 1).unwrap()
+}
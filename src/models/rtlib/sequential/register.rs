@@ -0,0 +1,158 @@
+use super::{advance, reset, select, Edge, Storable};
+use crate::direction::{Input, Output};
+use crate::models::{checked, drive};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// A rising edge triggered register that stores a [`LogicVector`]. It behaves like `width`
+/// [`DFlipFlop`](super::DFlipFlop)s that share their clock, reset and enable `Port`s.
+///
+/// If the width of [`Register::d`] does not match the width of the register, a rising edge stores
+/// [`Ieee1164::_X`] in all bits.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::sequential::Register;
+///
+/// let d = Port::<_, Output>::new(LogicVector::from_int(42, 8).unwrap());
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let q = Port::<_, Input>::new(LogicVector::with_width(8));
+/// let reg = Register::with_width(8);
+///
+/// let mut circuit = circuit!(signal!(d, reg.d), signal!(clk, reg.clk), signal!(reg.q, q));
+/// circuit.add(reg);
+///
+/// circuit.step().unwrap();
+/// clk.replace(Ieee1164::_1);
+/// circuit.step().unwrap();
+/// assert_eq!(Some(42), q.value().as_u128());
+/// ```
+#[derive(Debug, Component)]
+pub struct Register {
+    /// Data input `Port`
+    #[port(input)]
+    pub d: Port<LogicVector, Input>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Active-high synchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub sync_reset: Port<Ieee1164, Input>,
+    /// Active-high clock enable `Port`, active by default
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+    /// Output `Port`, holds the stored value
+    #[port(output)]
+    pub q: Port<LogicVector, Output>,
+    state: LogicVector,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl Register {
+    /// Creates a register whose data input and output are `width` bits wide.
+    pub fn with_width(width: usize) -> Self {
+        Self {
+            d: Port::new(LogicVector::with_width(width)),
+            clk: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            sync_reset: Port::new(Ieee1164::_0),
+            enable: Port::new(Ieee1164::_1),
+            q: Port::new(LogicVector::with_width(width)),
+            state: LogicVector::with_width(width),
+            last_clk: Ieee1164::_U,
+            _private: (),
+        }
+    }
+}
+
+impl Updateable for Register {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        let q = &self.state;
+        let d = checked(self.d.value(), q.width());
+        let next = reset(self.sync_reset.value(), &select(self.enable.value(), q, &d));
+        // the synchronous reset overrides `enable`, so the enable is already taken into account
+        self.state = advance(
+            edge,
+            q,
+            &next,
+            Ieee1164::_1,
+            self.reset.value(),
+            &q.filled(Ieee1164::_0),
+        );
+        drive(&mut self.q, self.state.clone())
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.reset.id()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+    const _X: Ieee1164 = Ieee1164::_X;
+
+    #[test]
+    fn store_and_reset() {
+        let reg = Register::with_width(130);
+        let mut d = Port::<_, Output>::new(LogicVector::from_int(0xdead_beef, 130).unwrap());
+        let mut clk = Port::<_, Output>::new(_0);
+        let mut reset = Port::<_, Output>::new(_0);
+        let q = Port::<_, Input>::new(LogicVector::with_width(130));
+        let mut circuit = circuit!(
+            signal!(d, reg.d),
+            signal!(clk, reg.clk),
+            signal!(reset, reg.reset),
+            signal!(reg.q, q)
+        );
+        circuit.add(reg);
+
+        circuit.step().unwrap();
+        clk.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!(Some(0xdead_beef), q.value().as_u128());
+
+        // only the bits that differ become unknown on an uncertain edge
+        d.replace(LogicVector::from_int(0xdead_0000, 130).unwrap());
+        clk.replace(_0);
+        circuit.step().unwrap();
+        clk.replace(_X);
+        circuit.step().unwrap();
+        assert_eq!(Some("X0XXXXX0XXX0XXXX"), q.value().to_string().get(114..));
+        assert_eq!(Some("1101111010101101"), q.value().to_string().get(98..114));
+
+        d.replace(LogicVector::with_width(3));
+        clk.replace(_0);
+        circuit.step().unwrap();
+        clk.replace(_1);
+        circuit.step().unwrap();
+        assert_eq!(LogicVector::from_ieee(_X, 130), q.value());
+
+        reset.replace(_1);
+        circuit.step().unwrap();
+        assert!(q.value().is_000());
+    }
+}
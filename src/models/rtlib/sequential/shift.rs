@@ -0,0 +1,339 @@
+use super::{advance, select, shift_in, shift_out, Edge, Storable};
+use crate::direction::{Input, Output};
+use crate::models::{checked, drive};
+use crate::Component;
+use crate::{Ieee1164, LogicVector, Port, PortId, Updateable};
+
+/// A serial-in, serial-out shift register, which delays [`SisoShiftRegister::serial_in`] by
+/// `width` clock cycles.
+///
+/// On every rising edge of [`SisoShiftRegister::clk`] the stored bits are shifted towards the most
+/// significant bit, `serial_in` is stored in the least significant bit and the most significant
+/// bit is outputted on [`SisoShiftRegister::serial_out`].
+#[derive(Debug, Component)]
+pub struct SisoShiftRegister {
+    /// Serial data input `Port`
+    #[port(input)]
+    pub serial_in: Port<Ieee1164, Input>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Active-high clock enable `Port`, active by default
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+    /// Serial data output `Port`, the most significant bit of `q`
+    #[port(output)]
+    pub serial_out: Port<Ieee1164, Output>,
+    /// Output `Port`, holds the stored bits
+    #[port(output)]
+    pub q: Port<LogicVector, Output>,
+    state: LogicVector,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl SisoShiftRegister {
+    /// Creates a shift register that stores `width` bits.
+    pub fn with_width(width: usize) -> Self {
+        Self {
+            serial_in: Port::default(),
+            clk: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            enable: Port::new(Ieee1164::_1),
+            serial_out: Port::default(),
+            q: Port::new(LogicVector::with_width(width)),
+            state: LogicVector::with_width(width),
+            last_clk: Ieee1164::_U,
+            _private: (),
+        }
+    }
+}
+
+impl Updateable for SisoShiftRegister {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        let q = &self.state;
+        let next = shift_in(q, self.serial_in.value());
+        self.state = advance(
+            edge,
+            q,
+            &next,
+            self.enable.value(),
+            self.reset.value(),
+            &q.filled(Ieee1164::_0),
+        );
+
+        let out_changed = drive(&mut self.serial_out, shift_out(&self.state));
+        drive(&mut self.q, self.state.clone()) || out_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.reset.id()]
+    }
+}
+
+/// A serial-in, parallel-out shift register, that converts a serial bit stream on
+/// [`SipoShiftRegister::serial_in`] into a [`LogicVector`] on [`SipoShiftRegister::q`].
+///
+/// On every rising edge of [`SipoShiftRegister::clk`] the stored bits are shifted towards the most
+/// significant bit and `serial_in` is stored in the least significant bit, i.e. the first bit
+/// that was shifted in ends up as the most significant bit.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::{circuit, signal, Circuit, Ieee1164, LogicVector, Port, Signal};
+/// use logical::direction::{Input, Output};
+/// use logical::models::rtlib::sequential::SipoShiftRegister;
+///
+/// let sipo = SipoShiftRegister::with_width(4);
+/// let mut serial_in = Port::<_, Output>::new(Ieee1164::_0);
+/// let mut clk = Port::<_, Output>::new(Ieee1164::_0);
+/// let q = Port::<_, Input>::new(LogicVector::with_width(4));
+///
+/// let mut circuit = circuit!(signal!(serial_in, sipo.serial_in), signal!(clk, sipo.clk), signal!(sipo.q, q));
+/// circuit.add(sipo);
+///
+/// circuit.step().unwrap();
+/// for bit in "1011".chars() {
+///     serial_in.replace(if bit == '1' { Ieee1164::_1 } else { Ieee1164::_0 });
+///     clk.replace(Ieee1164::_1);
+///     circuit.step().unwrap();
+///     clk.replace(Ieee1164::_0);
+///     circuit.step().unwrap();
+/// }
+/// assert_eq!("1011", q.value().to_string());
+/// ```
+#[derive(Debug, Component)]
+pub struct SipoShiftRegister {
+    /// Serial data input `Port`
+    #[port(input)]
+    pub serial_in: Port<Ieee1164, Input>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Active-high clock enable `Port`, active by default
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+    /// Parallel data output `Port`, holds the stored bits
+    #[port(output)]
+    pub q: Port<LogicVector, Output>,
+    state: LogicVector,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl SipoShiftRegister {
+    /// Creates a shift register that stores `width` bits.
+    pub fn with_width(width: usize) -> Self {
+        Self {
+            serial_in: Port::default(),
+            clk: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            enable: Port::new(Ieee1164::_1),
+            q: Port::new(LogicVector::with_width(width)),
+            state: LogicVector::with_width(width),
+            last_clk: Ieee1164::_U,
+            _private: (),
+        }
+    }
+}
+
+impl Updateable for SipoShiftRegister {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        let q = &self.state;
+        let next = shift_in(q, self.serial_in.value());
+        self.state = advance(
+            edge,
+            q,
+            &next,
+            self.enable.value(),
+            self.reset.value(),
+            &q.filled(Ieee1164::_0),
+        );
+        drive(&mut self.q, self.state.clone())
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.reset.id()]
+    }
+}
+
+/// A parallel-in, serial-out shift register, that converts a [`LogicVector`] on
+/// [`PisoShiftRegister::d`] into a serial bit stream on [`PisoShiftRegister::serial_out`], most
+/// significant bit first.
+///
+/// A rising edge of [`PisoShiftRegister::clk`] stores `d` if [`PisoShiftRegister::load`] is high.
+/// Otherwise the stored bits are shifted towards the most significant bit and
+/// [`PisoShiftRegister::serial_in`] is stored in the least significant bit, which allows to chain
+/// several shift registers.
+#[derive(Debug, Component)]
+pub struct PisoShiftRegister {
+    /// Parallel data input `Port`, which is stored if `load` is high
+    #[port(input)]
+    pub d: Port<LogicVector, Input>,
+    /// Serial data input `Port`, low by default
+    #[port(input)]
+    pub serial_in: Port<Ieee1164, Input>,
+    /// Clock input `Port`
+    #[port(input)]
+    pub clk: Port<Ieee1164, Input>,
+    /// Active-high asynchronous reset `Port`, inactive by default
+    #[port(input)]
+    pub reset: Port<Ieee1164, Input>,
+    /// Active-high synchronous load `Port`, inactive by default
+    #[port(input)]
+    pub load: Port<Ieee1164, Input>,
+    /// Active-high clock enable `Port`, active by default
+    #[port(input)]
+    pub enable: Port<Ieee1164, Input>,
+    /// Serial data output `Port`, the most significant bit of `q`
+    #[port(output)]
+    pub serial_out: Port<Ieee1164, Output>,
+    /// Output `Port`, holds the stored bits
+    #[port(output)]
+    pub q: Port<LogicVector, Output>,
+    state: LogicVector,
+    last_clk: Ieee1164,
+    _private: (),
+}
+
+impl PisoShiftRegister {
+    /// Creates a shift register whose parallel data input is `width` bits wide.
+    pub fn with_width(width: usize) -> Self {
+        Self {
+            d: Port::new(LogicVector::with_width(width)),
+            serial_in: Port::new(Ieee1164::_0),
+            clk: Port::default(),
+            reset: Port::new(Ieee1164::_0),
+            load: Port::new(Ieee1164::_0),
+            enable: Port::new(Ieee1164::_1),
+            serial_out: Port::default(),
+            q: Port::new(LogicVector::with_width(width)),
+            state: LogicVector::with_width(width),
+            last_clk: Ieee1164::_U,
+            _private: (),
+        }
+    }
+}
+
+impl Updateable for PisoShiftRegister {
+    fn update(&mut self) -> bool {
+        let clk = self.clk.value();
+        let edge = Edge::detect(self.last_clk, clk);
+        self.last_clk = clk;
+
+        let q = &self.state;
+        let d = checked(self.d.value(), q.width());
+        let next = select(self.load.value(), &shift_in(q, self.serial_in.value()), &d);
+        self.state = advance(
+            edge,
+            q,
+            &next,
+            self.enable.value(),
+            self.reset.value(),
+            &q.filled(Ieee1164::_0),
+        );
+
+        let out_changed = drive(&mut self.serial_out, shift_out(&self.state));
+        drive(&mut self.q, self.state.clone()) || out_changed
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.input_ids()
+    }
+
+    fn drives(&self) -> Vec<PortId> {
+        self.output_ids()
+    }
+
+    fn combinational_inputs(&self) -> Vec<PortId> {
+        vec![self.clk.id(), self.reset.id()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Circuit, Signal};
+
+    const _0: Ieee1164 = Ieee1164::_0;
+    const _1: Ieee1164 = Ieee1164::_1;
+
+    fn cycle(circuit: &mut Circuit, clk: &mut Port<Ieee1164, Output>) {
+        clk.replace(_1);
+        circuit.step().unwrap();
+        clk.replace(_0);
+        circuit.step().unwrap();
+    }
+
+    #[test]
+    fn parallel_to_serial_and_back() {
+        let piso = PisoShiftRegister::with_width(4);
+        let siso = SisoShiftRegister::with_width(2);
+        let sipo = SipoShiftRegister::with_width(4);
+        let d = Port::<_, Output>::new(LogicVector::from_int(0b1101, 4).unwrap());
+        let mut clk = Port::<_, Output>::new(_0);
+        let mut reset = Port::<_, Output>::new(_1);
+        let mut load = Port::<_, Output>::new(_1);
+        let serial = Port::<_, Input>::default();
+        let q = Port::<_, Input>::new(LogicVector::with_width(4));
+
+        let mut circuit = circuit!(
+            signal!(d, piso.d),
+            signal!(clk, piso.clk, siso.clk, sipo.clk),
+            signal!(reset, piso.reset, siso.reset, sipo.reset),
+            signal!(load, piso.load),
+            signal!(piso.serial_out, siso.serial_in),
+            signal!(siso.serial_out, sipo.serial_in, serial),
+            signal!(sipo.q, q)
+        );
+        circuit.add(piso);
+        circuit.add(siso);
+        circuit.add(sipo);
+        circuit.step().unwrap();
+        assert_eq!("0000", q.value().to_string());
+
+        reset.replace(_0);
+        cycle(&mut circuit, &mut clk);
+        load.replace(_0);
+
+        // the bits are delayed by the two stages of the serial-in, serial-out register
+        let mut bits = vec![];
+        for _ in 0..6 {
+            bits.push(serial.value());
+            cycle(&mut circuit, &mut clk);
+        }
+        assert_eq!(vec![_0, _0, _1, _1, _0, _1], bits);
+        assert_eq!("1101", q.value().to_string());
+    }
+}
@@ -1,4 +1,7 @@
-use std::sync::RwLock;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{RwLock, Weak};
 
 mod portconnector;
 mod portdirection;
@@ -15,4 +18,81 @@ pub use self::pport::Port;
 pub(crate) struct InnerPort<T> {
     value: RwLock<T>,
     signal: WeakSignal<T>,
+    generation: AtomicUsize,
+}
+
+impl<T> InnerPort<T> {
+    /// Marks the value of this port as changed.
+    fn touch(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Calls `f` with the value regardless of the direction of the port.
+    pub(crate) fn with_value<R, F: FnOnce(&T) -> R>(&self, f: F) -> R {
+        f(&self.value.read().unwrap())
+    }
+}
+
+impl<T: Clone> InnerPort<T> {
+    /// Reads the value regardless of the direction of the port.
+    pub(crate) fn peek(&self) -> T {
+        self.value.read().unwrap().clone()
+    }
+}
+
+/// Type-erased view on an [`InnerPort`], so a [`PortId`] does not need to know about `T`.
+pub(crate) trait AnyPort {
+    fn generation(&self) -> usize;
+}
+
+impl<T> AnyPort for InnerPort<T> {
+    fn generation(&self) -> usize {
+        self.generation.load(Ordering::SeqCst)
+    }
+}
+
+/// A `PortId` identifies a [`Port`] regardless of its value type and direction.
+///
+/// Two `PortId`s are equal if they were obtained from the same `Port` (or a clone of it). They are
+/// used by the [`Circuit`](crate::Circuit) to find out which [`Updateable`](crate::Updateable)s
+/// have to be re-evaluated when a value changes.
+#[derive(Clone)]
+pub struct PortId {
+    inner: Weak<dyn AnyPort>,
+}
+
+impl PortId {
+    pub(crate) fn new(inner: Weak<dyn AnyPort>) -> Self {
+        PortId { inner }
+    }
+
+    fn addr(&self) -> usize {
+        self.inner.as_ptr() as *const () as usize
+    }
+
+    /// Returns how often the value of the port has been written, or `None` if the port does not
+    /// exist anymore.
+    pub(crate) fn generation(&self) -> Option<usize> {
+        self.inner.upgrade().map(|p| p.generation())
+    }
+}
+
+impl PartialEq for PortId {
+    fn eq(&self, other: &PortId) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl Eq for PortId {}
+
+impl Hash for PortId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state)
+    }
+}
+
+impl fmt::Debug for PortId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PortId({:#x})", self.addr())
+    }
 }
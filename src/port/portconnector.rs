@@ -2,7 +2,7 @@ use std::fmt;
 use std::marker::PhantomData;
 use std::sync::{Arc, Weak};
 
-use super::InnerPort;
+use super::{AnyPort, InnerPort, PortId};
 use crate::direction::{Dir, InOut, Input, MaybeRead, MaybeWrite, Output, PortDirection, Read, Write};
 use crate::Port;
 use std::convert::TryFrom;
@@ -46,6 +46,18 @@ impl<T, D: PortDirection> PortConnector<T, D> {
     pub fn is_valid(&self) -> bool {
         self.inner.upgrade().is_some()
     }
+
+    /// Calls `f` with the value of the connected port, regardless of the direction.
+    pub fn with_value<R, F: FnOnce(&T) -> R>(&self, f: F) -> Option<R> {
+        self.inner.upgrade().map(|i| i.with_value(f))
+    }
+}
+
+impl<T: 'static, D: PortDirection> PortConnector<T, D> {
+    pub fn id(&self) -> PortId {
+        let weak = self.inner.clone();
+        PortId::new(weak as Weak<dyn AnyPort>)
+    }
 }
 
 impl<T, W> PortConnector<T, Dir<Read, W>>
@@ -64,10 +76,20 @@ where
     R: MaybeRead,
     Dir<R, Write>: PortDirection,
 {
-    pub fn set_value(&mut self, value: T) {
+    /// Writes `value` to the connected port. Returns `true` if the value of the port has changed.
+    pub fn set_value(&mut self, value: T) -> bool
+    where
+        T: PartialEq,
+    {
         if let Some(port) = self.to_port() {
-            *port.inner.value.write().unwrap() = value;
+            let mut guard = port.inner.value.write().unwrap();
+            if *guard != value {
+                *guard = value;
+                port.inner.touch();
+                return true;
+            }
         }
+        false
     }
 }
 
@@ -1,12 +1,11 @@
 use std::marker::PhantomData;
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::AtomicUsize;
+use std::sync::{Arc, RwLock, Weak};
 
-use super::InnerPort;
+use super::{AnyPort, InnerPort, PortId};
 
 use crate::direction::{Dir, MaybeRead, MaybeWrite, PortDirection, Read, Write};
-use crate::dump::IterValues;
 use crate::signal::WeakSignal;
-use crate::Ieee1164;
 
 #[allow(unused_imports)]
 use crate::direction::{InOut, Input, Output};
@@ -71,6 +70,7 @@ impl<T, D: PortDirection> Port<T, D> {
             inner: Arc::new(InnerPort {
                 value: RwLock::new(value),
                 signal: WeakSignal::default(),
+                generation: AtomicUsize::new(0),
             }),
             _marker: PhantomData,
         }
@@ -115,6 +115,22 @@ where
     }
 }
 
+impl<T: 'static, D: PortDirection> Port<T, D> {
+    /// Returns the [`PortId`] of this `Port`. Clones of a `Port` share the same id.
+    ///
+    /// ```rust
+    /// # use logical::{Ieee1164, Port};
+    /// # use logical::direction::Output;
+    /// let port = Port::<Ieee1164, Output>::default();
+    /// assert_eq!(port.id(), port.clone().id());
+    /// assert_ne!(port.id(), Port::<Ieee1164, Output>::default().id());
+    /// ```
+    pub fn id(&self) -> PortId {
+        let weak: Weak<InnerPort<T>> = Arc::downgrade(&self.inner);
+        PortId::new(weak as Weak<dyn AnyPort>)
+    }
+}
+
 impl<T, W> Port<T, Dir<Read, W>>
 where
     T: Clone,
@@ -170,6 +186,7 @@ where
     /// port.replace(9u32);
     /// ```
     pub fn replace(&mut self, value: T) -> T {
+        self.inner.touch();
         std::mem::replace(&mut self.inner.value.write().unwrap(), value)
     }
 
@@ -188,7 +205,11 @@ where
     /// });
     /// ```
     pub fn with_value_mut<F: FnOnce(&mut T) -> bool>(&mut self, f: F) -> bool {
-        f(&mut self.inner.value.write().unwrap())
+        let changed = f(&mut self.inner.value.write().unwrap());
+        if changed {
+            self.inner.touch();
+        }
+        changed
     }
 }
 
@@ -200,18 +221,6 @@ impl<T, D: PortDirection> PartialEq for Port<T, D> {
 
 impl<T, D: PortDirection> Eq for Port<T, D> {}
 
-impl<D> IterValues for Port<Ieee1164, D>
-where
-    D: PortDirection,
-{
-    fn iter_values<F>(&self, mut f: F)
-    where
-        F: FnMut(&Ieee1164),
-    {
-        f(&self.inner.value.read().unwrap());
-    }
-}
-
 //pub trait CanConnect {
 //    fn can_connect(&self, other: &Self) -> bool;
 //}
@@ -1,14 +1,21 @@
 use std::convert::TryInto;
-use std::sync::{Arc, RwLock, Weak};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, Weak};
 
 use crate::direction::{Input, Output, PortDirection};
 use crate::port::PortConnector;
-use crate::{Port, Resolve, Updateable};
+use crate::time::{Transition, Waveform};
+use crate::{Delay, Port, PortId, Resolve, Time, Updateable, Width};
 
 #[derive(Debug)]
 struct InnerSignal<T> {
     input_ports: RwLock<Vec<PortConnector<T, Input>>>,
     output_ports: RwLock<Vec<PortConnector<T, Output>>>,
+    /// Incremented on every change of `input_ports` or `output_ports`
+    wiring: AtomicUsize,
+    driver: Mutex<Waveform<T>>,
+    name: RwLock<Option<String>>,
 }
 
 #[derive(Debug, Clone)]
@@ -102,45 +109,97 @@ impl<T> Default for Signal<T> {
             inner: Arc::new(InnerSignal {
                 input_ports: RwLock::new(vec![]),
                 output_ports: RwLock::new(vec![]),
+                wiring: AtomicUsize::new(0),
+                driver: Mutex::new(Waveform::new(Delay::default())),
+                name: RwLock::new(None),
             }),
         }
     }
 }
 
+/// Errors that can occur when connecting a [`Port`] to a [`Signal`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConnectionError {
+    /// The `Port` is already connected to a `Signal`.
     AlreadyConnected,
+    /// The `Port` has a different [`Width`] than the ports that are already connected to the
+    /// `Signal`. The first value is the width of the `Signal`, the second one the width of the
+    /// `Port`.
     MismatchWidth(usize, usize),
 }
 
+impl fmt::Display for ConnectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConnectionError::AlreadyConnected => write!(f, "port is already connected to a signal"),
+            ConnectionError::MismatchWidth(expected, got) => {
+                write!(
+                    f,
+                    "signal is {} bit(s) wide, but the port has {} bit(s)",
+                    expected, got
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConnectionError {}
+
 impl<T> Signal<T> {
-    //    pub fn can_connect<D>(&self, port: &Port<T, D>)
-    //    where
-    //        D: PortDirection,
-    //    {
-    //        let in_guard = self.inner.input_ports.read().unwrap();
-    //        let out_guard = self.inner.input_ports.read().unwrap();
-    //
-    //        (if let Some(i) = in_guard.iter().next() {
-    //            i.can_connect(&port)
-    //        } && if let Some(o) = out_guard.iter().next() {
-    //            o.can_connect(&port);
-    //        })
-    //    }
+    /// Creates a new `Signal` with a name. The name is used e.g. in the errors of a
+    /// [`Circuit`](crate::Circuit), see [`Updateable::name`].
+    ///
+    /// ```rust
+    /// use logical::{Ieee1164, Signal, Updateable};
+    ///
+    /// let signal = Signal::<Ieee1164>::named("clk");
+    /// assert_eq!("clk", signal.name());
+    /// ```
+    pub fn named(name: &str) -> Self {
+        let mut signal = Self::default();
+        signal.set_name(name);
+        signal
+    }
 
-    /// Connects a [`Port`] to this `Signal`. A `Signal` is only connected once to the same `Port`.
-    /// If you try to connect it more than once you will get an [`ConnectionError::AlreadyConnected`]
-    /// error.
+    /// Sets the name of this `Signal`.
+    pub fn set_name(&mut self, name: &str) {
+        *self.inner.name.write().unwrap() = Some(name.to_string());
+    }
+
+    /// Creates a new `Signal` that delays every value change by `delay`, e.g. to model the
+    /// propagation delay of a long wire.
     ///
-    /// For an example see the [`Signal`] documentation.
-    pub fn connect<D>(&mut self, port: &Port<T, D>) -> Result<(), ConnectionError>
+    /// ```rust
+    /// use logical::{Delay, Ieee1164, Signal};
+    ///
+    /// let signal = Signal::<Ieee1164>::with_delay(Delay::transport(5, 5));
+    /// assert_eq!(Delay::transport(5, 5), signal.delay());
+    /// ```
+    pub fn with_delay(delay: Delay) -> Self {
+        let mut signal = Self::default();
+        signal.set_delay(delay);
+        signal
+    }
+
+    /// Returns the propagation [`Delay`] of this `Signal`.
+    pub fn delay(&self) -> Delay {
+        self.inner.driver.lock().unwrap().delay()
+    }
+
+    /// Sets the propagation [`Delay`] of this `Signal`. Already scheduled changes are not affected.
+    pub fn set_delay(&mut self, delay: Delay) {
+        self.inner.driver.lock().unwrap().set_delay(delay);
+    }
+
+    /// Connects a [`Port`] to this `Signal` like [`Signal::connect`], but without checking the
+    /// [`Width`] of the `Port`. This is needed for values that do not implement `Width`.
+    pub fn connect_unchecked<D>(&mut self, port: &Port<T, D>) -> Result<(), ConnectionError>
     where
         D: PortDirection,
     {
         if port.is_connected() {
             return Err(ConnectionError::AlreadyConnected);
         }
-        // TODO: check length
 
         let mut in_guard = self.inner.input_ports.write().unwrap();
         let mut out_guard = self.inner.output_ports.write().unwrap();
@@ -159,6 +218,7 @@ impl<T> Signal<T> {
                 out_guard.push(connector);
             }
         }
+        self.inner.wiring.fetch_add(1, Ordering::SeqCst);
 
         Ok(())
     }
@@ -180,6 +240,7 @@ impl<T> Signal<T> {
             let connector = port.try_into().unwrap();
             out_guard.remove_item(&connector);
         }
+        self.inner.wiring.fetch_add(1, Ordering::SeqCst);
     }
 
     fn remove_expired_portconnector(&mut self) {
@@ -188,24 +249,89 @@ impl<T> Signal<T> {
                 let mut guard = $vec.write().unwrap();
                 guard.retain(PortConnector::is_valid);
             };
-        };
+        }
 
         filter!(self.inner.input_ports);
         filter!(self.inner.output_ports);
     }
 }
 
-impl<T> Updateable for Signal<T>
+impl<T: Width> Signal<T> {
+    /// Returns the [`Width`] of this `Signal`, which is the width of the connected [`Port`]s, or
+    /// `None` if no `Port` is connected yet.
+    ///
+    /// ```rust
+    /// use logical::direction::Output;
+    /// use logical::{LogicVector, Port, Signal};
+    ///
+    /// let port = Port::<_, Output>::new(LogicVector::with_width(8));
+    /// let mut signal = Signal::default();
+    /// assert_eq!(None, signal.width());
+    ///
+    /// signal.connect(&port).unwrap();
+    /// assert_eq!(Some(8), signal.width());
+    /// ```
+    pub fn width(&self) -> Option<usize> {
+        let in_guard = self.inner.input_ports.read().unwrap();
+        let out_guard = self.inner.output_ports.read().unwrap();
+        in_guard
+            .iter()
+            .filter_map(|pc| pc.with_value(Width::width))
+            .chain(
+                out_guard
+                    .iter()
+                    .filter_map(|pc| pc.with_value(Width::width)),
+            )
+            .next()
+    }
+
+    /// Connects a [`Port`] to this `Signal`. A `Signal` is only connected once to the same `Port`.
+    /// If you try to connect it more than once you will get an [`ConnectionError::AlreadyConnected`]
+    /// error.
+    ///
+    /// All `Port`s of a `Signal` must have the same [`Width`], otherwise a
+    /// [`ConnectionError::MismatchWidth`] error is returned.
+    ///
+    /// ```rust
+    /// use logical::direction::{Input, Output};
+    /// use logical::{ConnectionError, LogicVector, Port, Signal};
+    ///
+    /// let data = Port::<_, Output>::new(LogicVector::with_width(8));
+    /// let addr = Port::<_, Input>::new(LogicVector::with_width(10));
+    /// let mut signal = Signal::default();
+    ///
+    /// signal.connect(&data).unwrap();
+    /// assert_eq!(Err(ConnectionError::MismatchWidth(8, 10)), signal.connect(&addr));
+    /// ```
+    ///
+    /// For more examples see the [`Signal`] documentation.
+    pub fn connect<D>(&mut self, port: &Port<T, D>) -> Result<(), ConnectionError>
+    where
+        D: PortDirection,
+    {
+        if port.is_connected() {
+            return Err(ConnectionError::AlreadyConnected);
+        }
+        if let Some(expected) = self.width() {
+            let got = port.inner.with_value(Width::width);
+            if expected != got {
+                return Err(ConnectionError::MismatchWidth(expected, got));
+            }
+        }
+        self.connect_unchecked(port)
+    }
+}
+
+impl<T> Signal<T>
 where
     for<'a> &'a T: Resolve<&'a T, Output = T>,
-    T: Clone + std::fmt::Debug,
+    T: Clone + PartialEq + Transition,
 {
-    fn update(&mut self) -> bool {
+    /// Resolves the values of all driving ports, or returns `None` if there are none.
+    fn resolve(&mut self) -> Option<T> {
         self.remove_expired_portconnector();
 
-        let old_value = self.inner.output_ports.read().unwrap().clone();
-
-        let in_guard = self.inner.input_ports.write().unwrap();
+        let in_guard = self.inner.input_ports.read().unwrap();
         let mut iter = in_guard.iter();
 
         let first_port = loop {
@@ -218,21 +344,85 @@ where
             }
         };
 
-        if let Some(first) = first_port {
-            //we hold a read guard, so nobody can mutate our in/inout list, so we are free to use unwrap here
-            let r = iter
-                .filter_map(|pc| pc.value())
-                .fold(first.value().unwrap(), |e, s| e.resolve(&s));
-
-            self.inner
-                .output_ports
-                .write()
-                .unwrap()
-                .iter_mut()
-                .for_each(|p| p.set_value(r.clone()));
+        //we hold a read guard, so nobody can mutate our in/inout list, so we are free to use unwrap here
+        first_port.map(|first| {
+            iter.filter_map(|pc| pc.value())
+                .fold(first.value().unwrap(), |e, s| e.resolve(&s))
+        })
+    }
+
+    /// Writes `value` to all reading ports and returns `true` if any of them has changed.
+    fn propagate(&self, value: T) -> bool {
+        self.inner
+            .output_ports
+            .write()
+            .unwrap()
+            .iter_mut()
+            .fold(false, |changed, p| p.set_value(value.clone()) | changed)
+    }
+}
+
+impl<T> Updateable for Signal<T>
+where
+    for<'a> &'a T: Resolve<&'a T, Output = T>,
+    T: Clone + PartialEq + Transition + std::fmt::Debug + 'static,
+{
+    fn update(&mut self) -> bool {
+        if let Some(value) = self.resolve() {
+            self.inner.driver.lock().unwrap().force(value.clone());
+            self.propagate(value)
+        } else {
+            false
+        }
+    }
+
+    fn update_at(&mut self, now: Time) -> bool {
+        let resolved = self.resolve();
+        let mut driver = self.inner.driver.lock().unwrap();
+        if let Some(value) = resolved {
+            driver.assign(now, value);
         }
+        match driver.apply(now) {
+            Some(value) => self.propagate(value),
+            None => false,
+        }
+    }
+
+    fn next_event(&self) -> Option<Time> {
+        self.inner.driver.lock().unwrap().next_event()
+    }
+
+    fn name(&self) -> String {
+        self.inner
+            .name
+            .read()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(|| std::any::type_name::<Self>().to_string())
+    }
+
+    fn sensitivity(&self) -> Vec<PortId> {
+        self.inner
+            .input_ports
+            .read()
+            .unwrap()
+            .iter()
+            .map(PortConnector::id)
+            .collect()
+    }
 
-        old_value != *self.inner.output_ports.read().unwrap()
+    fn drives(&self) -> Vec<PortId> {
+        self.inner
+            .output_ports
+            .read()
+            .unwrap()
+            .iter()
+            .map(PortConnector::id)
+            .collect()
+    }
+
+    fn wiring_generation(&self) -> usize {
+        self.inner.wiring.load(Ordering::SeqCst)
     }
 }
 
@@ -240,7 +430,7 @@ where
 mod tests {
     use super::*;
     use crate::direction::InOut;
-    use crate::{Ieee1164, Port};
+    use crate::{Ieee1164, LogicVector, Port};
 
     #[test]
     fn signal_no_value_no_port() {
@@ -283,6 +473,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn signal_reports_changes() {
+        let i = Port::<_, Input>::default();
+        let mut p = Port::<_, Output>::new(Ieee1164::_1);
+        let mut s = Signal::default();
+
+        s.connect(&i).unwrap();
+        s.connect(&p).unwrap();
+        assert!(s.update());
+        assert!(!s.update());
+
+        p.replace(Ieee1164::_1);
+        assert!(!s.update());
+
+        p.replace(Ieee1164::_0);
+        assert!(s.update());
+        assert!(!s.update());
+    }
+
+    #[test]
+    fn signal_sensitivity() {
+        let i = Port::<Ieee1164, Input>::default();
+        let o = Port::<_, Output>::default();
+        let io = Port::<_, InOut>::default();
+        let mut s = Signal::default();
+
+        s.connect(&i).unwrap();
+        s.connect(&o).unwrap();
+        s.connect(&io).unwrap();
+
+        assert_eq!(vec![o.id(), io.id()], s.sensitivity());
+        assert_eq!(vec![i.id(), io.id()], s.drives());
+    }
+
     #[test]
     fn signal_after_disconnect() {
         let val_a = Ieee1164::_1;
@@ -354,4 +578,50 @@ mod tests {
         s.connect(&p).unwrap();
         assert_eq!(1, s.inner.output_ports.read().unwrap().len());
     }
+
+    #[test]
+    fn connect_without_width() {
+        let p = Port::<u8, Input>::new(0);
+        let mut s = Signal::default();
+        assert_eq!(Ok(()), s.connect_unchecked(&p));
+        assert_eq!(1, s.inner.output_ports.read().unwrap().len());
+    }
+
+    #[test]
+    fn signal_width_mismatch() {
+        let i = Port::<_, Input>::new(LogicVector::with_width(10));
+        let o = Port::<_, Output>::new(LogicVector::with_width(8));
+        let io = Port::<_, InOut>::new(LogicVector::with_width(10));
+        let mut s = Signal::default();
+
+        s.connect(&i).unwrap();
+        assert_eq!(Some(10), s.width());
+        assert_eq!(Err(ConnectionError::MismatchWidth(10, 8)), s.connect(&o));
+        assert_eq!(Ok(()), s.connect(&io));
+
+        drop(i);
+        s.disconnect(&io);
+        assert_eq!(None, s.width());
+        assert_eq!(Ok(()), s.connect(&o));
+        assert_eq!(Some(8), s.width());
+    }
+
+    #[test]
+    fn signal_vector_bus() {
+        let mut rom = Port::<_, Output>::new(LogicVector::from_int(0xAB, 8).unwrap());
+        let mut ram = Port::<_, Output>::new(LogicVector::from_ieee(Ieee1164::_Z, 8));
+        let cpu = Port::<_, Input>::new(LogicVector::with_width(8));
+        let mut s = Signal::default();
+        s.connect(&rom).unwrap();
+        s.connect(&ram).unwrap();
+        s.connect(&cpu).unwrap();
+
+        s.update();
+        assert_eq!(Some(0xAB), cpu.value().as_u128());
+
+        rom.replace(LogicVector::from_ieee(Ieee1164::_Z, 8));
+        ram.replace(LogicVector::from_int(0x12, 8).unwrap());
+        s.update();
+        assert_eq!(Some(0x12), cpu.value().as_u128());
+    }
 }
@@ -0,0 +1,298 @@
+//! This module contains everything that is needed to model simulated time, e.g. [`Delay`]s of
+//! gates or signals.
+//!
+//! All times are measured in picoseconds, which matches the `$timescale` of the
+//! [`Vcd`](crate::dump::Vcd) dumper.
+
+use std::collections::VecDeque;
+
+use crate::direction::{Dir, MaybeRead, PortDirection, Write};
+use crate::{Ieee1164, LogicVector, Port};
+
+/// A point in simulated time (or a duration) in picoseconds.
+pub type Time = u64;
+
+/// Describes what happens to a delayed value change, if an other change is scheduled before the
+/// first one became visible.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DelayKind {
+    /// Pulses that are shorter than the delay are swallowed. This is how most gates behave.
+    #[default]
+    Inertial,
+    /// Every change is propagated, regardless how short it was. This is how a wire behaves.
+    Transport,
+}
+
+/// The propagation delay of a model or a [`Signal`](crate::Signal).
+///
+/// `rise` is used for changes to a logic high, `fall` for changes to a logic low. For every other
+/// value the smaller one of both is used. A [`LogicVector`] uses `rise` if all of its changing bits
+/// rise, `fall` if all of them fall and the smaller one otherwise. The default is no delay at all.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::time::{Delay, DelayKind};
+///
+/// let delay = Delay::inertial(10, 12);
+/// assert_eq!(10, delay.rise);
+/// assert_eq!(12, delay.fall);
+/// assert_eq!(DelayKind::Inertial, delay.kind);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Delay {
+    /// Delay for a change to a logic high
+    pub rise: Time,
+    /// Delay for a change to a logic low
+    pub fall: Time,
+    /// Whether short pulses are swallowed or not
+    pub kind: DelayKind,
+}
+
+impl Delay {
+    /// Creates an [inertial](DelayKind::Inertial) delay.
+    pub fn inertial(rise: Time, fall: Time) -> Self {
+        Delay {
+            rise,
+            fall,
+            kind: DelayKind::Inertial,
+        }
+    }
+
+    /// Creates a [transport](DelayKind::Transport) delay.
+    pub fn transport(rise: Time, fall: Time) -> Self {
+        Delay {
+            rise,
+            fall,
+            kind: DelayKind::Transport,
+        }
+    }
+
+    /// Returns `true` if neither `rise` nor `fall` has a delay.
+    pub fn is_zero(&self) -> bool {
+        self.rise == 0 && self.fall == 0
+    }
+}
+
+/// Decides how long it takes for a value to become visible.
+pub trait Transition {
+    /// Returns the time it takes to change from `previous` to `self`, e.g. [`Delay::rise`] for a
+    /// logic high. `previous` is `None` if there was no value before.
+    fn delay(&self, previous: Option<&Self>, delay: &Delay) -> Time;
+}
+
+impl Transition for Ieee1164 {
+    fn delay(&self, _previous: Option<&Self>, delay: &Delay) -> Time {
+        if self.is_1H() {
+            delay.rise
+        } else if self.is_0L() {
+            delay.fall
+        } else {
+            delay.rise.min(delay.fall)
+        }
+    }
+}
+
+impl Transition for LogicVector {
+    fn delay(&self, previous: Option<&Self>, delay: &Delay) -> Time {
+        let previous = previous.filter(|p| p.width() == self.width());
+        let mut changes = (0..self.width())
+            .filter_map(|i| {
+                self.get(i)
+                    .filter(|&bit| previous.and_then(|p| p.get(i)) != Some(bit))
+            })
+            .map(|bit| bit.delay(None, delay));
+        match changes.next() {
+            Some(first) if changes.all(|d| d == first) => first,
+            _ => delay.rise.min(delay.fall),
+        }
+    }
+}
+
+/// A `Waveform` holds the value changes of an output that have been scheduled, but did not become
+/// visible yet.
+///
+/// Models that support delays assign every newly calculated value to their `Waveform` and apply
+/// the due changes to their output ports.
+///
+/// # Example
+///
+/// ```rust
+/// use logical::Ieee1164;
+/// use logical::time::{Delay, Waveform};
+///
+/// let mut wave = Waveform::new(Delay::inertial(10, 10));
+/// wave.assign(0, Ieee1164::_1);
+/// assert_eq!(None, wave.apply(5));
+/// assert_eq!(Some(10), wave.next_event());
+/// assert_eq!(Some(Ieee1164::_1), wave.apply(10));
+/// assert_eq!(None, wave.next_event());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Waveform<T> {
+    delay: Delay,
+    current: Option<T>,
+    transactions: VecDeque<(Time, T)>,
+}
+
+impl<T> Waveform<T> {
+    /// Creates an empty `Waveform` that delays every change by `delay`.
+    pub fn new(delay: Delay) -> Self {
+        Waveform {
+            delay,
+            current: None,
+            transactions: VecDeque::new(),
+        }
+    }
+
+    /// Returns the [`Delay`] of this `Waveform`.
+    pub fn delay(&self) -> Delay {
+        self.delay
+    }
+
+    /// Sets the [`Delay`] of this `Waveform`. Already scheduled changes are not affected.
+    pub fn set_delay(&mut self, delay: Delay) {
+        self.delay = delay;
+    }
+
+    /// Returns the point in time of the next scheduled change, if there is any.
+    pub fn next_event(&self) -> Option<Time> {
+        self.transactions.front().map(|t| t.0)
+    }
+
+    /// Makes `value` visible immediately and drops all scheduled changes.
+    pub fn force(&mut self, value: T) {
+        self.transactions.clear();
+        self.current = Some(value);
+    }
+}
+
+impl<T> Waveform<T>
+where
+    T: Clone + PartialEq + Transition,
+{
+    /// Schedules `value` to become visible after the delay of this `Waveform` has passed, starting
+    /// at `now`.
+    pub fn assign(&mut self, now: Time, value: T) {
+        let projected = self
+            .transactions
+            .back()
+            .map(|t| &t.1)
+            .or(self.current.as_ref());
+        if projected == Some(&value) {
+            return;
+        }
+
+        let at = now + value.delay(projected, &self.delay);
+        match self.delay.kind {
+            DelayKind::Inertial => self.transactions.clear(),
+            DelayKind::Transport => self.transactions.retain(|t| t.0 < at),
+        }
+
+        let projected = self
+            .transactions
+            .back()
+            .map(|t| &t.1)
+            .or(self.current.as_ref());
+        if projected != Some(&value) {
+            self.transactions.push_back((at, value));
+        }
+    }
+
+    /// Removes every change that is due at `now` and returns the value that is visible from now on,
+    /// or `None` if nothing has changed.
+    pub fn apply(&mut self, now: Time) -> Option<T> {
+        let mut changed = false;
+        while self.next_event().is_some_and(|t| t <= now) {
+            self.current = self.transactions.pop_front().map(|t| t.1);
+            changed = true;
+        }
+        if changed {
+            self.current.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Assigns `value` at `now` (see [`Waveform::assign`]) and writes the value that becomes
+    /// visible to `port`.
+    ///
+    /// Returns `true` if the value of `port` has changed.
+    pub fn drive<R>(&mut self, now: Time, value: T, port: &mut Port<T, Dir<R, Write>>) -> bool
+    where
+        R: MaybeRead,
+        Dir<R, Write>: PortDirection,
+    {
+        self.assign(now, value);
+        match self.apply(now) {
+            Some(new_value) => port.replace(new_value.clone()) != new_value,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_delay_is_immediate() {
+        let mut wave = Waveform::new(Delay::default());
+        wave.assign(7, Ieee1164::_0);
+        assert_eq!(Some(Ieee1164::_0), wave.apply(7));
+        assert_eq!(None, wave.apply(7));
+    }
+
+    #[test]
+    fn rise_and_fall() {
+        let mut wave = Waveform::new(Delay::transport(3, 5));
+        wave.assign(0, Ieee1164::_1);
+        wave.assign(10, Ieee1164::_0);
+        wave.assign(20, Ieee1164::_X);
+        assert_eq!(Some(3), wave.next_event());
+        assert_eq!(Some(Ieee1164::_1), wave.apply(3));
+        assert_eq!(Some(15), wave.next_event());
+        assert_eq!(Some(Ieee1164::_0), wave.apply(15));
+        assert_eq!(Some(23), wave.next_event());
+    }
+
+    #[test]
+    fn vector_rise_and_fall() {
+        let lv = |s: &str| s.parse::<LogicVector>().unwrap();
+        let delay = Delay::transport(3, 5);
+        assert_eq!(3, lv("0110").delay(Some(&lv("0100")), &delay));
+        assert_eq!(5, lv("0100").delay(Some(&lv("0110")), &delay));
+        assert_eq!(3, lv("0110").delay(Some(&lv("1001")), &delay));
+        assert_eq!(3, lv("01X0").delay(Some(&lv("0100")), &delay));
+        assert_eq!(5, lv("0000").delay(None, &delay));
+        assert_eq!(3, lv("0101").delay(None, &delay));
+    }
+
+    #[test]
+    fn inertial_swallows_glitches() {
+        let mut wave = Waveform::new(Delay::inertial(10, 10));
+        wave.force(Ieee1164::_0);
+        wave.assign(0, Ieee1164::_1);
+        wave.assign(5, Ieee1164::_0);
+        assert_eq!(None, wave.next_event());
+        assert_eq!(None, wave.apply(20));
+    }
+
+    #[test]
+    fn transport_keeps_glitches() {
+        let mut wave = Waveform::new(Delay::transport(10, 10));
+        wave.force(Ieee1164::_0);
+        wave.assign(0, Ieee1164::_1);
+        wave.assign(5, Ieee1164::_0);
+        assert_eq!(Some(Ieee1164::_1), wave.apply(10));
+        assert_eq!(Some(Ieee1164::_0), wave.apply(15));
+    }
+
+    #[test]
+    fn same_value_does_not_reschedule() {
+        let mut wave = Waveform::new(Delay::inertial(10, 10));
+        wave.assign(0, Ieee1164::_1);
+        wave.assign(5, Ieee1164::_1);
+        assert_eq!(Some(10), wave.next_event());
+    }
+}